@@ -31,7 +31,7 @@ fn create_builder_build_field(field: &Field) -> Option<proc_macro2::TokenStream>
         } else {
             Some(quote! {
                 #field_ident: self.#field_ident
-                    .ok_or_else(|| crate::prelude::parse::Error::MissingAttribute(stringify!(#field_ident)))?,
+                    .ok_or_else(|| crate::prelude::parse::Error::MissingAttribute(stringify!(#field_ident), span.clone()))?,
             })
         }
     } else {
@@ -48,7 +48,7 @@ fn create_builder_insert_field(field: &Field) -> Option<proc_macro2::TokenStream
         };
         Some(quote! {
             #attribute => {
-                self.#field_ident = Some(value.to_string());
+                self.#field_ident = Some(crate::prelude::parse::attribute_value(value));
             }
         })
     } else {
@@ -81,7 +81,7 @@ fn create_builder(ast: &DeriveInput) -> proc_macro2::TokenStream {
         }
 
         impl #builder_ident {
-            fn build(self) -> Result<#ident, crate::prelude::parse::Error> {
+            fn build(self, span: &crate::prelude::parse::Span) -> Result<#ident, crate::prelude::parse::Error> {
                 Ok(#ident {
                     #(#build_fields)*
                 })
@@ -90,7 +90,7 @@ fn create_builder(ast: &DeriveInput) -> proc_macro2::TokenStream {
             fn insert<'a>(&mut self, name: xmlparser::StrSpan<'a>, value: xmlparser::StrSpan<'a>) -> Result<(), crate::prelude::parse::Error> {
                 match name.as_str() {
                     #(#insert_fields)*
-                    _ => return Err(crate::prelude::parse::Error::UnexpectedAttribute(name.start())),
+                    _ => return Err(crate::prelude::parse::Error::UnexpectedAttribute(name.into())),
                 };
                 Ok(())
             }
@@ -58,7 +58,7 @@ fn create_attribute_build(ast: &DeriveInput) -> proc_macro2::TokenStream {
             attributes: self.attributes,
         },
         AttributesKind::Struct(_) => quote! {
-            attributes: self.attributes.build()?,
+            attributes: self.attributes.build(&self.span)?,
         },
     }
 }
@@ -72,7 +72,7 @@ fn create_parse_attribute(ast: &DeriveInput) -> proc_macro2::TokenStream {
                 name: xmlparser::StrSpan<'a>,
                 value: xmlparser::StrSpan<'a>,
             ) -> Result<(), crate::prelude::parse::Error> {
-                self.attributes.insert(name.to_string(), value.to_string());
+                self.attributes.insert(name.to_string(), crate::prelude::parse::attribute_value(value));
                 Ok(())
             }
         },
@@ -158,7 +158,7 @@ fn create_parse_child_element(ast: &DeriveInput, opts: &Opts) -> proc_macro2::To
                 tokenizer: &mut xmlparser::Tokenizer<'a>,
             ) -> Result<(), crate::prelude::parse::Error> {
                 use crate::prelude::parse::Parsable;
-                self.children.push(<#ty>::parse(tag, tokenizer, self.opts.clone())?);
+                self.children.push(<#ty>::parse(tag, tokenizer, self.ctx.clone())?);
                 Ok(())
             }
         },
@@ -189,15 +189,17 @@ pub fn derive(input: TokenStream) -> TokenStream {
     quote! {
         #[derive(Debug)]
         struct #parser_ident {
-            opts: std::rc::Rc<crate::prelude::parse::ParserOptions>,
+            ctx: std::rc::Rc<crate::prelude::parse::ParserContext>,
+            span: crate::prelude::parse::Span,
             #attributes
             #children
         }
 
         impl #parser_ident {
-            fn new(opts: std::rc::Rc<crate::prelude::parse::ParserOptions>) -> Self {
+            fn new(ctx: std::rc::Rc<crate::prelude::parse::ParserContext>, span: crate::prelude::parse::Span) -> Self {
                 Self {
-                    opts,
+                    ctx,
+                    span,
                     #attributes_new
                     #children_new
                 }
@@ -221,9 +223,9 @@ pub fn derive(input: TokenStream) -> TokenStream {
         }
 
         impl crate::prelude::parse::Parsable for #origin_ident {
-            fn parse(_tag: xmlparser::StrSpan, tokenizer: &mut xmlparser::Tokenizer, opts: std::rc::Rc<crate::prelude::parse::ParserOptions>) -> Result<Self, crate::prelude::parse::Error> {
+            fn parse(tag: xmlparser::StrSpan, tokenizer: &mut xmlparser::Tokenizer, ctx: std::rc::Rc<crate::prelude::parse::ParserContext>) -> Result<Self, crate::prelude::parse::Error> {
                 use crate::prelude::parse::Parser;
-                #parser_ident::new(opts).parse(tokenizer)?.build()
+                #parser_ident::new(ctx.clone(), tag.into()).parse(tokenizer, &ctx)?.build()
             }
         }
     }
@@ -75,7 +75,7 @@ impl Generator {
                         crate::prelude::print::open(#tag_name, #attrs, true, pretty, level, indent_size)
                     } else {
                         let mut res = crate::prelude::print::open(#tag_name, #attrs, false, pretty, level, indent_size);
-                        res.push_str(&self.children);
+                        res.push_str(&crate::helper::escape::escape_text(&self.children));
                         res.push_str(&crate::prelude::print::close(#tag_name, pretty, level, indent_size));
                         res
                     }
@@ -87,7 +87,7 @@ impl Generator {
                         crate::prelude::print::open(#tag_name, #attrs, true, pretty, level, indent_size)
                     } else {
                         let mut res = crate::prelude::print::open(#tag_name, #attrs, false, false, level, indent_size);
-                        res.push_str(&self.children);
+                        res.push_str(&crate::helper::escape::escape_text(&self.children));
                         res.push_str(&crate::prelude::print::close(#tag_name, false, level, indent_size));
                         if pretty {
                             crate::prelude::print::indent(level, indent_size, res)
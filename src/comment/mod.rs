@@ -4,11 +4,13 @@ mod json;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 #[cfg(feature = "json")]
 const NAME: &str = "comment";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "json", derive(mrml_json_macros::MrmlJsonComponent))]
 #[cfg_attr(feature = "json", mrml_json(tag = "NAME"))]
 pub struct Comment {
@@ -0,0 +1,6 @@
+use super::Comment;
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for Comment {
+    fn validate_with(&self, _validator: &mut Validator) {}
+}
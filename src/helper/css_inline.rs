@@ -0,0 +1,355 @@
+//! Post-render stage that applies the stylesheets coming from `<mj-style inline="inline">`
+//! to the `style` attribute of the matching elements of the generated html.
+//!
+//! Only the elements in the `body` are considered and the elements hidden in
+//! the downlevel-hidden conditional comments (like `<!--[if mso]>`) are ignored.
+//! Rules using pseudo classes or at-rules (like `@media`) can't be inlined and are skipped.
+
+use htmlparser::{ElementEnd, Token, Tokenizer};
+use simplecss::{AttributeOperator, Declaration, DeclarationTokenizer, PseudoClass, StyleSheet};
+use std::ops::Range;
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+fn is_void_element(name: &str) -> bool {
+    VOID_ELEMENTS
+        .iter()
+        .any(|item| item.eq_ignore_ascii_case(name))
+}
+
+struct Element<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+    parent: Option<usize>,
+    prev_sibling: Option<usize>,
+    in_body: bool,
+    /// Position of the value of the `style` attribute, if any.
+    style: Option<Range<usize>>,
+    /// Position where a `style` attribute can be added.
+    insert_at: usize,
+}
+
+impl<'a> Element<'a> {
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+}
+
+#[derive(Default)]
+struct Document<'a> {
+    elements: Vec<Element<'a>>,
+}
+
+impl<'a> Document<'a> {
+    /// Builds the tree of elements out of the html. In case of a tokenizer error,
+    /// the elements read up to that point are kept.
+    fn parse(html: &'a str) -> Self {
+        let mut elements: Vec<Element<'a>> = Vec::new();
+        // currently open elements
+        let mut stack: Vec<usize> = Vec::new();
+        // last element seen for each level of the stack, the root level included
+        let mut last_children: Vec<Option<usize>> = vec![None];
+        // whether the opened conditional comments are hiding their content
+        let mut conditions: Vec<bool> = Vec::new();
+        let mut current: Option<usize> = None;
+
+        for token in Tokenizer::from(html) {
+            let token = match token {
+                Ok(value) => value,
+                Err(_) => break,
+            };
+            let hidden = conditions.iter().any(|item| *item);
+            match token {
+                Token::ConditionalCommentStart { span, .. } => {
+                    conditions.push(!span.as_str().ends_with("<!-->"));
+                }
+                Token::ConditionalCommentEnd { .. } => {
+                    conditions.pop();
+                }
+                Token::ElementStart { local, .. } if !hidden => {
+                    let index = elements.len();
+                    let parent = stack.last().copied();
+                    let in_body = local.as_str().eq_ignore_ascii_case("body")
+                        || parent.map(|p| elements[p].in_body).unwrap_or(false);
+                    let prev_sibling = last_children
+                        .last_mut()
+                        .and_then(|last| last.replace(index));
+                    elements.push(Element {
+                        name: local.as_str(),
+                        attributes: Vec::new(),
+                        parent,
+                        prev_sibling,
+                        in_body,
+                        style: None,
+                        insert_at: local.end(),
+                    });
+                    current = Some(index);
+                }
+                Token::Attribute {
+                    local, value, span, ..
+                } => {
+                    if let Some(element) = current.and_then(|index| elements.get_mut(index)) {
+                        if local.as_str().eq_ignore_ascii_case("style") {
+                            element.style = Some(value.range());
+                        }
+                        element.attributes.push((local.as_str(), value.as_str()));
+                        element.insert_at = span.end();
+                    }
+                }
+                Token::ElementEnd { end, .. } => match end {
+                    ElementEnd::Open => {
+                        if let Some(index) = current.take() {
+                            if !is_void_element(elements[index].name) {
+                                stack.push(index);
+                                last_children.push(None);
+                            }
+                        }
+                    }
+                    ElementEnd::Empty => {
+                        current = None;
+                    }
+                    ElementEnd::Close(_, local) if !hidden => {
+                        if let Some(position) = stack
+                            .iter()
+                            .rposition(|index| elements[*index].name.eq_ignore_ascii_case(&local))
+                        {
+                            stack.truncate(position);
+                            last_children.truncate(position + 1);
+                        }
+                    }
+                    ElementEnd::Close(_, _) => {}
+                },
+                _ => {}
+            }
+        }
+
+        Self { elements }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Node<'d, 'a> {
+    document: &'d Document<'a>,
+    index: usize,
+}
+
+impl<'d, 'a> Node<'d, 'a> {
+    fn element(&self) -> &'d Element<'a> {
+        &self.document.elements[self.index]
+    }
+
+    fn with_index(&self, index: Option<usize>) -> Option<Self> {
+        index.map(|index| Self {
+            document: self.document,
+            index,
+        })
+    }
+}
+
+impl<'d, 'a> simplecss::Element for Node<'d, 'a> {
+    fn parent_element(&self) -> Option<Self> {
+        self.with_index(self.element().parent)
+    }
+
+    fn prev_sibling_element(&self) -> Option<Self> {
+        self.with_index(self.element().prev_sibling)
+    }
+
+    fn has_local_name(&self, name: &str) -> bool {
+        self.element().name.eq_ignore_ascii_case(name)
+    }
+
+    fn attribute_matches(&self, local_name: &str, operator: AttributeOperator<'_>) -> bool {
+        self.element()
+            .attribute(local_name)
+            .map(|value| operator.matches(value))
+            .unwrap_or(false)
+    }
+
+    fn pseudo_class_matches(&self, class: PseudoClass<'_>) -> bool {
+        match class {
+            PseudoClass::FirstChild => self.element().prev_sibling.is_none(),
+            // dynamic pseudo classes can't be expressed in a style attribute
+            _ => false,
+        }
+    }
+}
+
+/// Adds the declaration, the last one winning unless the existing one is important.
+fn merge<'a>(result: &mut Vec<Declaration<'a>>, item: Declaration<'a>) {
+    if let Some(position) = result.iter().position(|d| d.name == item.name) {
+        if result[position].important && !item.important {
+            return;
+        }
+        result.remove(position);
+    }
+    result.push(item);
+}
+
+fn serialize(declarations: &[Declaration]) -> String {
+    declarations
+        .iter()
+        .fold(String::default(), |mut res, declaration| {
+            res.push_str(declaration.name);
+            res.push(':');
+            res.push_str(&declaration.value.replace('"', "&quot;"));
+            res.push(';');
+            res
+        })
+}
+
+/// Applies the given stylesheets to the elements of the html.
+///
+/// Rules are applied by order of specificity, then by order of declaration, and the
+/// declarations already present in the `style` attributes win over the stylesheet
+/// ones unless those are `!important`.
+pub fn inline_styles<T: AsRef<str>>(html: &str, styles: &[T]) -> String {
+    let mut sheet = StyleSheet::new();
+    for style in styles.iter() {
+        sheet.parse_more(style.as_ref());
+    }
+    if sheet.rules.is_empty() {
+        return html.to_string();
+    }
+
+    let document = Document::parse(html);
+    let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+    for (index, element) in document.elements.iter().enumerate() {
+        if !element.in_body {
+            continue;
+        }
+        let node = Node {
+            document: &document,
+            index,
+        };
+        let mut declarations = Vec::new();
+        sheet
+            .rules
+            .iter()
+            .filter(|rule| rule.selector.matches(&node))
+            .flat_map(|rule| rule.declarations.iter())
+            .for_each(|declaration| merge(&mut declarations, *declaration));
+        if declarations.is_empty() {
+            continue;
+        }
+        if let Some(ref range) = element.style {
+            DeclarationTokenizer::from(&html[range.clone()])
+                .for_each(|declaration| merge(&mut declarations, declaration));
+            replacements.push((range.clone(), serialize(&declarations)));
+        } else {
+            replacements.push((
+                element.insert_at..element.insert_at,
+                format!(" style=\"{}\"", serialize(&declarations)),
+            ));
+        }
+    }
+
+    let mut result = String::with_capacity(html.len());
+    let mut cursor = 0;
+    for (range, value) in replacements.iter() {
+        result.push_str(&html[cursor..range.start]);
+        result.push_str(value);
+        cursor = range.end;
+    }
+    result.push_str(&html[cursor..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inline_styles;
+
+    #[test]
+    fn should_add_style_attribute() {
+        assert_eq!(
+            inline_styles(
+                "<html><body><p class=\"red\">Hello</p><p>World</p></body></html>",
+                &[".red { color: red; }"],
+            ),
+            "<html><body><p class=\"red\" style=\"color:red;\">Hello</p><p>World</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn should_merge_with_existing_style() {
+        assert_eq!(
+            inline_styles(
+                "<body><p class=\"red\" style=\"color:blue;font-size:12px;\">Hello</p></body>",
+                &["p { color: red; margin: 0; }"],
+            ),
+            "<body><p class=\"red\" style=\"margin:0;color:blue;font-size:12px;\">Hello</p></body>"
+        );
+    }
+
+    #[test]
+    fn should_respect_important() {
+        assert_eq!(
+            inline_styles(
+                "<body><p class=\"red\" style=\"color:blue;\">Hello</p></body>",
+                &["p.red { color: red !important; }"],
+            ),
+            "<body><p class=\"red\" style=\"color:red;\">Hello</p></body>"
+        );
+    }
+
+    #[test]
+    fn should_respect_specificity() {
+        assert_eq!(
+            inline_styles(
+                "<body><p id=\"main\" class=\"red\">Hello</p></body>",
+                &["#main { color: green; } p.red { color: red; } p { color: blue; }"],
+            ),
+            "<body><p id=\"main\" class=\"red\" style=\"color:green;\">Hello</p></body>"
+        );
+    }
+
+    #[test]
+    fn should_follow_declaration_order_with_same_specificity() {
+        assert_eq!(
+            inline_styles(
+                "<body><p class=\"a b\">Hello</p></body>",
+                &[".a { color: red; }", ".b { color: blue; }"],
+            ),
+            "<body><p class=\"a b\" style=\"color:blue;\">Hello</p></body>"
+        );
+    }
+
+    #[test]
+    fn should_handle_combinators_and_void_elements() {
+        assert_eq!(
+            inline_styles(
+                "<body><div class=\"wrapper\"><img src=\"a.png\" /><br><span>Hi</span></div><span>Out</span></body>",
+                &[".wrapper > span { color: red; } div img { border: 0; }"],
+            ),
+            "<body><div class=\"wrapper\"><img src=\"a.png\" style=\"border:0;\" /><br><span style=\"color:red;\">Hi</span></div><span>Out</span></body>"
+        );
+    }
+
+    #[test]
+    fn should_ignore_head_and_hidden_conditional_comments() {
+        assert_eq!(
+            inline_styles(
+                "<html><head><title>Hi</title></head><body><!--[if mso]><table><tr><td><![endif]--><div>Hi</div><!--[if mso]></td></tr></table><![endif]--></body></html>",
+                &["* { color: red; } td { color: blue; }"],
+            ),
+            "<html><head><title>Hi</title></head><body style=\"color:red;\"><!--[if mso]><table><tr><td><![endif]--><div style=\"color:red;\">Hi</div><!--[if mso]></td></tr></table><![endif]--></body></html>"
+        );
+    }
+
+    #[test]
+    fn should_skip_pseudo_classes_and_media_queries() {
+        assert_eq!(
+            inline_styles(
+                "<body><a href=\"#\">Hi</a></body>",
+                &["a:hover { color: red; } @media (max-width: 480px) { a { color: blue; } }"],
+            ),
+            "<body><a href=\"#\">Hi</a></body>"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Escaping of the attribute values and of the text.
+//!
+//! The attribute values are stored unescaped in the tree, the predefined xml entities being
+//! decoded when parsing. The other references, like `&nbsp;` or `&#8856;`, are kept as is so
+//! that they reach the rendered html.
+//!
+//! The text is stored as it is written in the template, being html content, so only the
+//! characters that can't be parsed back are escaped.
+
+use std::borrow::Cow;
+
+#[cfg(any(feature = "parse", feature = "print", feature = "render"))]
+const ENTITIES: [(&str, char); 5] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+];
+
+#[cfg(any(feature = "print", feature = "render"))]
+/// Returns the length of the character or entity reference at the beginning of the value,
+/// `&` and `;` included.
+pub(crate) fn reference_len(value: &str) -> Option<usize> {
+    let body = value.strip_prefix('&')?;
+    let end = body.find(';')?;
+    let name = &body[..end];
+    let valid = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(dec) = name.strip_prefix('#') {
+        !dec.is_empty() && dec.chars().all(|c| c.is_ascii_digit())
+    } else {
+        name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+    valid.then_some(end + 2)
+}
+
+#[cfg(any(feature = "print", feature = "render"))]
+/// Returns true when the value starts with a reference that is not decoded when parsing.
+fn is_kept_reference(value: &str) -> bool {
+    reference_len(value)
+        .map(|len| &value[1..len - 1])
+        .is_some_and(|name| !ENTITIES.iter().any(|(entity, _)| *entity == name))
+}
+
+#[cfg(any(feature = "print", feature = "render"))]
+/// Escapes a value to be put between double quotes. An `&` starting a reference is kept,
+/// unless it's one of the predefined xml entities, which would be decoded when parsing.
+pub fn escape_attribute(value: &str) -> Cow<'_, str> {
+    if !value.contains(['&', '"', '<']) {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len() + 8);
+    for (index, c) in value.char_indices() {
+        match c {
+            '&' if !is_kept_reference(&value[index..]) => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            '<' => result.push_str("&lt;"),
+            other => result.push(other),
+        }
+    }
+    Cow::Owned(result)
+}
+
+#[cfg(feature = "print")]
+/// Escapes a text content, which would otherwise be read as the start of an element.
+pub fn escape_text(value: &str) -> Cow<'_, str> {
+    if value.contains('<') {
+        Cow::Owned(value.replace('<', "&lt;"))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(feature = "parse")]
+/// Decodes the predefined xml entities.
+pub fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find('&') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let decoded = ENTITIES.iter().find_map(|(name, c)| {
+            rest[1..]
+                .strip_prefix(name)
+                .and_then(|tail| tail.strip_prefix(';'))
+                .map(|tail| (*c, tail))
+        });
+        match decoded {
+            Some((c, tail)) => {
+                result.push(c);
+                rest = tail;
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+#[cfg(feature = "render")]
+/// Decodes the predefined xml entities, `&nbsp;` and the character references of an html
+/// content. The other references are kept as is.
+pub fn decode_html(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find('&') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let len = reference_len(rest).unwrap_or(1);
+        let name = rest.get(1..len - 1).unwrap_or_default();
+        let decoded = if let Some(number) = name.strip_prefix('#') {
+            match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => number.parse::<u32>().ok(),
+            }
+            .and_then(char::from_u32)
+        } else if name == "nbsp" {
+            Some('\u{a0}')
+        } else {
+            ENTITIES
+                .iter()
+                .find(|(entity, _)| *entity == name)
+                .map(|(_, c)| *c)
+        };
+        match decoded {
+            Some(c) => result.push(c),
+            None => result.push_str(&rest[..len]),
+        }
+        rest = &rest[len..];
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+#[cfg(all(test, feature = "parse", any(feature = "print", feature = "render")))]
+mod tests {
+    use super::{escape_attribute, unescape};
+
+    #[test]
+    fn should_escape_attribute() {
+        assert_eq!(escape_attribute("Hello World"), "Hello World");
+        assert_eq!(
+            escape_attribute(r#"say "hi" & <bye>"#),
+            "say &quot;hi&quot; &amp; &lt;bye>"
+        );
+        assert_eq!(
+            escape_attribute("?a=1&b=2&amp;c=&#8856;&#x2295;&nbsp;&;"),
+            "?a=1&amp;b=2&amp;amp;c=&#8856;&#x2295;&nbsp;&amp;;"
+        );
+    }
+
+    #[cfg(feature = "print")]
+    #[test]
+    fn should_escape_text() {
+        assert_eq!(super::escape_text("a & b > c"), "a & b > c");
+        assert_eq!(super::escape_text("a <b> &lt;"), "a &lt;b> &lt;");
+    }
+
+    #[test]
+    fn should_unescape() {
+        assert_eq!(unescape("Hello World"), "Hello World");
+        assert_eq!(
+            unescape("&quot;hi&quot; &amp; &lt;bye&gt; &apos;"),
+            r#""hi" & <bye> '"#
+        );
+        assert_eq!(unescape("&#8856;&nbsp;&amp &"), "&#8856;&nbsp;&amp &");
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_decode_html() {
+        assert_eq!(
+            super::decode_html("a&nbsp;&amp;&#8856;&#x2295;&copy;&"),
+            "a\u{a0}&\u{2298}\u{2295}&copy;&"
+        );
+    }
+
+    #[test]
+    fn should_round_trip() {
+        for value in [
+            r#"a "b" & <c>"#,
+            "&#8856;",
+            "?a=1&b=2",
+            "'quoted'",
+            "a &amp; b",
+            "&lt;&nbsp;",
+        ] {
+            assert_eq!(unescape(&escape_attribute(value)), value);
+        }
+    }
+}
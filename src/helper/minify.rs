@@ -0,0 +1,421 @@
+//! Post-render stage that reduces the size of the generated html.
+//!
+//! The insignificant whitespaces are collapsed, the comments are removed, the stylesheets and
+//! the `style` attributes are shortened and the quotes are dropped around the simple attribute
+//! values. The downlevel-hidden conditional comments (like `<!--[if mso]>`) are kept untouched,
+//! with their content, as Outlook is far less tolerant than the other clients.
+//! In case of a tokenizer error, the rest of the html is kept as is.
+
+use crate::helper::escape::reference_len;
+use htmlparser::{ElementEnd, Token, Tokenizer};
+
+const INLINE_ELEMENTS: [&str; 24] = [
+    "a", "abbr", "b", "big", "button", "cite", "code", "del", "em", "font", "i", "img", "input",
+    "ins", "label", "mark", "q", "s", "small", "span", "strong", "sub", "sup", "u",
+];
+
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["pre", "script", "textarea", "xmp"];
+
+fn is_inline_element(name: &str) -> bool {
+    INLINE_ELEMENTS
+        .iter()
+        .any(|item| item.eq_ignore_ascii_case(name))
+}
+
+fn is_raw_text_element(name: &str) -> bool {
+    RAW_TEXT_ELEMENTS
+        .iter()
+        .any(|item| item.eq_ignore_ascii_case(name))
+}
+
+/// The values that don't need to be quoted in any email client.
+fn is_unquoted_value(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'-' | b'_' | b'.' | b'%'))
+}
+
+/// Only the ascii whitespaces are collapsed, the non breaking spaces being significant.
+fn trim(value: &str) -> &str {
+    value.trim_matches(|c: char| c.is_ascii_whitespace())
+}
+
+fn push_collapsed(buf: &mut String, value: &str) {
+    let mut space = false;
+    for c in value.chars() {
+        if c.is_ascii_whitespace() {
+            space = true;
+        } else {
+            if space && !buf.is_empty() && !buf.ends_with(' ') {
+                buf.push(' ');
+            }
+            space = false;
+            buf.push(c);
+        }
+    }
+    if space && !buf.ends_with(' ') {
+        buf.push(' ');
+    }
+}
+
+/// Returns the quote written by the token, which can be encoded in an attribute value.
+fn as_quote(token: &str) -> Option<char> {
+    match token {
+        "\"" | "&quot;" | "&#34;" | "&#x22;" => Some('"'),
+        "'" | "&apos;" | "&#39;" | "&#x27;" => Some('\''),
+        _ => None,
+    }
+}
+
+/// Splits the value on the separator, ignoring the ones in quotes or parentheses,
+/// like in `url(data:image/png;base64,...)`, and the ones ending a reference, like `&quot;`.
+fn split_outside(value: &str, separator: char) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut index = 0;
+    while let Some(c) = value[index..].chars().next() {
+        let len = match c {
+            '&' => reference_len(&value[index..]).unwrap_or(1),
+            _ => c.len_utf8(),
+        };
+        match (quote, as_quote(&value[index..index + len])) {
+            (Some(q), Some(found)) if q == found => quote = None,
+            (Some(_), _) => {}
+            (None, Some(found)) => quote = Some(found),
+            (None, None) if c == '(' => depth += 1,
+            (None, None) if c == ')' => depth = depth.saturating_sub(1),
+            (None, None) if c == separator && depth == 0 => {
+                result.push(&value[start..index]);
+                start = index + len;
+            }
+            _ => {}
+        }
+        index += len;
+    }
+    result.push(&value[start..]);
+    result
+}
+
+/// Shortens the declarations of a `style` attribute, `color: red; width: 10px;`
+/// becoming `color:red;width:10px`.
+pub fn minify_declarations(value: &str) -> String {
+    split_outside(value, ';')
+        .into_iter()
+        .filter_map(|item| {
+            let (name, value) = item.split_once(':')?;
+            let mut result = format!("{}:", trim(name));
+            push_collapsed(&mut result, trim(value));
+            Some(result)
+        })
+        .filter(|item| !item.starts_with(':') && !item.ends_with(':'))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+const SEPARATORS: [char; 6] = ['{', '}', ';', ',', '>', ':'];
+
+/// Removes the comments and the whitespaces that don't change the meaning of a stylesheet.
+pub fn minify_stylesheet(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    let mut space = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                if space && !result.is_empty() && !result.ends_with(SEPARATORS) {
+                    result.push(' ');
+                }
+                space = false;
+                result.push(c);
+                for next in chars.by_ref() {
+                    result.push(next);
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = '\0';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                space = true;
+            }
+            c if c.is_ascii_whitespace() => space = true,
+            '{' | '}' | ';' | ',' | '>' => {
+                if c == '}' && result.ends_with(';') {
+                    result.pop();
+                }
+                result.push(c);
+                space = false;
+            }
+            _ => {
+                if space && !result.is_empty() && !result.ends_with(['{', '}', ';', ',', '>', ':'])
+                {
+                    result.push(' ');
+                }
+                space = false;
+                result.push(c);
+            }
+        }
+    }
+    result
+}
+
+#[derive(Default)]
+struct Minifier {
+    output: String,
+    /// Name of the element that was last opened or closed.
+    previous: Option<String>,
+    /// Whether the previous element was an inline one or some text was written.
+    previous_inline: bool,
+    /// Whitespaces waiting for the next token to know if they are significant.
+    pending_space: bool,
+    /// Name of the element which text is written as is, like `pre`, or minified, like `style`.
+    raw: Option<String>,
+    unquoted: bool,
+}
+
+impl Minifier {
+    fn boundary(&mut self, name: Option<&str>) {
+        if self.pending_space && (self.previous_inline || name.is_some_and(is_inline_element)) {
+            self.output.push(' ');
+        }
+        self.pending_space = false;
+        self.previous_inline = name.is_some_and(is_inline_element);
+        self.previous = name.map(String::from);
+    }
+
+    fn text(&mut self, value: &str) {
+        match self.raw.as_deref() {
+            Some(name) if name.eq_ignore_ascii_case("style") => {
+                self.output.push_str(&minify_stylesheet(value))
+            }
+            Some(_) => self.output.push_str(value),
+            None if trim(value).is_empty() => self.pending_space |= !value.is_empty(),
+            None => {
+                let trimmed = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+                if (self.pending_space || trimmed.len() < value.len())
+                    && !self.output.ends_with(' ')
+                {
+                    self.output.push(' ');
+                }
+                let content = trim(trimmed);
+                push_collapsed(&mut self.output, content);
+                self.pending_space = content.len() < trimmed.len();
+                self.previous_inline = true;
+            }
+        }
+    }
+
+    fn attribute(&mut self, name: &str, value: &str, quote: char) {
+        self.output.push(' ');
+        self.output.push_str(name);
+        let value = if name.eq_ignore_ascii_case("style") {
+            minify_declarations(value)
+        } else {
+            value.to_string()
+        };
+        self.unquoted = is_unquoted_value(&value);
+        self.output.push('=');
+        if self.unquoted {
+            self.output.push_str(&value);
+        } else {
+            self.output.push(quote);
+            self.output.push_str(&value);
+            self.output.push(quote);
+        }
+    }
+
+    fn run(mut self, html: &str) -> String {
+        let mut tokens = Tokenizer::from(html);
+        // start of the downlevel-hidden conditional comment being copied, with its depth
+        let mut hidden: Option<(usize, usize)> = None;
+        let mut position = 0;
+        for token in tokens.by_ref() {
+            let token = match token {
+                Ok(value) => value,
+                Err(_) => break,
+            };
+            let span = token.span();
+            if let Some((start, depth)) = hidden {
+                match token {
+                    Token::ConditionalCommentStart { .. } => hidden = Some((start, depth + 1)),
+                    Token::ConditionalCommentEnd { .. } if depth == 0 => {
+                        self.output.push_str(&html[start..span.end()]);
+                        hidden = None;
+                    }
+                    Token::ConditionalCommentEnd { .. } => hidden = Some((start, depth - 1)),
+                    _ => {}
+                }
+                position = span.end();
+                continue;
+            }
+            match token {
+                Token::ConditionalCommentStart { span, .. } => {
+                    self.boundary(None);
+                    if span.as_str().ends_with("-->") {
+                        self.output.push_str(span.as_str());
+                    } else {
+                        hidden = Some((span.start(), 0));
+                    }
+                }
+                Token::ConditionalCommentEnd { span } => {
+                    self.boundary(None);
+                    self.output.push_str(span.as_str());
+                }
+                Token::Comment { .. } => {}
+                Token::ElementStart { prefix, local, .. } => {
+                    self.boundary(Some(local.as_str()));
+                    self.output.push('<');
+                    if !prefix.is_empty() {
+                        self.output.push_str(prefix.as_str());
+                        self.output.push(':');
+                    }
+                    self.output.push_str(local.as_str());
+                    self.unquoted = false;
+                }
+                Token::Attribute {
+                    prefix,
+                    local,
+                    value,
+                    span,
+                } => {
+                    let name = if prefix.is_empty() {
+                        local.as_str().to_string()
+                    } else {
+                        format!("{}:{}", prefix.as_str(), local.as_str())
+                    };
+                    let quote = span.as_str().chars().last().unwrap_or('"');
+                    let quote = if quote == '\'' { '\'' } else { '"' };
+                    self.attribute(&name, value.as_str(), quote);
+                }
+                Token::ElementEnd { end, .. } => match end {
+                    ElementEnd::Open => {
+                        self.output.push('>');
+                        let name = self.previous.as_deref().unwrap_or_default();
+                        if name.eq_ignore_ascii_case("style") || is_raw_text_element(name) {
+                            self.raw = Some(name.to_string());
+                        }
+                    }
+                    ElementEnd::Empty => {
+                        if self.unquoted {
+                            self.output.push(' ');
+                        }
+                        self.output.push_str("/>");
+                    }
+                    ElementEnd::Close(prefix, local) => {
+                        if self
+                            .raw
+                            .as_deref()
+                            .is_some_and(|name| name.eq_ignore_ascii_case(local.as_str()))
+                        {
+                            self.raw = None;
+                        }
+                        self.boundary(Some(local.as_str()));
+                        self.output.push_str("</");
+                        if !prefix.is_empty() {
+                            self.output.push_str(prefix.as_str());
+                            self.output.push(':');
+                        }
+                        self.output.push_str(local.as_str());
+                        self.output.push('>');
+                    }
+                },
+                Token::Text { text } => self.text(text.as_str()),
+                other => {
+                    self.boundary(None);
+                    self.output.push_str(other.span().as_str());
+                }
+            }
+            position = span.end();
+        }
+        if let Some((start, _)) = hidden {
+            position = start;
+        }
+        if position < html.len() {
+            if self.pending_space {
+                self.output.push(' ');
+            }
+            self.output.push_str(&html[position..]);
+        }
+        self.output
+    }
+}
+
+/// Minifies the html generated by the renderer.
+pub fn minify(html: &str) -> String {
+    Minifier {
+        output: String::with_capacity(html.len()),
+        ..Default::default()
+    }
+    .run(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{minify, minify_declarations, minify_stylesheet};
+
+    #[test]
+    fn should_collapse_whitespaces() {
+        assert_eq!(
+            minify("<div>\n  <p>  Hello\n   <b>World</b> !</p>\n  <!-- comment -->\n</div>"),
+            "<div><p> Hello <b>World</b> !</p></div>"
+        );
+        assert_eq!(
+            minify("<p><span>a</span>\n  <span>b</span></p>"),
+            "<p><span>a</span> <span>b</span></p>"
+        );
+        assert_eq!(minify("<pre>  a\n  b </pre>"), "<pre>  a\n  b </pre>");
+    }
+
+    #[test]
+    fn should_shorten_styles() {
+        assert_eq!(
+            minify_declarations(" color : red ;background:url(data:image/png;base64,abc);  "),
+            "color:red;background:url(data:image/png;base64,abc)"
+        );
+        assert_eq!(
+            minify_declarations(
+                "font-family: &quot;Open Sans&quot;, Arial ; font-size : 13px; content: &apos;a;b&apos;; background: url(a.png?a=1&amp;b=2) , red"
+            ),
+            "font-family:&quot;Open Sans&quot;, Arial;font-size:13px;content:&apos;a;b&apos;;background:url(a.png?a=1&amp;b=2) , red"
+        );
+        assert_eq!(
+            minify(
+                r#"<p style="font-family: &quot;Open Sans&quot;, Arial; content: a &amp; b;">x</p>"#
+            ),
+            r#"<p style="font-family:&quot;Open Sans&quot;, Arial;content:a &amp; b">x</p>"#
+        );
+        assert_eq!(
+            minify_stylesheet(
+                "\n  /* reset */\n  #outlook a { padding:0; }\n  @media only screen and (min-width:480px) {\n    .a > td, .b { width: 100% !important; }\n  }\n"
+            ),
+            "#outlook a{padding:0}@media only screen and (min-width:480px){.a>td,.b{width:100% !important}}"
+        );
+    }
+
+    #[test]
+    fn should_drop_optional_quotes() {
+        assert_eq!(
+            minify(r#"<td align="center" style="color:red;" class="a b"><img width="600" /></td>"#),
+            r#"<td align=center style="color:red" class="a b"><img width=600 /></td>"#
+        );
+    }
+
+    #[test]
+    fn should_keep_conditional_comments() {
+        let html = "<div>\n<!--[if mso | IE]><table align=\"center\">\n  <tr><td><![endif]-->\n<div>Hello</div>\n<!--[if mso | IE]></td></tr></table><![endif]-->\n<!--[if !mso]><!--> <b>x</b> <!--<![endif]--></div>";
+        assert_eq!(
+            minify(html),
+            "<div><!--[if mso | IE]><table align=\"center\">\n  <tr><td><![endif]--><div>Hello</div><!--[if mso | IE]></td></tr></table><![endif]--><!--[if !mso]><!--> <b>x</b> <!--<![endif]--></div>"
+        );
+    }
+}
@@ -1,10 +1,17 @@
 #[cfg(any(test, feature = "render"))]
 pub mod condition;
 #[cfg(feature = "render")]
-pub mod random;
+pub mod css_inline;
+#[cfg(any(feature = "parse", feature = "print", feature = "render"))]
+pub mod escape;
+#[cfg(feature = "render")]
+pub mod minify;
 #[cfg(feature = "render")]
+pub mod random;
+#[cfg(any(feature = "render", feature = "validate"))]
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
 pub mod size;
-#[cfg(any(feature = "render", feature = "print"))]
+#[cfg(any(feature = "render", feature = "print", feature = "validate"))]
 pub mod sort;
 #[cfg(feature = "render")]
 pub mod spacing;
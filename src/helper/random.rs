@@ -1,16 +1,157 @@
+use crate::helper::sort::sort_by_key;
+use crate::prelude::hash::Map;
+use crate::prelude::render::{IdContext, IdStrategy};
 use rand::distributions::Alphanumeric;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
 use std::iter;
 
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn sample<R: Rng>(rng: &mut R, size: usize) -> String {
+    iter::repeat(())
+        .map(|()| rng.sample(Alphanumeric))
+        .map(char::from)
+        .take(size)
+        .collect::<String>()
+}
+
 pub fn generate(size: usize) -> String {
     if cfg!(test) {
         "a".repeat(size)
     } else {
-        let mut rng = thread_rng();
-        iter::repeat(())
-            .map(|()| rng.sample(Alphanumeric))
-            .map(char::from)
-            .take(size)
-            .collect::<String>()
+        sample(&mut thread_rng(), size)
+    }
+}
+
+/// FNV-1a, which result doesn't depend on the platform nor on the version of Rust.
+fn fnv1a(values: &[&[u8]]) -> u64 {
+    values
+        .iter()
+        .flat_map(|value| value.iter().chain(iter::once(&0xff)))
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Stable identifier made of alphanumeric characters, derived from a hash with splitmix64.
+fn from_hash(mut state: u64, size: usize) -> String {
+    iter::repeat_with(|| {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut value = state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^= value >> 31;
+        char::from(ALPHANUMERIC[(value % ALPHANUMERIC.len() as u64) as usize])
+    })
+    .take(size)
+    .collect()
+}
+
+/// Lists the attributes, sorted by name, to identify the content of an element.
+pub fn attributes_key(attributes: &Map<String, String>) -> String {
+    let mut entries = attributes.iter().collect::<Vec<_>>();
+    entries.sort_by(sort_by_key);
+    entries
+        .into_iter()
+        .map(|(key, value)| format!("{key}={value};"))
+        .collect()
+}
+
+/// Generates the identifiers of the elements of a template, following the [`IdStrategy`]
+/// of the render options.
+pub struct IdGenerator {
+    strategy: IdStrategy,
+    rng: Option<StdRng>,
+    count: usize,
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        Self::new(&IdStrategy::Random)
+    }
+}
+
+impl IdGenerator {
+    pub fn new(strategy: &IdStrategy) -> Self {
+        let rng = match strategy {
+            IdStrategy::Seeded(seed) => Some(StdRng::seed_from_u64(*seed)),
+            _ => None,
+        };
+        Self {
+            strategy: strategy.clone(),
+            rng,
+            count: 0,
+        }
+    }
+
+    pub fn generate(&mut self, tag: &str, content: &str, size: usize) -> String {
+        let index = self.count;
+        self.count += 1;
+        match (&self.strategy, self.rng.as_mut()) {
+            (IdStrategy::Seeded(_), Some(rng)) => sample(rng, size),
+            (IdStrategy::ContentHash, _) => {
+                let index = index.to_string();
+                let hash = fnv1a(&[tag.as_bytes(), index.as_bytes(), content.as_bytes()]);
+                from_hash(hash, size)
+            }
+            (IdStrategy::Custom(func), _) => func(&IdContext {
+                tag,
+                index,
+                content,
+                size,
+            }),
+            _ => generate(size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdGenerator;
+    use crate::prelude::render::IdStrategy;
+    use std::sync::Arc;
+
+    #[test]
+    fn should_generate_same_ids_with_seed() {
+        let mut first = IdGenerator::new(&IdStrategy::Seeded(42));
+        let mut second = IdGenerator::new(&IdStrategy::Seeded(42));
+        let ids = (0..3)
+            .map(|_| first.generate("mj-navbar", "", 8))
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 3);
+        assert_ne!(ids[0], ids[1]);
+        for id in ids {
+            assert_eq!(id.len(), 8);
+            assert_eq!(id, second.generate("mj-navbar", "", 8));
+        }
+        let mut other = IdGenerator::new(&IdStrategy::Seeded(43));
+        assert_ne!(
+            other.generate("mj-navbar", "", 8),
+            IdGenerator::new(&IdStrategy::Seeded(42)).generate("mj-navbar", "", 8)
+        );
+    }
+
+    #[test]
+    fn should_hash_content_and_position() {
+        let mut generator = IdGenerator::new(&IdStrategy::ContentHash);
+        let first = generator.generate("mj-carousel", "src=a.png;", 8);
+        let second = generator.generate("mj-carousel", "src=a.png;", 8);
+        assert_ne!(first, second);
+        assert!(first.chars().all(|c| c.is_ascii_alphanumeric()));
+        let mut generator = IdGenerator::new(&IdStrategy::ContentHash);
+        assert_eq!(first, generator.generate("mj-carousel", "src=a.png;", 8));
+        let mut generator = IdGenerator::new(&IdStrategy::ContentHash);
+        assert_ne!(first, generator.generate("mj-carousel", "src=b.png;", 8));
+        // the hash doesn't change from a version to another
+        assert_eq!(first, "jNqDI51m");
+    }
+
+    #[test]
+    fn should_call_custom_function() {
+        let strategy = IdStrategy::Custom(Arc::new(|ctx| format!("{}-{}", ctx.tag, ctx.index)));
+        let mut generator = IdGenerator::new(&strategy);
+        assert_eq!(generator.generate("mj-navbar", "", 8), "mj-navbar-0");
+        assert_eq!(generator.generate("mj-carousel", "", 8), "mj-carousel-1");
     }
 }
@@ -1,3 +1,4 @@
+use crate::helper::escape::escape_attribute;
 use crate::prelude::hash::{Map, Set};
 use std::borrow::Cow;
 
@@ -118,7 +119,7 @@ impl Tag {
             res.push(' ');
             res.push_str(key);
             res.push_str("=\"");
-            res.push_str(value);
+            res.push_str(&escape_attribute(value));
             res.push('"');
         }
         if !self.classes.is_empty() {
@@ -127,7 +128,7 @@ impl Tag {
                 if index > 0 {
                     res.push(' ');
                 }
-                res.push_str(classname);
+                res.push_str(&escape_attribute(classname));
             }
             res.push('"');
         }
@@ -136,7 +137,7 @@ impl Tag {
             for (key, value) in self.styles.iter() {
                 res.push_str(key);
                 res.push(':');
-                res.push_str(value);
+                res.push_str(&escape_attribute(value));
                 res.push(';');
             }
             res.push('"');
@@ -160,3 +161,20 @@ impl Tag {
         self.open() + input.as_ref() + &self.close()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Tag;
+
+    #[test]
+    fn should_escape_attributes() {
+        let tag = Tag::new("a")
+            .add_attribute("href", "https://host/?a=1&b=\"2\"&c=3&#8856;")
+            .add_class("a&b")
+            .add_style("font-family", "\"Open Sans\"");
+        assert_eq!(
+            tag.closed(),
+            "<a href=\"https://host/?a=1&amp;b=&quot;2&quot;&amp;c=3&#8856;\" class=\"a&amp;b\" style=\"font-family:&quot;Open Sans&quot;;\" />"
+        );
+    }
+}
@@ -27,11 +27,12 @@
 //! ```rust
 //! use mrml::prelude::parse::ParserOptions;
 //! use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
-//! use std::rc::Rc;
+//! use std::sync::Arc;
 //!
 //! let loader = MemoryIncludeLoader::from(vec![("partial.mjml", "<mj-button>Hello</mj-button>")]);
-//! let options = Rc::new(ParserOptions {
+//! let options = Arc::new(ParserOptions {
 //!     include_loader: Box::new(loader),
+//!     ..Default::default()
 //! });
 //! match mrml::parse_with_options("<mjml><mj-head /><mj-body><mj-include path=\"partial.mjml\" /></mj-body></mjml>", options) {
 //!     Ok(_) => println!("Success!"),
@@ -103,10 +104,11 @@ mod macros;
 /// ```rust
 /// use mrml::prelude::parse::ParserOptions;
 /// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 ///
-/// let options = Rc::new(ParserOptions {
+/// let options = Arc::new(ParserOptions {
 ///     include_loader: Box::new(MemoryIncludeLoader::default()),
+///     ..Default::default()
 /// });
 /// match mrml::parse_with_options("<mjml><mj-head /><mj-body /></mjml>", options) {
 ///     Ok(_) => println!("Success!"),
@@ -115,11 +117,90 @@ mod macros;
 /// ```
 pub fn parse_with_options<T: AsRef<str>>(
     input: T,
-    opts: std::rc::Rc<crate::prelude::parse::ParserOptions>,
+    opts: std::sync::Arc<crate::prelude::parse::ParserOptions>,
 ) -> Result<mjml::Mjml, prelude::parse::Error> {
     mjml::Mjml::parse_with_options(input, opts)
 }
 
+#[cfg(feature = "async")]
+/// Function to parse a raw mjml template with some asynchronous parsing [options](crate::prelude::parse::AsyncParserOptions).
+/// This function is just an alias to [the `Mjml::parse_with_options_async` function](crate::mjml::Mjml).
+///
+/// The content of the [`mj-include`](crate::mj_include) elements is loaded with an
+/// [`AsyncIncludeLoader`](crate::prelude::parse::loader::AsyncIncludeLoader), which doesn't
+/// block the runtime.
+///
+/// ```rust
+/// use mrml::prelude::parse::AsyncParserOptions;
+/// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
+/// use std::sync::Arc;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let options = Arc::new(AsyncParserOptions {
+///     include_loader: Box::new(MemoryIncludeLoader::default()),
+///     ..Default::default()
+/// });
+/// match mrml::parse_with_options_async("<mjml><mj-head /><mj-body /></mjml>", options).await {
+///     Ok(_) => println!("Success!"),
+///     Err(err) => eprintln!("Something went wrong: {err:?}"),
+/// }
+/// # })
+/// ```
+pub async fn parse_with_options_async<T: AsRef<str>>(
+    input: T,
+    opts: std::sync::Arc<crate::prelude::parse::AsyncParserOptions>,
+) -> Result<mjml::Mjml, prelude::parse::Error> {
+    mjml::Mjml::parse_with_options_async(input, opts).await
+}
+
+#[cfg(feature = "parse")]
+/// Function to parse a raw mjml template and get the errors skipped in [lenient](crate::prelude::parse::ParserSettings::lenient) mode.
+/// This function is just an alias to [the `Mjml::parse_with_diagnostics` function](crate::mjml::Mjml).
+///
+/// ```rust
+/// use mrml::prelude::parse::{ParserOptions, ParserSettings};
+/// use std::sync::Arc;
+///
+/// let options = Arc::new(ParserOptions {
+///     settings: ParserSettings {
+///         lenient: true,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// });
+/// match mrml::parse_with_diagnostics("<mjml><mj-head /><mj-body /></mjml>", options) {
+///     Ok(output) => println!("Success with {} warnings!", output.diagnostics.len()),
+///     Err(err) => eprintln!("Something went wrong: {err:?}"),
+/// }
+/// ```
+pub fn parse_with_diagnostics<T: AsRef<str>>(
+    input: T,
+    opts: std::sync::Arc<crate::prelude::parse::ParserOptions>,
+) -> Result<prelude::parse::ParseOutput<mjml::Mjml>, prelude::parse::Error> {
+    mjml::Mjml::parse_with_diagnostics(input, opts)
+}
+
+#[cfg(all(feature = "parse", feature = "print"))]
+/// Function to parse a raw mjml template and print it back, once edited, with the formatting of its source.
+/// This function is just an alias to [the `Mjml::parse_lossless` function](crate::mjml::Mjml).
+///
+/// ```rust
+/// use mrml::prelude::parse::ParserOptions;
+/// use std::sync::Arc;
+///
+/// let template = "<mjml>\n  <mj-body>\n    <!-- empty -->\n  </mj-body>\n</mjml>\n";
+/// match mrml::parse_lossless(template, Arc::new(ParserOptions::default())) {
+///     Ok(output) => assert_eq!(output.print().unwrap(), template),
+///     Err(err) => eprintln!("Something went wrong: {err:?}"),
+/// }
+/// ```
+pub fn parse_lossless<T: AsRef<str>>(
+    input: T,
+    opts: std::sync::Arc<crate::prelude::parse::ParserOptions>,
+) -> Result<prelude::print::lossless::LosslessOutput<mjml::Mjml>, prelude::parse::Error> {
+    mjml::Mjml::parse_lossless(input, opts)
+}
+
 #[cfg(feature = "parse")]
 /// Function to parse a raw mjml template using the default parsing [options](crate::prelude::parse::ParserOptions).
 ///
@@ -143,9 +224,18 @@ mod tests {
     #[test]
     fn parse_with_options() {
         use crate::prelude::parse::ParserOptions;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
-        let options = Rc::new(ParserOptions::default());
+        let options = Arc::new(ParserOptions::default());
         let _ = crate::parse_with_options("<mjml><mj-head /><mj-body /></mjml>", options);
     }
+
+    #[test]
+    fn should_be_shareable_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<crate::mjml::Mjml>();
+        assert_send_sync::<crate::prelude::parse::ParserOptions>();
+        #[cfg(feature = "render")]
+        assert_send_sync::<crate::prelude::render::Options>();
+    }
 }
@@ -19,6 +19,8 @@ mod parse;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 use crate::prelude::hash::Map;
 
@@ -26,7 +28,7 @@ pub use children::MjAccordionChild;
 
 pub const NAME: &str = "mj-accordion";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
 #[cfg_attr(feature = "parse", mrml_parse(child_text = false))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
@@ -43,7 +45,7 @@ mod tests {
     use crate::mj_accordion::MjAccordion;
     use crate::mj_accordion_element::{MjAccordionElement, MjAccordionElementChildren};
     use crate::mj_accordion_title::MjAccordionTitle;
-    use crate::prelude::parse::{is_element_start, next_token, Parsable, ParserOptions};
+    use crate::prelude::parse::{is_element_start, next_token, Parsable, ParserContext};
     use crate::prelude::print::Print;
     use crate::text::Text;
     use std::rc::Rc;
@@ -66,11 +68,11 @@ mod tests {
             .into()],
         };
         let initial = element.print(false, 0, 2);
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let mut tokenizer = Tokenizer::from("<mj-accordion><mj-accordion-element><mj-accordion-title>Hello World!</mj-accordion-title></mj-accordion-element></mj-accordion>");
         let token = next_token(&mut tokenizer).unwrap();
         let tag = is_element_start(&token).unwrap();
-        let elt = MjAccordion::parse(*tag, &mut tokenizer, opts).unwrap();
+        let elt = MjAccordion::parse(*tag, &mut tokenizer, ctx).unwrap();
         let result = elt.print(false, 0, 2);
         assert_eq!(initial, result);
     }
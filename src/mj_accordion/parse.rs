@@ -2,18 +2,18 @@ use std::rc::Rc;
 
 use super::MjAccordionChild;
 use crate::mj_accordion_element::{MjAccordionElement, NAME as MJ_ACCORDION_ELEMENT};
-use crate::prelude::parse::{Error, Parsable, ParserOptions};
+use crate::prelude::parse::{Error, Parsable, ParserContext};
 use xmlparser::{StrSpan, Tokenizer};
 
 impl Parsable for MjAccordionChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
-            MJ_ACCORDION_ELEMENT => Ok(MjAccordionElement::parse(tag, tokenizer, opts)?.into()),
-            _ => Err(Error::UnexpectedElement(tag.start())),
+            MJ_ACCORDION_ELEMENT => Ok(MjAccordionElement::parse(tag, tokenizer, ctx)?.into()),
+            _ => Err(Error::UnexpectedElement(tag.into())),
         }
     }
 }
@@ -66,7 +66,7 @@ impl<'e, 'h> Render<'h> for MjAccordionRender<'e, 'h> {
             "border" => Some("2px solid black"),
             "font-family" => Some("Ubuntu, Helvetica, Arial, sans-serif"),
             "icon-align" => Some("middle"),
-            "icon-position" => Some("right"),
+            "icon-position" => Some(self.header().end_side()),
             "icon-height" => Some("32px"),
             "icon-width" => Some("32px"),
             "icon-wrapped-url" => Some("https://i.imgur.com/bIXv1bk.png"),
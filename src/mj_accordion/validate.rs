@@ -0,0 +1,42 @@
+use super::{MjAccordion, MjAccordionChild, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("border", Grammar::String),
+        ("container-background-color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("icon-align", Grammar::Enum(&["top", "middle", "bottom"])),
+        ("icon-height", Grammar::SIZE),
+        ("icon-position", Grammar::Enum(&["left", "right"])),
+        ("icon-unwrapped-alt", Grammar::String),
+        ("icon-unwrapped-url", Grammar::String),
+        ("icon-width", Grammar::SIZE),
+        ("icon-wrapped-alt", Grammar::String),
+        ("icon-wrapped-url", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjAccordion {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjAccordionChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjAccordionElement(elt) => elt.validate_with(validator),
+        }
+    }
+}
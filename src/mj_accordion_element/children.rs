@@ -6,13 +6,13 @@ use crate::mj_accordion_title::MjAccordionTitle;
 #[cfg(feature = "parse")]
 use crate::mj_accordion_title::NAME as MJ_ACCORDION_TITLE;
 #[cfg(feature = "parse")]
-use crate::prelude::parse::{Error as ParserError, Parsable, ParserOptions};
+use crate::prelude::parse::{Error as ParserError, Parsable, ParserContext};
 #[cfg(feature = "parse")]
 use std::rc::Rc;
 #[cfg(feature = "parse")]
 use xmlparser::{StrSpan, Tokenizer};
 
-#[derive(Debug, mrml_macros::MrmlChildren)]
+#[derive(Clone, Debug, mrml_macros::MrmlChildren)]
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "json", serde(untagged))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintChildren))]
@@ -28,12 +28,12 @@ impl Parsable for MjAccordionElementChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, ParserError> {
         match tag.as_str() {
-            MJ_ACCORDION_TEXT => Ok(MjAccordionText::parse(tag, tokenizer, opts)?.into()),
-            MJ_ACCORDION_TITLE => Ok(MjAccordionTitle::parse(tag, tokenizer, opts)?.into()),
-            _ => Err(ParserError::UnexpectedElement(tag.start())),
+            MJ_ACCORDION_TEXT => Ok(MjAccordionText::parse(tag, tokenizer, ctx)?.into()),
+            MJ_ACCORDION_TITLE => Ok(MjAccordionTitle::parse(tag, tokenizer, ctx)?.into()),
+            _ => Err(ParserError::UnexpectedElement(tag.into())),
         }
     }
 }
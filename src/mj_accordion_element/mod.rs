@@ -7,6 +7,8 @@ mod parse;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 use crate::mj_accordion_text::MjAccordionText;
 use crate::mj_accordion_title::MjAccordionTitle;
@@ -16,14 +18,14 @@ pub use children::MjAccordionElementChild;
 
 pub const NAME: &str = "mj-accordion-element";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintChildren))]
 pub struct MjAccordionElementChildren {
     pub title: Option<MjAccordionTitle>,
     pub text: Option<MjAccordionText>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
 #[cfg_attr(feature = "print", mrml_print(tag = "NAME"))]
 #[cfg_attr(feature = "json", derive(mrml_json_macros::MrmlJsonComponent))]
@@ -3,21 +3,21 @@ use crate::mj_accordion_text::{MjAccordionText, NAME as MJ_ACCORDION_TEXT};
 use crate::mj_accordion_title::{MjAccordionTitle, NAME as MJ_ACCORDION_TITLE};
 use crate::parse_attribute;
 use crate::prelude::hash::Map;
-use crate::prelude::parse::{Error, Parsable, Parser, ParserOptions};
+use crate::prelude::parse::{Error, Parsable, Parser, ParserContext};
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
 
 #[derive(Debug, Default)]
 struct MjAccordionElementParser {
-    opts: Rc<ParserOptions>,
+    ctx: Rc<ParserContext>,
     attributes: Map<String, String>,
     children: MjAccordionElementChildren,
 }
 
 impl MjAccordionElementParser {
-    fn new(opts: Rc<ParserOptions>) -> Self {
+    fn new(ctx: Rc<ParserContext>) -> Self {
         Self {
-            opts,
+            ctx,
             attributes: Map::default(),
             children: Default::default(),
         }
@@ -43,14 +43,13 @@ impl Parser for MjAccordionElementParser {
     ) -> Result<(), Error> {
         match tag.as_str() {
             MJ_ACCORDION_TEXT => {
-                self.children.text =
-                    Some(MjAccordionText::parse(tag, tokenizer, self.opts.clone())?)
+                self.children.text = Some(MjAccordionText::parse(tag, tokenizer, self.ctx.clone())?)
             }
             MJ_ACCORDION_TITLE => {
                 self.children.title =
-                    Some(MjAccordionTitle::parse(tag, tokenizer, self.opts.clone())?)
+                    Some(MjAccordionTitle::parse(tag, tokenizer, self.ctx.clone())?)
             }
-            _ => return Err(Error::UnexpectedElement(tag.start())),
+            _ => return Err(Error::UnexpectedElement(tag.into())),
         };
         Ok(())
     }
@@ -60,10 +59,10 @@ impl Parsable for MjAccordionElement {
     fn parse(
         _tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjAccordionElementParser::new(opts)
-            .parse(tokenizer)?
+        MjAccordionElementParser::new(ctx.clone())
+            .parse(tokenizer, &ctx)?
             .build()
     }
 }
@@ -71,57 +70,57 @@ impl Parsable for MjAccordionElement {
 #[cfg(test)]
 mod tests {
     use crate::mj_accordion_element::MjAccordionElementChild;
-    use crate::prelude::parse::{is_element_start, next_token, Error, Parsable, ParserOptions};
+    use crate::prelude::parse::{is_element_start, next_token, Error, Parsable, ParserContext};
     use std::rc::Rc;
     use xmlparser::Tokenizer;
 
     #[test]
     fn parse_title_child() {
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let mut tokenizer = Tokenizer::from("<mj-accordion-title>Hello</mj-accordion-title>");
         let token = next_token(&mut tokenizer).unwrap();
         let tag = is_element_start(&token).unwrap();
-        let elt = MjAccordionElementChild::parse(*tag, &mut tokenizer, opts).unwrap();
+        let elt = MjAccordionElementChild::parse(*tag, &mut tokenizer, ctx).unwrap();
         assert!(elt.as_mj_accordion_title().is_some())
     }
 
     #[test]
     fn parse_title_child_errored() {
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let mut tokenizer =
             Tokenizer::from("<mj-accordion-title><span>Hello</span></mj-accordion-title>");
         let token = next_token(&mut tokenizer).unwrap();
         let tag = is_element_start(&token).unwrap();
-        let err = MjAccordionElementChild::parse(*tag, &mut tokenizer, opts).unwrap_err();
-        assert!(matches!(err, Error::UnexpectedElement(21)));
+        let err = MjAccordionElementChild::parse(*tag, &mut tokenizer, ctx).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedElement(span) if span.start == 21));
     }
 
     #[test]
     fn parse_text_child() {
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let mut tokenizer = Tokenizer::from("<mj-accordion-text>Hello</mj-accordion-text>");
         let token = next_token(&mut tokenizer).unwrap();
         let tag = is_element_start(&token).unwrap();
-        let elt = MjAccordionElementChild::parse(*tag, &mut tokenizer, opts).unwrap();
+        let elt = MjAccordionElementChild::parse(*tag, &mut tokenizer, ctx).unwrap();
         assert!(elt.as_mj_accordion_text().is_some());
     }
 
     #[test]
     fn parse_text_child_errored() {
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let mut tokenizer = Tokenizer::from("<mj-accordion-text>");
         let token = next_token(&mut tokenizer).unwrap();
         let tag = is_element_start(&token).unwrap();
-        let err = MjAccordionElementChild::parse(*tag, &mut tokenizer, opts).unwrap_err();
-        assert!(matches!(err, Error::InvalidFormat));
+        let err = MjAccordionElementChild::parse(*tag, &mut tokenizer, ctx).unwrap_err();
+        assert!(matches!(err, Error::EndOfStream(_)));
     }
 
     #[test]
     fn parse_unknown_child() {
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let mut tokenizer = Tokenizer::from("<mj-pouwet>Hello</mj-pouwet>");
         let token = next_token(&mut tokenizer).unwrap();
         let tag = is_element_start(&token).unwrap();
-        assert!(MjAccordionElementChild::parse(*tag, &mut tokenizer, opts).is_err());
+        assert!(MjAccordionElementChild::parse(*tag, &mut tokenizer, ctx).is_err());
     }
 }
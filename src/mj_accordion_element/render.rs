@@ -104,6 +104,7 @@ impl<'e, 'h> Render<'h> for MjAccordionElementRender<'e, 'h> {
         let td = Tag::td()
             .add_style("padding", "0px")
             .maybe_add_style("background-color", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
             .render(label);
         let tr = Tag::tr()
             .maybe_add_class(self.attribute("css-class"))
@@ -0,0 +1,33 @@
+use super::{MjAccordionElement, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_accordion::NAME],
+    attributes: &[
+        ("background-color", Grammar::Color),
+        ("border", Grammar::String),
+        ("font-family", Grammar::String),
+        ("icon-align", Grammar::Enum(&["top", "middle", "bottom"])),
+        ("icon-height", Grammar::SIZE),
+        ("icon-position", Grammar::Enum(&["left", "right"])),
+        ("icon-unwrapped-alt", Grammar::String),
+        ("icon-unwrapped-url", Grammar::String),
+        ("icon-width", Grammar::SIZE),
+        ("icon-wrapped-alt", Grammar::String),
+        ("icon-wrapped-url", Grammar::String),
+    ],
+};
+
+impl Validate for MjAccordionElement {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            if let Some(ref title) = self.children.title {
+                title.validate_with(validator);
+            }
+            if let Some(ref text) = self.children.text {
+                text.validate_with(validator);
+            }
+        });
+    }
+}
@@ -6,13 +6,15 @@ mod parse;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 use crate::mj_raw::MjRawChild;
 use crate::prelude::hash::Map;
 
 pub const NAME: &str = "mj-accordion-text";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
 #[cfg_attr(feature = "print", mrml_print(tag = "NAME"))]
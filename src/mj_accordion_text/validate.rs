@@ -0,0 +1,27 @@
+use super::{MjAccordionText, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_accordion_element::NAME],
+    attributes: &[
+        ("background-color", Grammar::Color),
+        ("color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-weight", Grammar::String),
+        ("letter-spacing", Grammar::String),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjAccordionText {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -6,13 +6,15 @@ mod parse;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 use crate::prelude::hash::Map;
 use crate::text::Text;
 
 pub const NAME: &str = "mj-accordion-title";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
 #[cfg_attr(
     feature = "parse",
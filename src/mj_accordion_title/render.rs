@@ -31,7 +31,9 @@ impl<'e, 'h> MjAccordionTitleRender<'e, 'h> {
         Ok(Tag::td()
             .add_style("width", "100%")
             .maybe_add_style("background-color", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
             .maybe_add_style("color", self.attribute("color"))
+            .maybe_add_class(self.dark_class("color", "color"))
             .maybe_add_style("font-size", self.attribute("font-size"))
             .maybe_add_style("font-family", self.attribute("font-family"))
             .maybe_add_style("padding-top", self.attribute("padding-top"))
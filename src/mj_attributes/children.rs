@@ -2,7 +2,7 @@ use crate::mj_attributes_all::MjAttributesAll;
 use crate::mj_attributes_class::MjAttributesClass;
 use crate::mj_attributes_element::MjAttributesElement;
 
-#[derive(Debug, mrml_macros::MrmlChildren)]
+#[derive(Clone, Debug, mrml_macros::MrmlChildren)]
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "json", serde(untagged))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintChildren))]
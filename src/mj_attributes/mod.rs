@@ -5,12 +5,14 @@ mod json;
 mod parse;
 #[cfg(feature = "print")]
 mod print;
+#[cfg(feature = "validate")]
+mod validate;
 
 pub use children::MjAttributesChild;
 
 pub const NAME: &str = "mj-attributes";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
 #[cfg_attr(
     feature = "parse",
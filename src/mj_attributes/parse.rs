@@ -4,7 +4,7 @@ use crate::mj_attributes_all::NAME as MJ_ALL;
 use crate::mj_attributes_class::MjAttributesClass;
 use crate::mj_attributes_class::NAME as MJ_CLASS;
 use crate::mj_attributes_element::MjAttributesElement;
-use crate::prelude::parse::ParserOptions;
+use crate::prelude::parse::ParserContext;
 use crate::prelude::parse::{Error, Parsable};
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
@@ -13,12 +13,12 @@ impl Parsable for MjAttributesChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
-            MJ_ALL => Ok(MjAttributesAll::parse(tag, tokenizer, opts)?.into()),
-            MJ_CLASS => Ok(MjAttributesClass::parse(tag, tokenizer, opts)?.into()),
-            _ => Ok(MjAttributesElement::parse(tag, tokenizer, opts)?.into()),
+            MJ_ALL => Ok(MjAttributesAll::parse(tag, tokenizer, ctx)?.into()),
+            MJ_CLASS => Ok(MjAttributesClass::parse(tag, tokenizer, ctx)?.into()),
+            _ => Ok(MjAttributesElement::parse(tag, tokenizer, ctx)?.into()),
         }
     }
 }
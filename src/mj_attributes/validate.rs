@@ -0,0 +1,20 @@
+use super::{MjAttributes, MjAttributesChild, NAME};
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjAttributes {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mj_head::NAME], |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjAttributesChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::MjAttributesAll(elt) => elt.validate_with(validator),
+            Self::MjAttributesClass(elt) => elt.validate_with(validator),
+            Self::MjAttributesElement(elt) => elt.validate_with(validator),
+        }
+    }
+}
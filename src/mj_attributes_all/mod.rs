@@ -6,10 +6,12 @@ mod json;
 mod parse;
 #[cfg(feature = "print")]
 mod print;
+#[cfg(feature = "validate")]
+mod validate;
 
 pub const NAME: &str = "mj-all";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
 #[cfg_attr(feature = "print", mrml_print(tag = "NAME"))]
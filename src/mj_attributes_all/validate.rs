@@ -0,0 +1,8 @@
+use super::{MjAttributesAll, NAME};
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjAttributesAll {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mj_attributes::NAME], |_| {});
+    }
+}
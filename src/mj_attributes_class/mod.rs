@@ -6,10 +6,12 @@ mod json;
 mod parse;
 #[cfg(feature = "print")]
 mod print;
+#[cfg(feature = "validate")]
+mod validate;
 
 pub const NAME: &str = "mj-class";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct MjAttributesClass {
     name: String,
     attributes: Map<String, String>,
@@ -3,7 +3,7 @@ use std::rc::Rc;
 use super::MjAttributesClass;
 use crate::prelude::{
     hash::Map,
-    parse::{Error, Parsable, Parser, ParserOptions},
+    parse::{attribute_value, Error, Parsable, Parser, ParserContext},
 };
 use xmlparser::{StrSpan, Tokenizer};
 
@@ -14,7 +14,7 @@ struct MjAttributesClassParser {
 }
 
 impl MjAttributesClassParser {
-    fn new(_opts: Rc<ParserOptions>) -> Self {
+    fn new(_ctx: Rc<ParserContext>) -> Self {
         Self {
             name: String::default(),
             attributes: Map::new(),
@@ -34,9 +34,10 @@ impl Parser for MjAttributesClassParser {
 
     fn parse_attribute<'a>(&mut self, name: StrSpan<'a>, value: StrSpan<'a>) -> Result<(), Error> {
         if name.as_str() == "name" {
-            self.name = value.to_string();
+            self.name = attribute_value(value);
         } else {
-            self.attributes.insert(name.to_string(), value.to_string());
+            self.attributes
+                .insert(name.to_string(), attribute_value(value));
         }
         Ok(())
     }
@@ -46,9 +47,11 @@ impl Parsable for MjAttributesClass {
     fn parse(
         _tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjAttributesClassParser::new(opts).parse(tokenizer)?.build()
+        MjAttributesClassParser::new(ctx.clone())
+            .parse(tokenizer, &ctx)?
+            .build()
     }
 }
 
@@ -6,8 +6,10 @@ mod json;
 mod parse;
 #[cfg(feature = "print")]
 mod print;
+#[cfg(feature = "validate")]
+mod validate;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct MjAttributesElement {
     name: String,
     attributes: Map<String, String>,
@@ -1,7 +1,7 @@
 use super::MjAttributesElement;
 use crate::prelude::{
     hash::Map,
-    parse::{Error, Parsable, Parser, ParserOptions},
+    parse::{attribute_value, Error, Parsable, Parser, ParserContext},
 };
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
@@ -13,7 +13,7 @@ struct MjAttributesElementParser {
 }
 
 impl MjAttributesElementParser {
-    pub fn new(name: String, _opts: Rc<ParserOptions>) -> Self {
+    pub fn new(name: String, _ctx: Rc<ParserContext>) -> Self {
         Self {
             name,
             attributes: Map::new(),
@@ -35,7 +35,8 @@ impl Parser for MjAttributesElementParser {
         if name.as_str() == "name" {
             self.name = name.to_string();
         } else {
-            self.attributes.insert(name.to_string(), value.to_string());
+            self.attributes
+                .insert(name.to_string(), attribute_value(value));
         }
         Ok(())
     }
@@ -45,10 +46,10 @@ impl Parsable for MjAttributesElement {
     fn parse(
         tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjAttributesElementParser::new(tag.to_string(), opts)
-            .parse(tokenizer)?
+        MjAttributesElementParser::new(tag.to_string(), ctx.clone())
+            .parse(tokenizer, &ctx)?
             .build()
     }
 }
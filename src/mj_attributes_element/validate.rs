@@ -0,0 +1,8 @@
+use super::MjAttributesElement;
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjAttributesElement {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(&self.name, &[crate::mj_attributes::NAME], |_| {});
+    }
+}
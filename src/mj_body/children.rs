@@ -25,7 +25,7 @@ use std::cell::RefCell;
 #[cfg(feature = "render")]
 use std::rc::Rc;
 
-#[derive(Debug, mrml_macros::MrmlChildren)]
+#[derive(Clone, Debug, mrml_macros::MrmlChildren)]
 #[cfg_attr(feature = "json", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "json", serde(untagged))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintChildren))]
@@ -65,7 +65,7 @@ impl MjBodyChild {
             Self::MjDivider(elt) => elt,
             Self::MjGroup(elt) => elt,
             Self::MjHero(elt) => elt,
-            Self::MjInclude(_elt) => todo!(),
+            Self::MjInclude(elt) => elt,
             Self::MjImage(elt) => elt,
             Self::MjNavbar(elt) => elt,
             Self::MjRaw(elt) => elt,
@@ -79,6 +79,24 @@ impl MjBodyChild {
             Self::Text(elt) => elt,
         }
     }
+
+    /// Lists the renderable elements, replacing every `mj-include` by the children
+    /// it was resolved to, so that they get rendered as if they were written inline.
+    #[cfg(feature = "render")]
+    pub(crate) fn flatten_renderables<'r, 'e: 'r, 'h: 'r>(
+        children: &'e [Self],
+    ) -> Vec<&'e (dyn Renderable<'r, 'e, 'h> + 'e)> {
+        children
+            .iter()
+            .fold(Vec::with_capacity(children.len()), |mut res, child| {
+                if let Self::MjInclude(include) = child {
+                    res.extend(include.children.iter().map(|item| item.as_renderable()));
+                } else {
+                    res.push(child.as_renderable());
+                }
+                res
+            })
+    }
 }
 
 #[cfg(feature = "render")]
@@ -34,7 +34,7 @@ use crate::mj_text::NAME as MJ_TEXT;
 use crate::mj_wrapper::MjWrapper;
 use crate::mj_wrapper::NAME as MJ_WRAPPER;
 use crate::node::Node;
-use crate::prelude::parse::ParserOptions;
+use crate::prelude::parse::ParserContext;
 use crate::prelude::parse::{Error, Parsable};
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
@@ -43,27 +43,27 @@ impl Parsable for MjBodyChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
-            MJ_ACCORDION => Ok(MjAccordion::parse(tag, tokenizer, opts)?.into()),
-            MJ_BUTTON => Ok(MjButton::parse(tag, tokenizer, opts)?.into()),
-            MJ_CAROUSEL => Ok(MjCarousel::parse(tag, tokenizer, opts)?.into()),
-            MJ_COLUMN => Ok(MjColumn::parse(tag, tokenizer, opts)?.into()),
-            MJ_DIVIDER => Ok(MjDivider::parse(tag, tokenizer, opts)?.into()),
-            MJ_GROUP => Ok(MjGroup::parse(tag, tokenizer, opts)?.into()),
-            MJ_HERO => Ok(MjHero::parse(tag, tokenizer, opts)?.into()),
-            MJ_IMAGE => Ok(MjImage::parse(tag, tokenizer, opts)?.into()),
-            MJ_INCLUDE => Ok(MjIncludeBody::parse(tag, tokenizer, opts)?.into()),
-            MJ_NAVBAR => Ok(MjNavbar::parse(tag, tokenizer, opts)?.into()),
-            MJ_RAW => Ok(MjRaw::parse(tag, tokenizer, opts)?.into()),
-            MJ_SECTION => Ok(MjSection::parse(tag, tokenizer, opts)?.into()),
-            MJ_SOCIAL => Ok(MjSocial::parse(tag, tokenizer, opts)?.into()),
-            MJ_SPACER => Ok(MjSpacer::parse(tag, tokenizer, opts)?.into()),
-            MJ_TABLE => Ok(MjTable::parse(tag, tokenizer, opts)?.into()),
-            MJ_TEXT => Ok(MjText::parse(tag, tokenizer, opts)?.into()),
-            MJ_WRAPPER => Ok(MjWrapper::parse(tag, tokenizer, opts)?.into()),
-            _ => Ok(Node::<MjBodyChild>::parse(tag, tokenizer, opts)?.into()),
+            MJ_ACCORDION => Ok(MjAccordion::parse(tag, tokenizer, ctx)?.into()),
+            MJ_BUTTON => Ok(MjButton::parse(tag, tokenizer, ctx)?.into()),
+            MJ_CAROUSEL => Ok(MjCarousel::parse(tag, tokenizer, ctx)?.into()),
+            MJ_COLUMN => Ok(MjColumn::parse(tag, tokenizer, ctx)?.into()),
+            MJ_DIVIDER => Ok(MjDivider::parse(tag, tokenizer, ctx)?.into()),
+            MJ_GROUP => Ok(MjGroup::parse(tag, tokenizer, ctx)?.into()),
+            MJ_HERO => Ok(MjHero::parse(tag, tokenizer, ctx)?.into()),
+            MJ_IMAGE => Ok(MjImage::parse(tag, tokenizer, ctx)?.into()),
+            MJ_INCLUDE => Ok(MjIncludeBody::parse(tag, tokenizer, ctx)?.into()),
+            MJ_NAVBAR => Ok(MjNavbar::parse(tag, tokenizer, ctx)?.into()),
+            MJ_RAW => Ok(MjRaw::parse(tag, tokenizer, ctx)?.into()),
+            MJ_SECTION => Ok(MjSection::parse(tag, tokenizer, ctx)?.into()),
+            MJ_SOCIAL => Ok(MjSocial::parse(tag, tokenizer, ctx)?.into()),
+            MJ_SPACER => Ok(MjSpacer::parse(tag, tokenizer, ctx)?.into()),
+            MJ_TABLE => Ok(MjTable::parse(tag, tokenizer, ctx)?.into()),
+            MJ_TEXT => Ok(MjText::parse(tag, tokenizer, ctx)?.into()),
+            MJ_WRAPPER => Ok(MjWrapper::parse(tag, tokenizer, ctx)?.into()),
+            _ => Ok(Node::<MjBodyChild>::parse(tag, tokenizer, ctx)?.into()),
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::MjBody;
+use super::{MjBody, MjBodyChild};
 use crate::helper::size::Pixel;
 use crate::helper::tag::Tag;
 use crate::prelude::hash::Map;
@@ -26,10 +26,12 @@ impl<'e, 'h> MjBodyRender<'e, 'h> {
         self.set_body_style(Tag::new("div"))
             .maybe_add_attribute("class", self.attribute("css-class"))
             .maybe_add_attribute("lang", self.header().lang().map(ToString::to_string))
+            .maybe_add_attribute("dir", self.header().dir().map(ToString::to_string))
     }
 
     fn set_body_style(&self, tag: Tag) -> Tag {
         tag.maybe_add_style("background-color", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
     }
 
     fn render_preview(&self) -> String {
@@ -50,25 +52,22 @@ impl<'e, 'h> MjBodyRender<'e, 'h> {
         }
     }
 
-    fn render_content(&self, opts: &Options) -> Result<String, Error> {
+    fn render_content(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let div = self.get_content_div_tag();
         let element_width = self.get_width();
-        let mut children = String::default();
-        let raw_siblings = self
-            .element
-            .children
-            .iter()
-            .filter(|item| item.is_raw())
-            .count();
-        for (index, child) in self.element.children.iter().enumerate() {
+        buf.push_str(&div.open());
+        let elements = MjBodyChild::flatten_renderables(&self.element.children);
+        let raw_siblings = elements.iter().filter(|item| item.is_raw()).count();
+        for (index, child) in elements.iter().enumerate() {
             let mut renderer = child.renderer(Rc::clone(&self.header));
             renderer.set_container_width(element_width.clone());
             renderer.set_index(index);
             renderer.set_raw_siblings(raw_siblings);
-            renderer.set_siblings(self.element.children.len());
-            children.push_str(&renderer.render(opts)?);
+            renderer.set_siblings(elements.len());
+            renderer.render_into(buf, opts)?;
         }
-        Ok(div.render(children))
+        buf.push_str(&div.close());
+        Ok(())
     }
 }
 
@@ -89,9 +88,18 @@ impl<'e, 'h> Render<'h> for MjBodyRender<'e, 'h> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
+        let mut buf = String::default();
+        self.render_into(&mut buf, opts)?;
+        Ok(buf)
+    }
+
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let body = self.get_body_tag();
-        let result = body.render(self.render_preview() + &self.render_content(opts)?);
-        Ok(result)
+        buf.push_str(&body.open());
+        buf.push_str(&self.render_preview());
+        self.render_content(buf, opts)?;
+        buf.push_str(&body.close());
+        Ok(())
     }
 }
 
@@ -4,10 +4,12 @@ mod json;
 mod parse;
 #[cfg(feature = "print")]
 mod print;
+#[cfg(feature = "validate")]
+mod validate;
 
 pub const NAME: &str = "mj-breakpoint";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintAttributes))]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseAttributes))]
@@ -16,7 +18,7 @@ pub struct MjBreakpointAttributes {
     pub width: String,
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
 #[cfg_attr(feature = "print", mrml_print(tag = "NAME"))]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
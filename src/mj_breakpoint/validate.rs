@@ -0,0 +1,10 @@
+use super::{MjBreakpoint, NAME};
+use crate::prelude::validate::{Grammar, Validate, Validator};
+
+impl Validate for MjBreakpoint {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mj_head::NAME], |validator| {
+            validator.attribute("width", &self.attributes.width, Grammar::PIXEL);
+        });
+    }
+}
@@ -65,13 +65,16 @@ impl<'e, 'h> MjButtonRender<'e, 'h> {
             .maybe_add_style("mso-padding-alt", self.attribute("inner-padding"))
             .maybe_add_style("text-align", self.attribute("text-align"))
             .maybe_add_style("background", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
     }
 
     fn set_style_content(&self, tag: Tag) -> Tag {
         tag.add_style("display", "inline-block")
             .maybe_add_style("width", self.content_width())
             .maybe_add_style("background", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
             .maybe_add_style("color", self.attribute("color"))
+            .maybe_add_class(self.dark_class("color", "color"))
             .maybe_add_style("font-family", self.attribute("font-family"))
             .maybe_add_style("font-size", self.attribute("font-size"))
             .maybe_add_style("font-style", self.attribute("font-style"))
@@ -134,15 +137,14 @@ impl<'e, 'h> Render<'h> for MjButtonRender<'e, 'h> {
             .maybe_add_attribute("bgcolor", self.attribute("background-color"))
             .add_attribute("role", "presentation")
             .maybe_add_attribute("valign", self.attribute("vertical-align"));
-        let link = Tag::new(self.attribute("href").map(|_| "a").unwrap_or("p"))
-            .maybe_add_attribute("href", self.attribute("href"))
+        let href = self
+            .attribute("href")
+            .map(|href| self.rewrite_link(href, opts));
+        let link = Tag::new(href.as_ref().map(|_| "a").unwrap_or("p"))
+            .maybe_add_attribute("href", href.clone())
             .maybe_add_attribute("rel", self.attribute("rel"))
             .maybe_add_attribute("name", self.attribute("name"))
-            .maybe_add_attribute(
-                "target",
-                self.attribute("href")
-                    .and_then(|_v| self.attribute("target")),
-            );
+            .maybe_add_attribute("target", href.and_then(|_v| self.attribute("target")));
         let link = self.set_style_content(link);
 
         Ok(table.render(
@@ -0,0 +1,48 @@
+use super::{MjButton, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("background-color", Grammar::Color),
+        ("border", Grammar::String),
+        ("border-bottom", Grammar::String),
+        ("border-left", Grammar::String),
+        ("border-radius", Grammar::String),
+        ("border-right", Grammar::String),
+        ("border-top", Grammar::String),
+        ("color", Grammar::Color),
+        ("container-background-color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-style", Grammar::String),
+        ("font-weight", Grammar::String),
+        ("height", Grammar::SIZE),
+        ("href", Grammar::String),
+        ("inner-padding", Grammar::SPACING),
+        ("letter-spacing", Grammar::String),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("name", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("rel", Grammar::String),
+        ("target", Grammar::String),
+        ("text-align", Grammar::ALIGN),
+        ("text-decoration", Grammar::String),
+        ("text-transform", Grammar::String),
+        ("title", Grammar::String),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+        ("width", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjButton {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -7,6 +7,8 @@ mod parse;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 use crate::prelude::hash::Map;
 
@@ -14,7 +16,7 @@ pub use children::MjCarouselChild;
 
 pub const NAME: &str = "mj-carousel";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "parse", derive(mrml_parse_macros::MrmlParseComponent))]
 #[cfg_attr(feature = "parse", mrml_parse(child_text = false))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
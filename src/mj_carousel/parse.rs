@@ -2,18 +2,18 @@ use std::rc::Rc;
 
 use super::MjCarouselChild;
 use crate::mj_carousel_image::{MjCarouselImage, NAME as MJ_CAROUSEL_IMAGE};
-use crate::prelude::parse::{Error, Parsable, ParserOptions};
+use crate::prelude::parse::{Error, Parsable, ParserContext};
 use xmlparser::{StrSpan, Tokenizer};
 
 impl Parsable for MjCarouselChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
-            MJ_CAROUSEL_IMAGE => Ok(MjCarouselImage::parse(tag, tokenizer, opts)?.into()),
-            _ => Err(Error::UnexpectedElement(tag.start())),
+            MJ_CAROUSEL_IMAGE => Ok(MjCarouselImage::parse(tag, tokenizer, ctx)?.into()),
+            _ => Err(Error::UnexpectedElement(tag.into())),
         }
     }
 }
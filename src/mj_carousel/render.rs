@@ -456,12 +456,32 @@ impl<'e, 'h> Render<'h> for MjCarouselRender<'e, 'h> {
     }
 }
 
+impl MjCarousel {
+    /// Attributes of the element and of its children, to generate a stable identifier.
+    fn content_key(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                MjCarouselChild::MjCarouselImage(item) => {
+                    Some(random::attributes_key(&item.attributes))
+                }
+                _ => None,
+            })
+            .fold(random::attributes_key(&self.attributes), |res, item| {
+                res + "|" + &item
+            })
+    }
+}
+
 impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for MjCarousel {
     fn renderer(&'e self, header: Rc<RefCell<Header<'h>>>) -> Box<dyn Render<'h> + 'r> {
+        let id = header
+            .borrow_mut()
+            .generate_id(NAME, &self.content_key(), 8);
         Box::new(MjCarouselRender::<'e, 'h> {
             element: self,
             header,
-            id: random::generate(8),
+            id,
             container_width: None,
             siblings: 1,
             raw_siblings: 0,
@@ -485,6 +505,21 @@ mod tests {
         html_compare::assert_similar(expected, result.as_str());
     }
 
+    #[test]
+    fn should_render_identically_with_content_hash() {
+        let opts = Options {
+            id_strategy: crate::prelude::render::IdStrategy::ContentHash,
+            ..Default::default()
+        };
+        let template = include_str!("../../resources/compare/success/mj-carousel.mjml");
+        let root = Mjml::parse(template).unwrap();
+        let first = root.render(&opts).unwrap();
+        assert!(!first.contains("mj-carousel-aaaaaaaa"));
+        assert_eq!(first, root.render(&opts).unwrap());
+        let other = Mjml::parse(template.replace(".jpg", ".png")).unwrap();
+        assert_ne!(first, other.render(&opts).unwrap().replace(".png", ".jpg"));
+    }
+
     #[test]
     fn align_border_radius_class() {
         let opts = Options::default();
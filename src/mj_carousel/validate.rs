@@ -0,0 +1,43 @@
+use super::{MjCarousel, MjCarouselChild, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("border-radius", Grammar::SPACING),
+        ("container-background-color", Grammar::Color),
+        ("icon-width", Grammar::SIZE),
+        ("left-icon", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("right-icon", Grammar::String),
+        ("tb-border", Grammar::String),
+        ("tb-border-radius", Grammar::SIZE),
+        ("tb-hover-border-color", Grammar::Color),
+        ("tb-selected-border-color", Grammar::Color),
+        ("tb-width", Grammar::SIZE),
+        ("thumbnails", Grammar::Enum(&["visible", "hidden"])),
+    ],
+};
+
+impl Validate for MjCarousel {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjCarouselChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjCarouselImage(elt) => elt.validate_with(validator),
+        }
+    }
+}
@@ -175,7 +175,7 @@ impl<'e, 'h> Render<'h> for MjCarouselImageRender<'e, 'h> {
         }
     }
 
-    fn render(&self, _opts: &Options) -> Result<String, Error> {
+    fn render(&self, opts: &Options) -> Result<String, Error> {
         let img = self
             .set_style_images_img(Tag::new("img"))
             .add_attribute("border", "0")
@@ -192,7 +192,7 @@ impl<'e, 'h> Render<'h> for MjCarouselImageRender<'e, 'h> {
         let link = match self.attribute("href") {
             None => img,
             Some(href) => Tag::new("a")
-                .add_attribute("href", href)
+                .add_attribute("href", self.rewrite_link(href, opts))
                 .maybe_add_attribute("rel", self.attribute("rel"))
                 .add_attribute("target", "_blank")
                 .render(img),
@@ -0,0 +1,25 @@
+use super::{MjCarouselImage, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_carousel::NAME],
+    attributes: &[
+        ("alt", Grammar::String),
+        ("border-radius", Grammar::SPACING),
+        ("href", Grammar::String),
+        ("rel", Grammar::String),
+        ("src", Grammar::String),
+        ("target", Grammar::String),
+        ("tb-border", Grammar::String),
+        ("tb-border-radius", Grammar::SPACING),
+        ("thumbnails-src", Grammar::String),
+        ("title", Grammar::String),
+    ],
+};
+
+impl Validate for MjCarouselImage {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -1,6 +1,7 @@
 use super::{MjColumn, NAME};
 use crate::helper::size::{Pixel, Size};
 use crate::helper::tag::Tag;
+use crate::mj_body::MjBodyChild;
 use crate::prelude::hash::Map;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
@@ -112,7 +113,7 @@ impl<'e, 'h> MjColumnRender<'e, 'h> {
 
     fn set_style_root_div(&self, tag: Tag) -> Tag {
         tag.add_style("font-size", "0px")
-            .add_style("text-align", "left")
+            .add_style("text-align", self.header().start_side())
             .maybe_add_style("direction", self.attribute("direction"))
             .add_style("display", "inline-block")
             .maybe_add_style("vertical-align", self.attribute("vertical-align"))
@@ -159,6 +160,7 @@ impl<'e, 'h> MjColumnRender<'e, 'h> {
 
     fn set_style_table_simple(&self, tag: Tag) -> Tag {
         tag.maybe_add_style("background-color", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
             .maybe_add_style("border", self.attribute("border"))
             .maybe_add_style("border-bottom", self.attribute("border-bottom"))
             .maybe_add_style("border-left", self.attribute("border-left"))
@@ -177,12 +179,21 @@ impl<'e, 'h> MjColumnRender<'e, 'h> {
             .maybe_add_style("padding-left", self.attribute("padding-left"))
     }
 
-    fn render_gutter(&self, opts: &Options) -> Result<String, Error> {
+    fn render_gutter(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let table = Tag::table_presentation().add_attribute("width", "100%");
         let tbody = Tag::tbody();
         let tr = Tag::tr();
         let td = self.set_style_gutter_td(Tag::td());
-        Ok(table.render(tbody.render(tr.render(td.render(self.render_column(opts)?)))))
+        buf.push_str(&table.open());
+        buf.push_str(&tbody.open());
+        buf.push_str(&tr.open());
+        buf.push_str(&td.open());
+        self.render_column(buf, opts)?;
+        buf.push_str(&td.close());
+        buf.push_str(&tr.close());
+        buf.push_str(&tbody.close());
+        buf.push_str(&table.close());
+        Ok(())
     }
 
     fn set_style_table(&self, tag: Tag) -> Tag {
@@ -193,54 +204,62 @@ impl<'e, 'h> MjColumnRender<'e, 'h> {
         }
     }
 
-    fn render_column(&self, opts: &Options) -> Result<String, Error> {
+    fn render_column(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let table = self
             .set_style_table(Tag::table_presentation())
             .add_attribute("width", "100%");
         let tbody = Tag::tbody();
-        let siblings = self.element.children.len();
-        let raw_siblings = self.element.children.iter().filter(|i| i.is_raw()).count();
+        let elements = MjBodyChild::flatten_renderables(&self.element.children);
+        let siblings = elements.len();
+        let raw_siblings = elements.iter().filter(|i| i.is_raw()).count();
         let current_width = self.current_width();
-        let content = self.element.children.iter().enumerate().try_fold(
-            String::default(),
-            |res, (index, child)| {
-                let mut renderer = child.renderer(Rc::clone(&self.header));
-                renderer.set_index(index);
-                renderer.set_raw_siblings(raw_siblings);
-                renderer.set_siblings(siblings);
-                renderer.set_container_width(current_width.clone());
-                let result = if child.is_raw() {
-                    renderer.render(opts)?
-                } else {
-                    let tr = Tag::tr();
-                    let td = Tag::td()
-                        .maybe_add_style(
-                            "background",
-                            renderer.attribute("container-background-color"),
-                        )
-                        .add_style("font-size", "0px")
-                        .maybe_add_style("padding", renderer.attribute("padding"))
-                        .maybe_add_style("padding-top", renderer.attribute("padding-top"))
-                        .maybe_add_style("padding-right", renderer.attribute("padding-right"))
-                        .maybe_add_style("padding-bottom", renderer.attribute("padding-bottom"))
-                        .maybe_add_style("padding-left", renderer.attribute("padding-left"))
-                        .add_style("word-break", "break-word")
-                        .maybe_add_attribute("align", renderer.attribute("align"))
-                        .maybe_add_attribute("vertical-align", renderer.attribute("vertical-align"))
-                        .maybe_add_class(renderer.attribute("css-class"));
-                    tr.render(td.render(renderer.render(opts)?))
-                };
-                Ok(res + &result)
-            },
-        )?;
-        Ok(table.render(tbody.render(content)))
+        buf.push_str(&table.open());
+        buf.push_str(&tbody.open());
+        for (index, child) in elements.iter().enumerate() {
+            let mut renderer = child.renderer(Rc::clone(&self.header));
+            renderer.set_index(index);
+            renderer.set_raw_siblings(raw_siblings);
+            renderer.set_siblings(siblings);
+            renderer.set_container_width(current_width.clone());
+            if child.is_raw() {
+                renderer.render_into(buf, opts)?;
+            } else {
+                let tr = Tag::tr();
+                let td = Tag::td()
+                    .maybe_add_style(
+                        "background",
+                        renderer.attribute("container-background-color"),
+                    )
+                    .add_style("font-size", "0px")
+                    .maybe_add_style("padding", renderer.attribute("padding"))
+                    .maybe_add_style("padding-top", renderer.attribute("padding-top"))
+                    .maybe_add_style("padding-right", renderer.attribute("padding-right"))
+                    .maybe_add_style("padding-bottom", renderer.attribute("padding-bottom"))
+                    .maybe_add_style("padding-left", renderer.attribute("padding-left"))
+                    .add_style("word-break", "break-word")
+                    .maybe_add_attribute("align", renderer.attribute("align"))
+                    .maybe_add_attribute("vertical-align", renderer.attribute("vertical-align"))
+                    .maybe_add_class(renderer.attribute("css-class"))
+                    .maybe_add_class(
+                        renderer.dark_class("container-background-color", "background-color"),
+                    );
+                buf.push_str(&tr.open());
+                buf.push_str(&td.open());
+                renderer.render_into(buf, opts)?;
+                buf.push_str(&td.close());
+                buf.push_str(&tr.close());
+            }
+        }
+        buf.push_str(&tbody.close());
+        buf.push_str(&table.close());
+        Ok(())
     }
 }
 
 impl<'e, 'h> Render<'h> for MjColumnRender<'e, 'h> {
     fn default_attribute(&self, name: &str) -> Option<&str> {
         match name {
-            "direction" => Some("ltr"),
+            "direction" => Some(self.header().direction()),
             "vertical-align" => Some("top"),
             _ => None,
         }
@@ -290,6 +309,12 @@ impl<'e, 'h> Render<'h> for MjColumnRender<'e, 'h> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
+        let mut buf = String::default();
+        self.render_into(&mut buf, opts)?;
+        Ok(buf)
+    }
+
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let (classname, size) = self.get_column_class();
         self.header
             .borrow_mut()
@@ -299,12 +324,14 @@ impl<'e, 'h> Render<'h> for MjColumnRender<'e, 'h> {
             .add_class("mj-outlook-group-fix")
             .add_class(classname)
             .maybe_add_class(self.attribute("css-class"));
-        let content = if self.has_gutter() {
-            self.render_gutter(opts)?
+        buf.push_str(&div.open());
+        if self.has_gutter() {
+            self.render_gutter(buf, opts)?;
         } else {
-            self.render_column(opts)?
-        };
-        Ok(div.render(content))
+            self.render_column(buf, opts)?;
+        }
+        buf.push_str(&div.close());
+        Ok(())
     }
 }
 
@@ -0,0 +1,40 @@
+use super::{MjColumn, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_group::NAME, crate::mj_section::NAME],
+    attributes: &[
+        ("background-color", Grammar::Color),
+        ("border", Grammar::String),
+        ("border-bottom", Grammar::String),
+        ("border-left", Grammar::String),
+        ("border-radius", Grammar::SPACING),
+        ("border-right", Grammar::String),
+        ("border-top", Grammar::String),
+        ("direction", Grammar::DIRECTION),
+        ("inner-background-color", Grammar::Color),
+        ("inner-border", Grammar::String),
+        ("inner-border-bottom", Grammar::String),
+        ("inner-border-left", Grammar::String),
+        ("inner-border-radius", Grammar::SPACING),
+        ("inner-border-right", Grammar::String),
+        ("inner-border-top", Grammar::String),
+        ("mobile-width", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+        ("width", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjColumn {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
@@ -0,0 +1,26 @@
+use super::{MjDivider, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("border-color", Grammar::Color),
+        ("border-style", Grammar::String),
+        ("border-width", Grammar::PIXEL),
+        ("container-background-color", Grammar::Color),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("width", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjDivider {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -0,0 +1,8 @@
+use super::{MjFont, NAME};
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjFont {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mj_head::NAME], |_| {});
+    }
+}
@@ -2,6 +2,7 @@ use super::{MjGroup, NAME};
 use crate::helper::condition::conditional_tag;
 use crate::helper::size::{Pixel, Size};
 use crate::helper::tag::Tag;
+use crate::mj_body::MjBodyChild;
 use crate::prelude::hash::Map;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
@@ -64,11 +65,12 @@ impl<'e, 'h> MjGroupRender<'e, 'h> {
     fn set_style_root_div(&self, tag: Tag) -> Tag {
         tag.add_style("font-size", "0")
             .add_style("line-height", "0")
-            .add_style("text-align", "left")
+            .add_style("text-align", self.header().start_side())
             .add_style("display", "inline-block")
             .add_style("width", "100%")
             .maybe_add_style("direction", self.attribute("direction"))
             .maybe_add_style("background-color", self.attribute("background-color"))
+            .maybe_add_class(self.dark_class("background-color", "background-color"))
             .maybe_add_style("vertical-align", self.attribute("vertical-align"))
     }
 
@@ -77,51 +79,44 @@ impl<'e, 'h> MjGroupRender<'e, 'h> {
             .add_style("width", self.current_width().to_string())
     }
 
-    fn render_children(&self, opts: &Options) -> Result<String, Error> {
+    fn render_children(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let current_width = self.current_width();
-        let siblings = self.element.children.len();
-        let raw_siblings = self
-            .element
-            .children
-            .iter()
-            .filter(|item| item.is_raw())
-            .count();
-        self.element.children.iter().enumerate().try_fold(
-            String::default(),
-            |res, (index, child)| {
-                let mut renderer = child.renderer(Rc::clone(&self.header));
-                renderer.set_index(index);
-                renderer.set_siblings(siblings);
-                renderer.set_raw_siblings(raw_siblings);
-                renderer.set_container_width(Some(current_width.clone()));
-                renderer.add_extra_attribute("mobile-width", "mobile-width");
-                let result = if child.is_raw() {
-                    renderer.render(opts)?
-                } else {
-                    let td = Tag::td()
-                        .maybe_add_style("align", renderer.attribute("align"))
-                        .maybe_add_style("vertical-align", renderer.attribute("vertical-align"))
-                        .maybe_add_style(
-                            "width",
-                            renderer
-                                .get_width()
-                                .map(|w| w.to_string())
-                                .or_else(|| renderer.attribute("width")),
-                        );
-                    conditional_tag(td.open())
-                        + &renderer.render(opts)?
-                        + &conditional_tag(td.close())
-                };
-                Ok(res + &result)
-            },
-        )
+        let elements = MjBodyChild::flatten_renderables(&self.element.children);
+        let siblings = elements.len();
+        let raw_siblings = elements.iter().filter(|item| item.is_raw()).count();
+        for (index, child) in elements.iter().enumerate() {
+            let mut renderer = child.renderer(Rc::clone(&self.header));
+            renderer.set_index(index);
+            renderer.set_siblings(siblings);
+            renderer.set_raw_siblings(raw_siblings);
+            renderer.set_container_width(Some(current_width.clone()));
+            renderer.add_extra_attribute("mobile-width", "mobile-width");
+            if child.is_raw() {
+                renderer.render_into(buf, opts)?;
+            } else {
+                let td = Tag::td()
+                    .maybe_add_style("align", renderer.attribute("align"))
+                    .maybe_add_style("vertical-align", renderer.attribute("vertical-align"))
+                    .maybe_add_style(
+                        "width",
+                        renderer
+                            .get_width()
+                            .map(|w| w.to_string())
+                            .or_else(|| renderer.attribute("width")),
+                    );
+                buf.push_str(&conditional_tag(td.open()));
+                renderer.render_into(buf, opts)?;
+                buf.push_str(&conditional_tag(td.close()));
+            }
+        }
+        Ok(())
     }
 }
 
 impl<'e, 'h> Render<'h> for MjGroupRender<'e, 'h> {
     fn default_attribute(&self, name: &str) -> Option<&str> {
         match name {
-            "direction" => Some("ltr"),
+            "direction" => Some(self.header().direction()),
             _ => None,
         }
     }
@@ -162,6 +157,12 @@ impl<'e, 'h> Render<'h> for MjGroupRender<'e, 'h> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
+        let mut buf = String::default();
+        self.render_into(&mut buf, opts)?;
+        Ok(buf)
+    }
+
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let (classname, size) = self.get_column_class();
         self.header
             .borrow_mut()
@@ -171,21 +172,25 @@ impl<'e, 'h> Render<'h> for MjGroupRender<'e, 'h> {
             .add_class(classname)
             .add_class("mj-outlook-group-fix")
             .maybe_add_class(self.attribute("css-class"));
-        let table = Tag::table_presentation().maybe_add_attribute(
-            "bgcolor",
-            self.attribute("background-color").and_then(|color| {
-                if color == "none" {
-                    None
-                } else {
-                    Some(color)
-                }
-            }),
-        );
+        let table = Tag::table_presentation()
+            .maybe_add_attribute(
+                "bgcolor",
+                self.attribute("background-color").and_then(|color| {
+                    if color == "none" {
+                        None
+                    } else {
+                        Some(color)
+                    }
+                }),
+            )
+            .maybe_add_attribute("dir", self.outlook_dir());
         let tr = Tag::tr();
-        let content = conditional_tag(table.open() + &tr.open())
-            + &self.render_children(opts)?
-            + &conditional_tag(tr.close() + &table.close());
-        Ok(div.render(content))
+        buf.push_str(&div.open());
+        buf.push_str(&conditional_tag(table.open() + &tr.open()));
+        self.render_children(buf, opts)?;
+        buf.push_str(&conditional_tag(tr.close() + &table.close()));
+        buf.push_str(&div.close());
+        Ok(())
     }
 }
 
@@ -0,0 +1,21 @@
+use super::{MjGroup, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_section::NAME],
+    attributes: &[
+        ("background-color", Grammar::Color),
+        ("direction", Grammar::DIRECTION),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+        ("width", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjGroup {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
@@ -7,6 +7,8 @@ mod parse;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
 use crate::mj_breakpoint::MjBreakpoint;
 use crate::mj_preview::MjPreview;
@@ -15,7 +17,7 @@ pub use children::MjHeadChild;
 
 pub const NAME: &str = "mj-head";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
 #[cfg_attr(feature = "print", mrml_print(tag = "NAME"))]
 #[cfg_attr(feature = "json", derive(mrml_json_macros::MrmlJsonComponent))]
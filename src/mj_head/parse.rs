@@ -13,7 +13,7 @@ use crate::mj_style::MjStyle;
 use crate::mj_style::NAME as MJ_STYLE;
 use crate::mj_title::MjTitle;
 use crate::mj_title::NAME as MJ_TITLE;
-use crate::prelude::parse::ParserOptions;
+use crate::prelude::parse::ParserContext;
 use crate::prelude::parse::{Error, Parsable, Parser};
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
@@ -22,34 +22,34 @@ impl Parsable for MjHeadChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
-            MJ_ATTRIBUTES => Ok(MjAttributes::parse(tag, tokenizer, opts)?.into()),
-            MJ_BREAKPOINT => Ok(MjBreakpoint::parse(tag, tokenizer, opts)?.into()),
-            MJ_FONT => Ok(MjFont::parse(tag, tokenizer, opts)?.into()),
+            MJ_ATTRIBUTES => Ok(MjAttributes::parse(tag, tokenizer, ctx)?.into()),
+            MJ_BREAKPOINT => Ok(MjBreakpoint::parse(tag, tokenizer, ctx)?.into()),
+            MJ_FONT => Ok(MjFont::parse(tag, tokenizer, ctx)?.into()),
             crate::mj_include::NAME => {
-                Ok(crate::mj_include::head::MjIncludeHead::parse(tag, tokenizer, opts)?.into())
+                Ok(crate::mj_include::head::MjIncludeHead::parse(tag, tokenizer, ctx)?.into())
             }
-            MJ_PREVIEW => Ok(MjPreview::parse(tag, tokenizer, opts)?.into()),
-            MJ_RAW => Ok(MjRaw::parse(tag, tokenizer, opts)?.into()),
-            MJ_STYLE => Ok(MjStyle::parse(tag, tokenizer, opts)?.into()),
-            MJ_TITLE => Ok(MjTitle::parse(tag, tokenizer, opts)?.into()),
-            _ => Err(Error::UnexpectedElement(tag.start())),
+            MJ_PREVIEW => Ok(MjPreview::parse(tag, tokenizer, ctx)?.into()),
+            MJ_RAW => Ok(MjRaw::parse(tag, tokenizer, ctx)?.into()),
+            MJ_STYLE => Ok(MjStyle::parse(tag, tokenizer, ctx)?.into()),
+            MJ_TITLE => Ok(MjTitle::parse(tag, tokenizer, ctx)?.into()),
+            _ => Err(Error::UnexpectedElement(tag.into())),
         }
     }
 }
 
 #[derive(Debug)]
 struct MjHeadParser {
-    opts: Rc<ParserOptions>,
+    ctx: Rc<ParserContext>,
     children: Vec<MjHeadChild>,
 }
 
 impl MjHeadParser {
-    fn new(opts: Rc<ParserOptions>) -> Self {
+    fn new(ctx: Rc<ParserContext>) -> Self {
         Self {
-            opts,
+            ctx,
             children: Vec::default(),
         }
     }
@@ -70,7 +70,7 @@ impl Parser for MjHeadParser {
         tokenizer: &mut Tokenizer<'a>,
     ) -> Result<(), Error> {
         self.children
-            .push(MjHeadChild::parse(tag, tokenizer, self.opts.clone())?);
+            .push(MjHeadChild::parse(tag, tokenizer, self.ctx.clone())?);
         Ok(())
     }
 }
@@ -79,9 +79,11 @@ impl Parsable for MjHead {
     fn parse(
         _tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjHeadParser::new(opts).parse(tokenizer)?.build()
+        MjHeadParser::new(ctx.clone())
+            .parse(tokenizer, &ctx)?
+            .build()
     }
 }
 
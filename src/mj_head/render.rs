@@ -1,5 +1,6 @@
 use super::{MjHead, MjHeadChild};
 use crate::helper::condition::{END_NEGATION_CONDITIONAL_TAG, START_MSO_NEGATION_CONDITIONAL_TAG};
+use crate::helper::escape::escape_attribute;
 use crate::helper::sort::sort_by_key;
 use crate::mj_include::head::MjIncludeHeadKind;
 use crate::prelude::hash::Map;
@@ -32,12 +33,14 @@ p { display: block; margin: 13px 0; }
 <![endif]-->
 "#;
 
-fn select_mj_styles<'a>(buffer: &mut Vec<&'a str>, children: &'a [MjHeadChild]) {
+fn select_mj_styles<'a>(buffer: &mut Vec<&'a str>, children: &'a [MjHeadChild], inline: bool) {
     for child in children.iter() {
         if let Some(mj_style) = child.as_mj_style() {
-            buffer.push(mj_style.children());
+            if mj_style.is_inline() == inline {
+                buffer.push(mj_style.children());
+            }
         } else if let Some(mj_include) = child.as_mj_include() {
-            if let MjIncludeHeadKind::Css { inline: false } = mj_include.attributes.kind {
+            if mj_include.attributes.kind == (MjIncludeHeadKind::Css { inline }) {
                 mj_include
                     .children
                     .iter()
@@ -111,6 +114,13 @@ impl MjHead {
             })
     }
 
+    /// Lists the stylesheets that should be applied to the `style` attribute of the elements.
+    pub fn build_inline_styles(&self) -> Vec<&str> {
+        let mut buffer = Vec::default();
+        select_mj_styles(&mut buffer, &self.children, true);
+        buffer
+    }
+
     pub fn build_font_families(&self) -> Map<&str, &str> {
         self.children
             .iter()
@@ -131,7 +141,10 @@ impl<'e, 'h> MjHeadRender<'e, 'h> {
     }
 
     fn render_font_link(&self, href: &str) -> String {
-        format!("<link href=\"{href}\" rel=\"stylesheet\" type=\"text/css\">")
+        format!(
+            "<link href=\"{}\" rel=\"stylesheet\" type=\"text/css\">",
+            escape_attribute(href)
+        )
     }
 
     fn render_font_families(&self, opts: &Options) -> String {
@@ -177,6 +190,44 @@ impl<'e, 'h> MjHeadRender<'e, 'h> {
         }
     }
 
+    /// Styles applying the `dark-` variants of the colors, for the clients supporting
+    /// `prefers-color-scheme` and for Outlook.com, which flags its dark mode with the
+    /// `data-ogsc` (text) and `data-ogsb` (background) attributes.
+    fn render_dark_styles(&self) -> String {
+        let header = self.header.borrow();
+        let styles = header.dark_styles();
+        if styles.is_empty() {
+            return String::default();
+        }
+        let mut classnames = styles.iter().collect::<Vec<_>>();
+        classnames.sort_by(sort_by_key);
+        let mut buf = String::from("<style type=\"text/css\">");
+        buf.push_str(":root { color-scheme: light dark; supported-color-schemes: light dark; } ");
+        buf.push_str("@media (prefers-color-scheme: dark) { ");
+        classnames
+            .iter()
+            .for_each(|(classname, (property, value))| {
+                buf.push_str(&format!(
+                    ".{classname} {{ {property}:{value} !important; }} "
+                ));
+            });
+        buf.push_str("} ");
+        classnames
+            .iter()
+            .for_each(|(classname, (property, value))| {
+                let selector = if property.starts_with("background") {
+                    "[data-ogsb]"
+                } else {
+                    "[data-ogsc]"
+                };
+                buf.push_str(&format!(
+                    "{selector} .{classname} {{ {property}:{value} !important; }} "
+                ));
+            });
+        buf.push_str("</style>");
+        buf
+    }
+
     fn render_media_queries(&self) -> String {
         let header = self.header.borrow();
         if header.media_queries().is_empty() {
@@ -219,6 +270,21 @@ impl<'e, 'h> MjHeadRender<'e, 'h> {
             buf.push_str("; } ");
         });
         buf.push_str("</style>");
+        // outlook web app ignores the media queries, the desktop layout is forced
+        if header.owa() == Some("desktop") {
+            buf.push_str("<style type=\"text/css\">");
+            classnames.iter().for_each(|(classname, size)| {
+                let size = size.to_string();
+                buf.push_str("[owa] .");
+                buf.push_str(classname);
+                buf.push_str(" { width:");
+                buf.push_str(size.as_str());
+                buf.push_str(" !important; max-width:");
+                buf.push_str(size.as_str());
+                buf.push_str("; } ");
+            });
+            buf.push_str("</style>");
+        }
         buf
     }
 
@@ -238,7 +304,7 @@ impl<'e, 'h> MjHeadRender<'e, 'h> {
         };
         let head_styles = {
             let mut buffer = Vec::default();
-            select_mj_styles(&mut buffer, &self.element.children);
+            select_mj_styles(&mut buffer, &self.element.children, false);
             let buffer = buffer.join("\n");
             if buffer.is_empty() {
                 buffer
@@ -259,6 +325,14 @@ impl<'e, 'h> MjHeadRender<'e, 'h> {
                 renderer.set_siblings(siblings);
                 buffer.push(renderer.render(opts)?);
             } else if let Some(mj_include) = child.as_mj_include() {
+                if mj_include.attributes.kind == MjIncludeHeadKind::Html {
+                    mj_include
+                        .children
+                        .iter()
+                        .filter_map(|c| c.as_text())
+                        .for_each(|c| buffer.push(c.inner_str().to_string()));
+                    continue;
+                }
                 for include_child in mj_include.children.iter() {
                     if let Some(mj_raw) = include_child.as_mj_raw() {
                         let mut renderer = mj_raw.renderer(Rc::clone(&self.header));
@@ -294,9 +368,14 @@ impl<'e, 'h> Render<'h> for MjHeadRender<'e, 'h> {
         buf.push_str(END_NEGATION_CONDITIONAL_TAG);
         buf.push_str("<meta http-equiv=\"Content-Type\" content=\"text/html; charset=UTF-8\">");
         buf.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">");
+        if !self.header().dark_styles().is_empty() {
+            buf.push_str("<meta name=\"color-scheme\" content=\"light dark\">");
+            buf.push_str("<meta name=\"supported-color-schemes\" content=\"light dark\">");
+        }
         buf.push_str(STYLE_BASE);
         buf.push_str(&self.render_font_families(opts));
         buf.push_str(&self.render_media_queries());
+        buf.push_str(&self.render_dark_styles());
         buf.push_str(&self.render_styles());
         buf.push_str(&self.render_raw(opts)?);
         buf.push_str("</head>");
@@ -338,4 +417,45 @@ mod tests {
         let result = root.render(&opts).unwrap();
         html_compare::assert_similar(expected, result.as_str());
     }
+
+    #[test]
+    fn should_force_desktop_layout_in_owa() {
+        let template = r#"<mjml owa="desktop"><mj-body><mj-section><mj-column><mj-text>Hello</mj-text></mj-column><mj-column width="200px"><mj-text>World</mj-text></mj-column></mj-section></mj-body></mjml>"#;
+        let root = Mjml::parse(template).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(result.contains(r#"<style type="text/css">[owa] .mj-column-per-50 { width:50% !important; max-width:50%; } [owa] .mj-column-px-200 { width:200px !important; max-width:200px; } </style>"#));
+        let root = Mjml::parse(template.replace(r#" owa="desktop""#, "")).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(!result.contains("[owa]"));
+    }
+
+    #[test]
+    fn should_render_dark_styles() {
+        let template = r##"<mjml>
+  <mj-head>
+    <mj-attributes>
+      <mj-text dark-color="#eeeeee" />
+    </mj-attributes>
+  </mj-head>
+  <mj-body background-color="#ffffff" dark-background-color="#111111">
+    <mj-section>
+      <mj-column>
+        <mj-text color="#000000">Hello</mj-text>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"##;
+        let root = Mjml::parse(template).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(result.contains(r#"<meta name="color-scheme" content="light dark">"#));
+        assert!(result.contains("@media (prefers-color-scheme: dark) { .mj-dark-background-color-111111 { background-color:#111111 !important; } .mj-dark-color-eeeeee { color:#eeeeee !important; } } "));
+        assert!(result.contains(
+            "[data-ogsb] .mj-dark-background-color-111111 { background-color:#111111 !important; }"
+        ));
+        assert!(result.contains("[data-ogsc] .mj-dark-color-eeeeee { color:#eeeeee !important; }"));
+        assert!(result.contains(r#"class="mj-dark-color-eeeeee""#));
+        let root = Mjml::parse(template.replace("dark-", "")).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(!result.contains("color-scheme"));
+    }
 }
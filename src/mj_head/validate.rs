@@ -0,0 +1,26 @@
+use super::{MjHead, MjHeadChild, NAME};
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjHead {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mjml::NAME], |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjHeadChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjAttributes(elt) => elt.validate_with(validator),
+            Self::MjBreakpoint(elt) => elt.validate_with(validator),
+            Self::MjFont(elt) => elt.validate_with(validator),
+            Self::MjInclude(elt) => elt.validate_with(validator),
+            Self::MjPreview(elt) => elt.validate_with(validator),
+            Self::MjRaw(elt) => elt.validate_with(validator),
+            Self::MjStyle(elt) => elt.validate_with(validator),
+            Self::MjTitle(elt) => elt.validate_with(validator),
+        }
+    }
+}
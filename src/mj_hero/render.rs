@@ -2,6 +2,7 @@ use super::{MjHero, NAME};
 use crate::helper::condition::conditional_tag;
 use crate::helper::size::Pixel;
 use crate::helper::tag::Tag;
+use crate::mj_body::MjBodyChild;
 use crate::prelude::hash::Map;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
@@ -57,6 +58,7 @@ impl<'e, 'h> MjHeroRender<'e, 'h> {
 
     fn set_style_outlook_inner_td(&self, tag: Tag) -> Tag {
         tag.maybe_add_style("background-color", self.attribute("inner-background-color"))
+            .maybe_add_class(self.dark_class("inner-background-color", "background-color"))
             .maybe_add_style("padding", self.attribute("inner-padding"))
             .maybe_add_style("padding-top", self.attribute("inner-padding-top"))
             .maybe_add_style("padding-right", self.attribute("inner-padding-right"))
@@ -66,6 +68,7 @@ impl<'e, 'h> MjHeroRender<'e, 'h> {
 
     fn set_style_inner_div(&self, tag: Tag) -> Tag {
         tag.maybe_add_style("background-color", self.attribute("inner-background-color"))
+            .maybe_add_class(self.dark_class("inner-background-color", "background-color"))
             .maybe_add_style("float", self.attribute("align"))
             .add_style("margin", "0px auto")
             .maybe_add_style("width", self.attribute("width"))
@@ -111,7 +114,8 @@ impl<'e, 'h> MjHeroRender<'e, 'h> {
     }
 
     fn set_style_hero(&self, tag: Tag) -> Tag {
-        tag.maybe_add_style("background", self.get_background())
+        tag.maybe_add_class(self.dark_class("background-color", "background-color"))
+            .maybe_add_style("background", self.get_background())
             .maybe_add_style("background-position", self.attribute("background-position"))
             .add_style("background-repeat", "no-repeat")
             .maybe_add_style("padding", self.attribute("padding"))
@@ -123,42 +127,45 @@ impl<'e, 'h> MjHeroRender<'e, 'h> {
     }
 
     fn render_children(&self, opts: &Options) -> Result<String, Error> {
-        let siblings = self.element.children.len();
-        let raw_siblings = self.element.children.iter().filter(|c| c.is_raw()).count();
-        self.element.children.iter().enumerate().try_fold(
-            String::default(),
-            |res, (index, child)| {
-                let mut renderer = child.renderer(Rc::clone(&self.header));
-                renderer.set_index(index);
-                renderer.set_siblings(siblings);
-                renderer.set_raw_siblings(raw_siblings);
-                let result = if child.is_raw() {
-                    renderer.render(opts)?
-                } else {
-                    let tr = Tag::tr();
-                    let td = Tag::td()
-                        .maybe_add_style(
-                            "background",
-                            renderer.attribute("container-background-color"),
-                        )
-                        .add_style("font-size", "0px")
-                        .maybe_add_style("padding", renderer.attribute("padding"))
-                        .maybe_add_style("padding-top", renderer.attribute("padding-top"))
-                        .maybe_add_style("padding-right", renderer.attribute("padding-right"))
-                        .maybe_add_style("padding-bottom", renderer.attribute("padding-bottom"))
-                        .maybe_add_style("padding-left", renderer.attribute("padding-left"))
-                        .add_style("word-break", "break-word")
-                        .maybe_add_attribute("align", renderer.attribute("align"))
-                        .maybe_add_attribute(
-                            "background",
-                            renderer.attribute("container-background-color"),
-                        )
-                        .maybe_add_attribute("class", renderer.attribute("css-class"));
-                    tr.render(td.render(renderer.render(opts)?))
-                };
-                Ok(res + &result)
-            },
-        )
+        let elements = MjBodyChild::flatten_renderables(&self.element.children);
+        let siblings = elements.len();
+        let raw_siblings = elements.iter().filter(|c| c.is_raw()).count();
+        let mut buf = String::default();
+        for (index, child) in elements.iter().enumerate() {
+            let mut renderer = child.renderer(Rc::clone(&self.header));
+            renderer.set_index(index);
+            renderer.set_siblings(siblings);
+            renderer.set_raw_siblings(raw_siblings);
+            if child.is_raw() {
+                renderer.render_into(&mut buf, opts)?;
+            } else {
+                let tr = Tag::tr();
+                let td = Tag::td()
+                    .maybe_add_style(
+                        "background",
+                        renderer.attribute("container-background-color"),
+                    )
+                    .add_style("font-size", "0px")
+                    .maybe_add_style("padding", renderer.attribute("padding"))
+                    .maybe_add_style("padding-top", renderer.attribute("padding-top"))
+                    .maybe_add_style("padding-right", renderer.attribute("padding-right"))
+                    .maybe_add_style("padding-bottom", renderer.attribute("padding-bottom"))
+                    .maybe_add_style("padding-left", renderer.attribute("padding-left"))
+                    .add_style("word-break", "break-word")
+                    .maybe_add_attribute("align", renderer.attribute("align"))
+                    .maybe_add_attribute(
+                        "background",
+                        renderer.attribute("container-background-color"),
+                    )
+                    .maybe_add_attribute("class", renderer.attribute("css-class"));
+                buf.push_str(&tr.open());
+                buf.push_str(&td.open());
+                renderer.render_into(&mut buf, opts)?;
+                buf.push_str(&td.close());
+                buf.push_str(&tr.close());
+            }
+        }
+        Ok(buf)
     }
 
     fn render_content(&self, opts: &Options) -> Result<String, Error> {
@@ -323,6 +330,21 @@ mod tests {
         html_compare::assert_similar(expected, result.as_str());
     }
 
+    #[test]
+    fn dark_background_colors() {
+        let template = r##"<mjml>
+  <mj-body>
+    <mj-hero background-color="#ffffff" dark-background-color="#111111" inner-background-color="#eeeeee" dark-inner-background-color="#222222">
+      <mj-text>Hello</mj-text>
+    </mj-hero>
+  </mj-body>
+</mjml>"##;
+        let root = Mjml::parse(template).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(result.contains(r#"class="mj-dark-background-color-111111""#));
+        assert!(result.contains(r#"class="mj-dark-background-color-222222""#));
+    }
+
     #[test]
     fn background_height() {
         let opts = Options::default();
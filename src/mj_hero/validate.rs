@@ -0,0 +1,39 @@
+use super::{MjHero, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_body::NAME, crate::mj_wrapper::NAME],
+    attributes: &[
+        ("background-color", Grammar::Color),
+        ("background-height", Grammar::SIZE),
+        ("background-position", Grammar::String),
+        ("background-url", Grammar::String),
+        ("background-width", Grammar::SIZE),
+        ("border-radius", Grammar::String),
+        ("container-background-color", Grammar::Color),
+        ("height", Grammar::SIZE),
+        ("inner-background-color", Grammar::Color),
+        ("inner-padding", Grammar::SPACING),
+        ("inner-padding-bottom", Grammar::SIZE),
+        ("inner-padding-left", Grammar::SIZE),
+        ("inner-padding-right", Grammar::SIZE),
+        ("inner-padding-top", Grammar::SIZE),
+        ("mode", Grammar::Enum(&["fixed-height", "fluid-height"])),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+        ("width", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjHero {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
@@ -111,9 +111,13 @@ impl<'e, 'h> MjImageRender<'e, 'h> {
         self.set_style_img(img).closed()
     }
 
-    fn render_link(&self) -> String {
+    fn render_link(&self, opts: &Options) -> String {
         Tag::new("a")
-            .maybe_add_attribute("href", self.attribute("href"))
+            .maybe_add_attribute(
+                "href",
+                self.attribute("href")
+                    .map(|href| self.rewrite_link(href, opts)),
+            )
             .maybe_add_attribute("name", self.attribute("name"))
             .maybe_add_attribute("rel", self.attribute("rel"))
             .maybe_add_attribute("target", self.attribute("target"))
@@ -161,7 +165,7 @@ impl<'e, 'h> Render<'h> for MjImageRender<'e, 'h> {
         self.header.borrow()
     }
 
-    fn render(&self, _opts: &Options) -> Result<String, Error> {
+    fn render(&self, opts: &Options) -> Result<String, Error> {
         let style = self.render_style();
         self.header.borrow_mut().add_style(style);
         let class = if self.is_fluid_on_mobile() {
@@ -176,7 +180,7 @@ impl<'e, 'h> Render<'h> for MjImageRender<'e, 'h> {
         let tr = Tag::tr();
         let td = self.set_style_td(Tag::td()).maybe_add_class(class);
         let content = if self.attribute_exists("href") {
-            self.render_link()
+            self.render_link(opts)
         } else {
             self.render_image()
         };
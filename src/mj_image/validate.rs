@@ -0,0 +1,44 @@
+use super::{MjImage, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("alt", Grammar::String),
+        ("border", Grammar::String),
+        ("border-bottom", Grammar::String),
+        ("border-left", Grammar::String),
+        ("border-radius", Grammar::SPACING),
+        ("border-right", Grammar::String),
+        ("border-top", Grammar::String),
+        ("container-background-color", Grammar::Color),
+        ("fluid-on-mobile", Grammar::Boolean),
+        ("font-size", Grammar::PIXEL),
+        ("full-width", Grammar::Enum(&["full-width", "false"])),
+        ("height", Grammar::String),
+        ("href", Grammar::String),
+        ("max-height", Grammar::SIZE),
+        ("name", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("rel", Grammar::String),
+        ("sizes", Grammar::String),
+        ("src", Grammar::String),
+        ("srcset", Grammar::String),
+        ("target", Grammar::String),
+        ("title", Grammar::String),
+        ("usemap", Grammar::String),
+        ("width", Grammar::PIXEL),
+    ],
+};
+
+impl Validate for MjImage {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -2,7 +2,7 @@ use super::{MjIncludeBody, MjIncludeBodyAttributes, MjIncludeBodyChild, MjInclud
 use crate::comment::Comment;
 use crate::mj_body::MjBodyChild;
 use crate::mj_wrapper::MjWrapper;
-use crate::prelude::parse::{Error, Parsable, Parser, ParserOptions};
+use crate::prelude::parse::{attribute_value, Error, Parsable, Parser, ParserContext, Span};
 use crate::text::Text;
 use std::convert::TryFrom;
 use std::rc::Rc;
@@ -53,58 +53,58 @@ impl Parsable for MjIncludeBodyChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
             crate::mj_accordion::NAME => Ok(Self::MjAccordion(
-                crate::mj_accordion::MjAccordion::parse(tag, tokenizer, opts)?,
+                crate::mj_accordion::MjAccordion::parse(tag, tokenizer, ctx)?,
             )),
             crate::mj_button::NAME => Ok(Self::MjButton(crate::mj_button::MjButton::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_carousel::NAME => Ok(Self::MjCarousel(
-                crate::mj_carousel::MjCarousel::parse(tag, tokenizer, opts)?,
+                crate::mj_carousel::MjCarousel::parse(tag, tokenizer, ctx)?,
             )),
             crate::mj_column::NAME => Ok(Self::MjColumn(crate::mj_column::MjColumn::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_divider::NAME => Ok(Self::MjDivider(crate::mj_divider::MjDivider::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_group::NAME => Ok(Self::MjGroup(crate::mj_group::MjGroup::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_hero::NAME => Ok(Self::MjHero(crate::mj_hero::MjHero::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_image::NAME => Ok(Self::MjImage(crate::mj_image::MjImage::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_navbar::NAME => Ok(Self::MjNavbar(crate::mj_navbar::MjNavbar::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_raw::NAME => Ok(Self::MjRaw(crate::mj_raw::MjRaw::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_section::NAME => Ok(Self::MjSection(crate::mj_section::MjSection::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_social::NAME => Ok(Self::MjSocial(crate::mj_social::MjSocial::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_spacer::NAME => Ok(Self::MjSpacer(crate::mj_spacer::MjSpacer::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_table::NAME => Ok(Self::MjTable(crate::mj_table::MjTable::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_text::NAME => Ok(Self::MjText(crate::mj_text::MjText::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_wrapper::NAME => Ok(Self::MjWrapper(crate::mj_wrapper::MjWrapper::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
-            _ => Ok(Self::Node(crate::node::Node::parse(tag, tokenizer, opts)?)),
+            _ => Ok(Self::Node(crate::node::Node::parse(tag, tokenizer, ctx)?)),
         }
     }
 }
@@ -116,9 +116,10 @@ impl FromStr for MjIncludeBodyKind {
         match s {
             "html" => Ok(Self::Html),
             "mjml" => Ok(Self::Mjml),
-            other => Err(Error::InvalidElement(format!(
-                "invalid mj-include attribute kind {other:?}"
-            ))),
+            other => Err(Error::InvalidElement(
+                format!("invalid mj-include attribute kind {other:?}"),
+                Span::default(),
+            )),
         }
     }
 }
@@ -140,14 +141,16 @@ impl MjIncludeBodyKind {
 
 #[derive(Debug)]
 struct MjIncludeBodyParser {
-    opts: Rc<ParserOptions>,
+    ctx: Rc<ParserContext>,
+    span: Span,
     attributes: MjIncludeBodyAttributes,
 }
 
 impl MjIncludeBodyParser {
-    fn new(opts: Rc<ParserOptions>) -> Self {
+    fn new(ctx: Rc<ParserContext>, span: Span) -> Self {
         Self {
-            opts,
+            ctx,
+            span,
             attributes: MjIncludeBodyAttributes::default(),
         }
     }
@@ -157,13 +160,20 @@ impl Parser for MjIncludeBodyParser {
     type Output = MjIncludeBody;
 
     fn build(self) -> Result<Self::Output, Error> {
-        let child = self
-            .opts
+        let path = self
+            .ctx
+            .include_path(&self.attributes.path)
+            .map_err(|err| Error::IncludeLoaderError(err, self.span.clone()))?;
+        let _guard = self.ctx.enter_include(&path, &self.span)?;
+        let content = self
+            .ctx
+            .options
             .include_loader
-            .resolve(&self.attributes.path)
-            .map_err(Error::IncludeLoaderError)?;
+            .resolve(&path)
+            .map_err(|err| Error::IncludeLoaderError(err, self.span.clone()))?;
 
-        let child = crate::prelude::parse::loader::parse(&child, self.opts.clone())?;
+        let child =
+            crate::prelude::parse::loader::parse_included(&path, &content, self.ctx.clone())?;
 
         let children = self.attributes.kind.wrap(vec![child])?;
 
@@ -176,12 +186,13 @@ impl Parser for MjIncludeBodyParser {
     fn parse_attribute<'a>(&mut self, name: StrSpan<'a>, value: StrSpan<'a>) -> Result<(), Error> {
         match name.as_str() {
             "path" => {
-                self.attributes.path = value.to_string();
+                self.attributes.path = attribute_value(value);
             }
             "type" => {
-                self.attributes.kind = MjIncludeBodyKind::from_str(value.as_str())?;
+                self.attributes.kind = MjIncludeBodyKind::from_str(value.as_str())
+                    .map_err(|err| err.with_span(value))?;
             }
-            _ => return Err(Error::UnexpectedAttribute(name.start())),
+            _ => return Err(Error::UnexpectedAttribute(name.into())),
         }
         Ok(())
     }
@@ -189,11 +200,13 @@ impl Parser for MjIncludeBodyParser {
 
 impl Parsable for MjIncludeBody {
     fn parse(
-        _tag: StrSpan,
+        tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjIncludeBodyParser::new(opts).parse(tokenizer)?.build()
+        MjIncludeBodyParser::new(ctx.clone(), tag.into())
+            .parse(tokenizer, &ctx)?
+            .build()
     }
 }
 
@@ -202,7 +215,7 @@ mod tests {
     use crate::mj_include::body::MjIncludeBodyKind;
     use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
     use crate::prelude::parse::{Error, ParserOptions};
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     #[test]
     fn basic_in_noop_resolver() {
@@ -214,7 +227,7 @@ mod tests {
 "#;
         let err = crate::mjml::Mjml::parse(json).unwrap_err();
         match err {
-            Error::IncludeLoaderError(origin) => {
+            Error::IncludeLoaderError(origin, _) => {
                 assert_eq!(origin.reason, std::io::ErrorKind::NotFound);
             }
             _ => panic!("expected a IncludeLoaderError"),
@@ -227,6 +240,7 @@ mod tests {
             MemoryIncludeLoader::from(vec![("basic.mjml", "<mj-button>Hello</mj-button>")]);
         let opts = ParserOptions {
             include_loader: Box::new(resolver),
+            ..Default::default()
         };
         let json = r#"<mjml>
   <mj-body>
@@ -234,7 +248,7 @@ mod tests {
   </mj-body>
 </mjml>
 "#;
-        let root = crate::mjml::Mjml::parse_with_options(json, Rc::new(opts)).unwrap();
+        let root = crate::mjml::Mjml::parse_with_options(json, Arc::new(opts)).unwrap();
         let body = root.children.body.unwrap();
         let include = body.children.first().unwrap().as_mj_include().unwrap();
         assert_eq!(include.attributes.kind, MjIncludeBodyKind::Mjml);
@@ -246,6 +260,7 @@ mod tests {
         let resolver = MemoryIncludeLoader::from(vec![("partial.html", "<h1>Hello World!</h1>")]);
         let opts = ParserOptions {
             include_loader: Box::new(resolver),
+            ..Default::default()
         };
         let json = r#"<mjml>
   <mj-body>
@@ -253,7 +268,7 @@ mod tests {
   </mj-body>
 </mjml>
 "#;
-        let root = crate::mjml::Mjml::parse_with_options(json, Rc::new(opts)).unwrap();
+        let root = crate::mjml::Mjml::parse_with_options(json, Arc::new(opts)).unwrap();
         let body = root.children.body.unwrap();
         let include = body.children.first().unwrap().as_mj_include().unwrap();
         assert_eq!(include.attributes.kind, MjIncludeBodyKind::Html);
@@ -1,4 +1,5 @@
 use super::{MjIncludeBody, MjIncludeBodyChild};
+use crate::helper::size::Pixel;
 use crate::prelude::hash::Map;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
@@ -43,6 +44,8 @@ impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for MjIncludeBodyChild {
 struct MjIncludeBodyRender<'e, 'h> {
     header: Rc<RefCell<Header<'h>>>,
     element: &'e MjIncludeBody,
+    container_width: Option<Pixel>,
+    index: usize,
 }
 
 impl<'e, 'h> Render<'h> for MjIncludeBodyRender<'e, 'h> {
@@ -58,12 +61,29 @@ impl<'e, 'h> Render<'h> for MjIncludeBodyRender<'e, 'h> {
         self.header.borrow()
     }
 
+    fn set_container_width(&mut self, width: Option<Pixel>) {
+        self.container_width = width;
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
     fn render(&self, opts: &Options) -> Result<String, Error> {
+        let siblings = self.element.children.len();
+        let raw_siblings = self
+            .element
+            .children
+            .iter()
+            .filter(|item| item.is_raw())
+            .count();
         let mut children = String::default();
         for (index, child) in self.element.children.iter().enumerate() {
             let mut renderer = child.renderer(Rc::clone(&self.header));
-            renderer.set_index(index);
-            renderer.set_siblings(self.element.children.len());
+            renderer.set_container_width(self.container_width.clone());
+            renderer.set_index(self.index + index);
+            renderer.set_siblings(siblings);
+            renderer.set_raw_siblings(raw_siblings);
             children.push_str(&renderer.render(opts)?);
         }
         Ok(children)
@@ -75,6 +95,8 @@ impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for MjIncludeBody {
         Box::new(MjIncludeBodyRender::<'e, 'h> {
             element: self,
             header,
+            container_width: None,
+            index: 0,
         })
     }
 }
@@ -83,13 +105,17 @@ impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for MjIncludeBody {
 mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
 
     use crate::mj_body::MjBodyChild;
     use crate::mj_head::MjHead;
     use crate::mj_include::body::{MjIncludeBody, MjIncludeBodyChild, MjIncludeBodyKind};
     use crate::mj_raw::{MjRaw, MjRawChild};
     use crate::mj_text::MjText;
+    use crate::mjml::Mjml;
     use crate::node::Node;
+    use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
+    use crate::prelude::parse::ParserOptions;
     use crate::prelude::render::{Header, Options, Renderable};
     use crate::text::Text;
 
@@ -149,4 +175,71 @@ mod tests {
         };
         assert_eq!(expected, result);
     }
+
+    fn render_with_loader(template: &str, loader: MemoryIncludeLoader) -> String {
+        let opts = ParserOptions {
+            include_loader: Box::new(loader),
+            ..Default::default()
+        };
+        let root = Mjml::parse_with_options(template, Arc::new(opts)).unwrap();
+        root.render(&Options::default()).unwrap()
+    }
+
+    #[test]
+    fn in_body_should_render_like_inline() {
+        let expected = Mjml::parse(
+            r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text></mj-column></mj-section><mj-section><mj-column><mj-text>World</mj-text></mj-column></mj-section></mj-body></mjml>"#,
+        )
+        .unwrap()
+        .render(&Options::default())
+        .unwrap();
+        let loader = MemoryIncludeLoader::from(vec![(
+            "partial.mjml",
+            "<mj-section><mj-column><mj-text>World</mj-text></mj-column></mj-section>",
+        )]);
+        let result = render_with_loader(
+            r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text></mj-column></mj-section><mj-include path="partial.mjml" /></mj-body></mjml>"#,
+            loader,
+        );
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn in_section_should_count_included_columns_as_siblings() {
+        let expected = Mjml::parse(
+            r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text></mj-column><mj-column><mj-text>World</mj-text></mj-column></mj-section></mj-body></mjml>"#,
+        )
+        .unwrap()
+        .render(&Options::default())
+        .unwrap();
+        let loader = MemoryIncludeLoader::from(vec![(
+            "column.mjml",
+            "<mj-column><mj-text>World</mj-text></mj-column>",
+        )]);
+        let result = render_with_loader(
+            r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text></mj-column><mj-include path="column.mjml" /></mj-section></mj-body></mjml>"#,
+            loader,
+        );
+        assert!(result.contains("mj-column-per-50"));
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn in_column_should_render_like_inline() {
+        let expected = Mjml::parse(
+            r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text><mj-raw><span>World</span></mj-raw><mj-button>Click</mj-button></mj-column></mj-section></mj-body></mjml>"#,
+        )
+        .unwrap()
+        .render(&Options::default())
+        .unwrap();
+        let loader = MemoryIncludeLoader::from(vec![
+            ("raw.mjml", "<mj-raw><span>World</span></mj-raw>"),
+            ("button.mjml", "<mj-button>Click</mj-button>"),
+        ]);
+        let result = render_with_loader(
+            r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text><mj-include path="raw.mjml" /><mj-include path="button.mjml" /></mj-column></mj-section></mj-body></mjml>"#,
+            loader,
+        );
+        assert_eq!(expected, result);
+    }
 }
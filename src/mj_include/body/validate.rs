@@ -0,0 +1,37 @@
+use super::{MjIncludeBody, MjIncludeBodyChild};
+use crate::mj_include::NAME;
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjIncludeBody {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.transparent_element(NAME, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjIncludeBodyChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjAccordion(elt) => elt.validate_with(validator),
+            Self::MjButton(elt) => elt.validate_with(validator),
+            Self::MjCarousel(elt) => elt.validate_with(validator),
+            Self::MjColumn(elt) => elt.validate_with(validator),
+            Self::MjDivider(elt) => elt.validate_with(validator),
+            Self::MjGroup(elt) => elt.validate_with(validator),
+            Self::MjHero(elt) => elt.validate_with(validator),
+            Self::MjImage(elt) => elt.validate_with(validator),
+            Self::MjNavbar(elt) => elt.validate_with(validator),
+            Self::MjRaw(elt) => elt.validate_with(validator),
+            Self::MjSection(elt) => elt.validate_with(validator),
+            Self::MjSocial(elt) => elt.validate_with(validator),
+            Self::MjSpacer(elt) => elt.validate_with(validator),
+            Self::MjTable(elt) => elt.validate_with(validator),
+            Self::MjText(elt) => elt.validate_with(validator),
+            Self::MjWrapper(elt) => elt.validate_with(validator),
+            Self::Node(elt) => elt.validate_with(validator),
+            Self::Text(elt) => elt.validate_with(validator),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use super::{MjIncludeHead, MjIncludeHeadAttributes, MjIncludeHeadChild, MjIncludeHeadKind};
 use crate::mj_head::MjHeadChild;
-use crate::prelude::parse::{Error, Parsable, Parser, ParserOptions};
+use crate::prelude::parse::{attribute_value, Error, Parsable, Parser, ParserContext, Span};
+use crate::text::Text;
 use std::{rc::Rc, str::FromStr};
 use xmlparser::{StrSpan, Tokenizer};
 
@@ -19,6 +20,7 @@ impl std::convert::TryFrom<MjIncludeHeadChild> for MjHeadChild {
             MjIncludeHeadChild::MjTitle(inner) => Ok(Self::MjTitle(inner)),
             MjIncludeHeadChild::Text(_inner) => Err(Error::InvalidElement(
                 "Text element should be wrapped in another element".into(),
+                Span::default(),
             )),
         }
     }
@@ -28,31 +30,31 @@ impl Parsable for MjIncludeHeadChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
             crate::mj_attributes::NAME => Ok(Self::MjAttributes(
-                crate::mj_attributes::MjAttributes::parse(tag, tokenizer, opts)?,
+                crate::mj_attributes::MjAttributes::parse(tag, tokenizer, ctx)?,
             )),
             crate::mj_breakpoint::NAME => Ok(Self::MjBreakpoint(
-                crate::mj_breakpoint::MjBreakpoint::parse(tag, tokenizer, opts)?,
+                crate::mj_breakpoint::MjBreakpoint::parse(tag, tokenizer, ctx)?,
             )),
             crate::mj_font::NAME => Ok(Self::MjFont(crate::mj_font::MjFont::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_preview::NAME => Ok(Self::MjPreview(crate::mj_preview::MjPreview::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_raw::NAME => Ok(Self::MjRaw(crate::mj_raw::MjRaw::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_style::NAME => Ok(Self::MjStyle(crate::mj_style::MjStyle::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
             crate::mj_title::NAME => Ok(Self::MjTitle(crate::mj_title::MjTitle::parse(
-                tag, tokenizer, opts,
+                tag, tokenizer, ctx,
             )?)),
-            _ => Err(Error::UnexpectedElement(tag.start())),
+            _ => Err(Error::UnexpectedElement(tag.into())),
         }
     }
 }
@@ -65,23 +67,26 @@ impl FromStr for MjIncludeHeadKind {
             "html" => Ok(Self::Html),
             "mjml" => Ok(Self::Mjml),
             "css" => Ok(Self::Css { inline: false }),
-            other => Err(Error::InvalidElement(format!(
-                "invalid mj-include attribute kind {other:?}"
-            ))),
+            other => Err(Error::InvalidElement(
+                format!("invalid mj-include attribute kind {other:?}"),
+                Span::default(),
+            )),
         }
     }
 }
 
 #[derive(Debug)]
 struct MjIncludeHeadParser {
-    opts: Rc<ParserOptions>,
+    ctx: Rc<ParserContext>,
+    span: Span,
     attributes: MjIncludeHeadAttributes,
 }
 
 impl MjIncludeHeadParser {
-    fn new(opts: Rc<ParserOptions>) -> Self {
+    fn new(ctx: Rc<ParserContext>, span: Span) -> Self {
         Self {
-            opts,
+            ctx,
+            span,
             attributes: MjIncludeHeadAttributes::default(),
         }
     }
@@ -91,27 +96,30 @@ impl Parser for MjIncludeHeadParser {
     type Output = MjIncludeHead;
 
     fn build(self) -> Result<Self::Output, Error> {
-        let child = self
-            .opts
+        let path = self
+            .ctx
+            .include_path(&self.attributes.path)
+            .map_err(|err| Error::IncludeLoaderError(err, self.span.clone()))?;
+        let _guard = self.ctx.enter_include(&path, &self.span)?;
+        let content = self
+            .ctx
+            .options
             .include_loader
-            .resolve(&self.attributes.path)
-            .map_err(Error::IncludeLoaderError)?;
+            .resolve(&path)
+            .map_err(|err| Error::IncludeLoaderError(err, self.span.clone()))?;
 
         let children = match self.attributes.kind {
-            MjIncludeHeadKind::Css { inline: false } => {
-                vec![MjIncludeHeadChild::MjStyle(crate::mj_style::MjStyle::from(
-                    child,
-                ))]
+            MjIncludeHeadKind::Css { .. } | MjIncludeHeadKind::Html => {
+                vec![MjIncludeHeadChild::Text(Text::from(content))]
             }
-            MjIncludeHeadKind::Css { inline: true } => todo!(),
             MjIncludeHeadKind::Mjml => {
-                let child = crate::prelude::parse::loader::parse::<MjIncludeHeadChild>(
-                    &child,
-                    self.opts.clone(),
+                let child = crate::prelude::parse::loader::parse_included::<MjIncludeHeadChild>(
+                    &path,
+                    &content,
+                    self.ctx.clone(),
                 )?;
                 vec![child]
             }
-            MjIncludeHeadKind::Html => todo!(),
         };
 
         Ok(MjIncludeHead {
@@ -123,15 +131,28 @@ impl Parser for MjIncludeHeadParser {
     fn parse_attribute<'a>(&mut self, name: StrSpan<'a>, value: StrSpan<'a>) -> Result<(), Error> {
         match name.as_str() {
             "path" => {
-                self.attributes.path = value.to_string();
+                self.attributes.path = attribute_value(value);
             }
             "type" => {
-                self.attributes.kind = MjIncludeHeadKind::from_str(value.as_str())?;
+                self.attributes.kind = match MjIncludeHeadKind::from_str(value.as_str())
+                    .map_err(|err| err.with_span(value))?
+                {
+                    // keep the css-inline attribute if it was set before the type
+                    MjIncludeHeadKind::Css { .. } => MjIncludeHeadKind::Css {
+                        inline: matches!(
+                            self.attributes.kind,
+                            MjIncludeHeadKind::Css { inline: true }
+                        ),
+                    },
+                    other => other,
+                };
             }
             "css-inline" => {
-                self.attributes.kind = MjIncludeHeadKind::Css { inline: true };
+                self.attributes.kind = MjIncludeHeadKind::Css {
+                    inline: value.as_str() == "inline",
+                };
             }
-            _ => return Err(Error::UnexpectedAttribute(name.start())),
+            _ => return Err(Error::UnexpectedAttribute(name.into())),
         }
         Ok(())
     }
@@ -139,17 +160,19 @@ impl Parser for MjIncludeHeadParser {
 
 impl Parsable for MjIncludeHead {
     fn parse(
-        _tag: StrSpan,
+        tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjIncludeHeadParser::new(opts).parse(tokenizer)?.build()
+        MjIncludeHeadParser::new(ctx.clone(), tag.into())
+            .parse(tokenizer, &ctx)?
+            .build()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use crate::mj_include::head::MjIncludeHeadKind;
     use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
@@ -165,7 +188,7 @@ mod tests {
 "#;
         let err = crate::mjml::Mjml::parse(json).unwrap_err();
         match err {
-            Error::IncludeLoaderError(origin) => {
+            Error::IncludeLoaderError(origin, _) => {
                 assert_eq!(origin.reason, std::io::ErrorKind::NotFound);
             }
             _ => panic!("expected a IncludeLoaderError"),
@@ -178,6 +201,7 @@ mod tests {
             MemoryIncludeLoader::from(vec![("basic.mjml", "<mj-title>Hello</mj-title>")]);
         let opts = ParserOptions {
             include_loader: Box::new(resolver),
+            ..Default::default()
         };
         let json = r#"<mjml>
   <mj-head>
@@ -186,7 +210,7 @@ mod tests {
   <mj-body></mj-body>
 </mjml>
 "#;
-        let root = crate::mjml::Mjml::parse_with_options(json, Rc::new(opts)).unwrap();
+        let root = crate::mjml::Mjml::parse_with_options(json, Arc::new(opts)).unwrap();
         let head = root.children.head.unwrap();
         let include = head.children.first().unwrap().as_mj_include().unwrap();
         assert_eq!(include.attributes.kind, MjIncludeHeadKind::Mjml);
@@ -199,6 +223,7 @@ mod tests {
             MemoryIncludeLoader::from(vec![("partial.css", "* { background-color: red; }")]);
         let opts = ParserOptions {
             include_loader: Box::new(resolver),
+            ..Default::default()
         };
         let json = r#"<mjml>
   <mj-head>
@@ -207,7 +232,7 @@ mod tests {
   <mj-body></mj-body>
 </mjml>
 "#;
-        let root = crate::mjml::Mjml::parse_with_options(json, Rc::new(opts)).unwrap();
+        let root = crate::mjml::Mjml::parse_with_options(json, Arc::new(opts)).unwrap();
         let head = root.children.head.unwrap();
         let include = head.children.first().unwrap().as_mj_include().unwrap();
         assert_eq!(
@@ -216,4 +241,69 @@ mod tests {
         );
         let _content = include.children.first().unwrap();
     }
+
+    #[test]
+    fn type_css_inline_in_memory_resolver() {
+        let resolver =
+            MemoryIncludeLoader::from(vec![("partial.css", "* { background-color: red; }")]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            ..Default::default()
+        });
+        for template in [
+            r#"<mjml><mj-head><mj-include path="partial.css" type="css" css-inline="inline" /></mj-head></mjml>"#,
+            r#"<mjml><mj-head><mj-include path="partial.css" css-inline="inline" type="css" /></mj-head></mjml>"#,
+        ] {
+            let root = crate::mjml::Mjml::parse_with_options(template, opts.clone()).unwrap();
+            let head = root.children.head.unwrap();
+            let include = head.children.first().unwrap().as_mj_include().unwrap();
+            assert_eq!(
+                include.attributes.kind,
+                MjIncludeHeadKind::Css { inline: true }
+            );
+            assert_eq!(
+                include
+                    .children
+                    .first()
+                    .unwrap()
+                    .as_text()
+                    .unwrap()
+                    .inner_str(),
+                "* { background-color: red; }"
+            );
+        }
+    }
+
+    #[test]
+    fn type_html_in_memory_resolver() {
+        let resolver = MemoryIncludeLoader::from(vec![(
+            "partial.html",
+            "<meta name=\"x-apple-disable-message-reformatting\">",
+        )]);
+        let opts = ParserOptions {
+            include_loader: Box::new(resolver),
+            ..Default::default()
+        };
+        let json = r#"<mjml>
+  <mj-head>
+    <mj-include path="partial.html" type="html" />
+  </mj-head>
+  <mj-body></mj-body>
+</mjml>
+"#;
+        let root = crate::mjml::Mjml::parse_with_options(json, Arc::new(opts)).unwrap();
+        let head = root.children.head.unwrap();
+        let include = head.children.first().unwrap().as_mj_include().unwrap();
+        assert_eq!(include.attributes.kind, MjIncludeHeadKind::Html);
+        assert_eq!(
+            include
+                .children
+                .first()
+                .unwrap()
+                .as_text()
+                .unwrap()
+                .inner_str(),
+            "<meta name=\"x-apple-disable-message-reformatting\">"
+        );
+    }
 }
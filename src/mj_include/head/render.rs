@@ -122,4 +122,53 @@ mod tests {
         };
         similar_asserts::assert_eq!(expected, result);
     }
+
+    #[test]
+    fn css_inline_kind() {
+        let expected = {
+            let opts = Options::default();
+            let mut mj_style = MjStyle::from("* { background-color: red; }".to_string());
+            mj_style.attributes.inline = Some("inline".to_string());
+            let mut mj_head = MjHead::default();
+            mj_head.children.push(mj_style.into());
+            let mut root = Mjml::default();
+            root.children.head = Some(mj_head);
+            root.children.body = Some(MjBody::default());
+            root.render(&opts).unwrap()
+        };
+        let result = {
+            let opts = Options::default();
+            let mut mj_include = MjIncludeHead::default();
+            mj_include.attributes.path = "partial.css".to_owned();
+            mj_include.attributes.kind = MjIncludeHeadKind::Css { inline: true };
+            mj_include
+                .children
+                .push(Text::from("* { background-color: red; }".to_string()).into());
+            let mut mj_head = MjHead::default();
+            mj_head.children.push(mj_include.into());
+            let mut root = Mjml::default();
+            root.children.head = Some(mj_head);
+            root.children.body = Some(MjBody::default());
+            root.render(&opts).unwrap()
+        };
+        similar_asserts::assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn html_kind() {
+        let opts = Options::default();
+        let mut mj_include = MjIncludeHead::default();
+        mj_include.attributes.path = "partial.html".to_owned();
+        mj_include.attributes.kind = MjIncludeHeadKind::Html;
+        mj_include.children.push(
+            Text::from("<meta name=\"x-apple-disable-message-reformatting\">".to_string()).into(),
+        );
+        let mut mj_head = MjHead::default();
+        mj_head.children.push(mj_include.into());
+        let mut root = Mjml::default();
+        root.children.head = Some(mj_head);
+        root.children.body = Some(MjBody::default());
+        let result = root.render(&opts).unwrap();
+        assert!(result.contains("<meta name=\"x-apple-disable-message-reformatting\"></head>"));
+    }
 }
@@ -0,0 +1,27 @@
+use super::{MjIncludeHead, MjIncludeHeadChild};
+use crate::mj_include::NAME;
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjIncludeHead {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.transparent_element(NAME, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjIncludeHeadChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjAttributes(elt) => elt.validate_with(validator),
+            Self::MjBreakpoint(elt) => elt.validate_with(validator),
+            Self::MjFont(elt) => elt.validate_with(validator),
+            Self::MjPreview(elt) => elt.validate_with(validator),
+            Self::MjRaw(elt) => elt.validate_with(validator),
+            Self::MjStyle(elt) => elt.validate_with(validator),
+            Self::MjTitle(elt) => elt.validate_with(validator),
+            Self::Text(elt) => elt.validate_with(validator),
+        }
+    }
+}
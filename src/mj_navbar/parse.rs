@@ -2,18 +2,18 @@ use std::rc::Rc;
 
 use super::MjNavbarChild;
 use crate::mj_navbar_link::{MjNavbarLink, NAME as MJ_NAVBAR_LINK};
-use crate::prelude::parse::{Error, Parsable, ParserOptions};
+use crate::prelude::parse::{Error, Parsable, ParserContext};
 use xmlparser::{StrSpan, Tokenizer};
 
 impl Parsable for MjNavbarChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
         match tag.as_str() {
-            MJ_NAVBAR_LINK => Ok(MjNavbarLink::parse(tag, tokenizer, opts)?.into()),
-            _ => Err(Error::UnexpectedElement(tag.start())),
+            MJ_NAVBAR_LINK => Ok(MjNavbarLink::parse(tag, tokenizer, ctx)?.into()),
+            _ => Err(Error::UnexpectedElement(tag.into())),
         }
     }
 }
@@ -200,12 +200,30 @@ impl<'e, 'h> Render<'h> for MjNavbarRender<'e, 'h> {
     }
 }
 
+impl MjNavbar {
+    /// Attributes of the element and of its children, to generate a stable identifier.
+    fn content_key(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                MjNavbarChild::MjNavbarLink(item) => Some(random::attributes_key(&item.attributes)),
+                _ => None,
+            })
+            .fold(random::attributes_key(&self.attributes), |res, item| {
+                res + "|" + &item
+            })
+    }
+}
+
 impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for MjNavbar {
     fn renderer(&'e self, header: Rc<RefCell<Header<'h>>>) -> Box<dyn Render<'h> + 'r> {
+        let id = header
+            .borrow_mut()
+            .generate_id(NAME, &self.content_key(), 8);
         Box::new(MjNavbarRender::<'e, 'h> {
             element: self,
             header,
-            id: random::generate(8),
+            id,
             container_width: None,
             siblings: 1,
             raw_siblings: 0,
@@ -0,0 +1,43 @@
+use super::{MjNavbar, MjNavbarChild, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("base-url", Grammar::String),
+        ("hamburger", Grammar::String),
+        ("ico-align", Grammar::ALIGN),
+        ("ico-close", Grammar::String),
+        ("ico-color", Grammar::Color),
+        ("ico-font-family", Grammar::String),
+        ("ico-font-size", Grammar::SIZE),
+        ("ico-line-height", Grammar::LINE_HEIGHT),
+        ("ico-open", Grammar::String),
+        ("ico-padding", Grammar::SPACING),
+        ("ico-padding-bottom", Grammar::SIZE),
+        ("ico-padding-left", Grammar::SIZE),
+        ("ico-padding-right", Grammar::SIZE),
+        ("ico-padding-top", Grammar::SIZE),
+        ("ico-text-decoration", Grammar::String),
+        ("ico-text-transform", Grammar::String),
+    ],
+};
+
+impl Validate for MjNavbar {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjNavbarChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjNavbarLink(elt) => elt.validate_with(validator),
+        }
+    }
+}
@@ -18,6 +18,7 @@ impl<'e, 'h> MjNavbarLinkRender<'e, 'h> {
     fn set_style_a(&self, tag: Tag) -> Tag {
         tag.add_style("display", "inline-block")
             .maybe_add_style("color", self.attribute("color"))
+            .maybe_add_class(self.dark_class("color", "color"))
             .maybe_add_style("font-family", self.attribute("font-family"))
             .maybe_add_style("font-size", self.attribute("font-size"))
             .maybe_add_style("font-style", self.attribute("font-style"))
@@ -41,11 +42,13 @@ impl<'e, 'h> MjNavbarLinkRender<'e, 'h> {
             .maybe_add_style("padding-left", self.attribute("padding-left"))
     }
 
-    fn get_link(&self) -> Option<String> {
-        self.attribute("href").as_ref().and_then(|href| {
-            self.attribute("navbar-base-url")
+    fn get_link(&self, opts: &Options) -> Option<String> {
+        self.attribute("href").as_ref().map(|href| {
+            let link = self
+                .attribute("navbar-base-url")
                 .map(move |base| format!("{base}{href}"))
-                .or_else(|| Some(href.to_string()))
+                .unwrap_or_else(|| href.to_string());
+            self.rewrite_link(link, opts)
         })
     }
 
@@ -54,7 +57,7 @@ impl<'e, 'h> MjNavbarLinkRender<'e, 'h> {
             .set_style_a(Tag::new("a"))
             .add_class("mj-link")
             .maybe_add_class(self.attribute("css-class"))
-            .maybe_add_attribute("href", self.get_link())
+            .maybe_add_attribute("href", self.get_link(opts))
             .maybe_add_attribute("rel", self.attribute("rel"))
             .maybe_add_attribute("target", self.attribute("target"))
             .maybe_add_attribute("name", self.attribute("name"));
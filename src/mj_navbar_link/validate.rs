@@ -0,0 +1,33 @@
+use super::{MjNavbarLink, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_navbar::NAME],
+    attributes: &[
+        ("color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-style", Grammar::String),
+        ("font-weight", Grammar::String),
+        ("href", Grammar::String),
+        ("letter-spacing", Grammar::String),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("name", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("rel", Grammar::String),
+        ("target", Grammar::String),
+        ("text-decoration", Grammar::String),
+        ("text-transform", Grammar::String),
+    ],
+};
+
+impl Validate for MjNavbarLink {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -1,6 +1,6 @@
 use super::{MjRaw, MjRawChild};
 use crate::node::Node;
-use crate::prelude::parse::{Error, Parsable, Parser, ParserOptions};
+use crate::prelude::parse::{Error, Parsable, Parser, ParserContext};
 use crate::{parse_child, parse_comment, parse_text};
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
@@ -9,22 +9,22 @@ impl Parsable for MjRawChild {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        Ok(Node::<MjRawChild>::parse(tag, tokenizer, opts)?.into())
+        Ok(Node::<MjRawChild>::parse(tag, tokenizer, ctx)?.into())
     }
 }
 
 #[derive(Debug, Default)]
 struct MjRawParser {
-    opts: Rc<ParserOptions>,
+    ctx: Rc<ParserContext>,
     children: Vec<MjRawChild>,
 }
 
 impl MjRawParser {
-    fn new(opts: Rc<ParserOptions>) -> Self {
+    fn new(ctx: Rc<ParserContext>) -> Self {
         Self {
-            opts,
+            ctx,
             children: Vec::new(),
         }
     }
@@ -48,8 +48,10 @@ impl Parsable for MjRaw {
     fn parse(
         _tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjRawParser::new(opts).parse(tokenizer)?.build()
+        MjRawParser::new(ctx.clone())
+            .parse(tokenizer, &ctx)?
+            .build()
     }
 }
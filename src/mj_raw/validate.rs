@@ -0,0 +1,9 @@
+use super::{MjRaw, NAME};
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjRaw {
+    fn validate_with(&self, validator: &mut Validator) {
+        // the content of mj-raw is left untouched
+        validator.element(NAME, &[], |_| {});
+    }
+}
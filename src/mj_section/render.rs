@@ -1,7 +1,8 @@
 use super::{MjSection, NAME};
-use crate::helper::condition::{conditional_tag, END_CONDITIONAL_TAG, START_CONDITIONAL_TAG};
+use crate::helper::condition::{END_CONDITIONAL_TAG, START_CONDITIONAL_TAG};
 use crate::helper::size::{Percent, Pixel};
 use crate::helper::tag::Tag;
+use crate::mj_body::MjBodyChild;
 use crate::prelude::hash::Map;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
@@ -82,6 +83,7 @@ pub trait WithMjSectionBackground<'h>: Render<'h> {
     }
 
     fn set_background_style(&self, tag: Tag) -> Tag {
+        let tag = tag.maybe_add_class(self.dark_class("background-color", "background-color"));
         if self.has_background() {
             tag.maybe_add_style("background", self.get_background())
                 .add_style("background-position", self.get_background_position_str())
@@ -204,7 +206,10 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
         self.attribute_exists("full-width")
     }
 
-    fn render_with_background<T: AsRef<str>>(&self, content: T) -> String {
+    fn render_with_background<F>(&self, buf: &mut String, content: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut String) -> Result<(), Error>,
+    {
         let full_width = self.is_full_width();
         let vrect = Tag::new("v:rect")
             .maybe_add_attribute(
@@ -226,9 +231,15 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
         let vtextbox = Tag::new("v:textbox")
             .add_attribute("inset", "0,0,0,0")
             .add_style("mso-fit-shape-to-text", "true");
-        let before = vrect.open() + &vfill.closed() + &vtextbox.open();
-        let after = vtextbox.close() + &vrect.close();
-        before + END_CONDITIONAL_TAG + content.as_ref() + START_CONDITIONAL_TAG + &after
+        buf.push_str(&vrect.open());
+        buf.push_str(&vfill.closed());
+        buf.push_str(&vtextbox.open());
+        buf.push_str(END_CONDITIONAL_TAG);
+        content(buf)?;
+        buf.push_str(START_CONDITIONAL_TAG);
+        buf.push_str(&vtextbox.close());
+        buf.push_str(&vrect.close());
+        Ok(())
     }
 
     fn set_style_section_div(&self, tag: Tag) -> Tag {
@@ -245,7 +256,10 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
             )
     }
 
-    fn render_wrap<T: AsRef<str>>(&self, content: T) -> String {
+    fn render_wrap<F>(&self, buf: &mut String, content: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut String) -> Result<(), Error>,
+    {
         let table = Tag::table_presentation()
             .maybe_add_attribute("bgcolor", self.attribute("background-color"))
             .add_attribute("align", "center")
@@ -265,48 +279,58 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
             .add_style("line-height", "0px")
             .add_style("font-size", "0px")
             .add_style("mso-line-height-rule", "exactly");
-        let before = table.open() + &tr.open() + &td.open();
-        let after = td.close() + &tr.close() + &table.close();
-        conditional_tag(before + content.as_ref() + &after)
+        buf.push_str(START_CONDITIONAL_TAG);
+        buf.push_str(&table.open());
+        buf.push_str(&tr.open());
+        buf.push_str(&td.open());
+        content(buf)?;
+        buf.push_str(&td.close());
+        buf.push_str(&tr.close());
+        buf.push_str(&table.close());
+        buf.push_str(END_CONDITIONAL_TAG);
+        Ok(())
     }
 
     fn get_siblings(&self) -> usize {
-        self.children().len()
+        MjBodyChild::flatten_renderables(self.children()).len()
     }
 
     fn get_raw_siblings(&self) -> usize {
-        self.children().iter().filter(|elt| elt.is_raw()).count()
+        MjBodyChild::flatten_renderables(self.children())
+            .iter()
+            .filter(|elt| elt.is_raw())
+            .count()
     }
 
-    fn render_wrapped_children(&self, opts: &Options) -> Result<String, Error> {
+    fn render_wrapped_children(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let siblings = self.get_siblings();
         let raw_siblings = self.get_raw_siblings();
         let tr = Tag::tr();
-        let mut result = tr.open();
-        let children = self.children();
+        buf.push_str(&tr.open());
+        let children = MjBodyChild::flatten_renderables(self.children());
         if !children.is_empty() {
-            for child in self.children().iter() {
+            for child in children.iter() {
                 let mut renderer = child.renderer(self.clone_header());
                 renderer.set_siblings(siblings);
                 renderer.set_raw_siblings(raw_siblings);
                 renderer.set_container_width(self.container_width().clone());
                 if child.is_raw() {
-                    result.push_str(&renderer.render(opts)?);
+                    renderer.render_into(buf, opts)?;
                 } else {
                     let td = renderer
                         .set_style("td-outlook", Tag::td())
                         .maybe_add_attribute("align", renderer.attribute("align"))
                         .maybe_add_suffixed_class(renderer.attribute("css-class"), "outlook");
-                    result.push_str(&td.open());
-                    result.push_str(END_CONDITIONAL_TAG);
-                    result.push_str(&renderer.render(opts)?);
-                    result.push_str(START_CONDITIONAL_TAG);
-                    result.push_str(&td.close());
+                    buf.push_str(&td.open());
+                    buf.push_str(END_CONDITIONAL_TAG);
+                    renderer.render_into(buf, opts)?;
+                    buf.push_str(START_CONDITIONAL_TAG);
+                    buf.push_str(&td.close());
                 }
             }
         }
-        result.push_str(&tr.close());
-        Ok(result)
+        buf.push_str(&tr.close());
+        Ok(())
     }
 
     fn set_style_section_inner_div(&self, tag: Tag) -> Tag {
@@ -340,7 +364,7 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
             .maybe_add_style("text-align", self.attribute("text-align"))
     }
 
-    fn render_section(&self, opts: &Options) -> Result<String, Error> {
+    fn render_section(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let is_full_width = self.is_full_width();
         let div = self
             .set_style_section_div(Tag::div())
@@ -365,19 +389,31 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
         let tbody = Tag::tbody();
         let tr = Tag::tr();
         let td = self.set_style_section_td(Tag::td());
-        let inner_table = Tag::table_presentation();
-
-        let content = self.render_wrapped_children(opts)?;
-        let content = conditional_tag(inner_table.open() + &content + &inner_table.close());
-        let content = td.render(content);
-        let content = tr.render(content);
-        let content = tbody.render(content);
-        let content = table.render(content);
-        Ok(div.render(if self.has_background() {
-            inner_div.render(content)
-        } else {
-            content
-        }))
+        let inner_table = Tag::table_presentation().maybe_add_attribute("dir", self.outlook_dir());
+
+        let has_background = self.has_background();
+        buf.push_str(&div.open());
+        if has_background {
+            buf.push_str(&inner_div.open());
+        }
+        buf.push_str(&table.open());
+        buf.push_str(&tbody.open());
+        buf.push_str(&tr.open());
+        buf.push_str(&td.open());
+        buf.push_str(START_CONDITIONAL_TAG);
+        buf.push_str(&inner_table.open());
+        self.render_wrapped_children(buf, opts)?;
+        buf.push_str(&inner_table.close());
+        buf.push_str(END_CONDITIONAL_TAG);
+        buf.push_str(&td.close());
+        buf.push_str(&tr.close());
+        buf.push_str(&tbody.close());
+        buf.push_str(&table.close());
+        if has_background {
+            buf.push_str(&inner_div.close());
+        }
+        buf.push_str(&div.close());
+        Ok(())
     }
 
     fn set_style_table_full_width(&self, tag: Tag) -> Tag {
@@ -397,31 +433,46 @@ pub trait SectionLikeRender<'h>: WithMjSectionBackground<'h> {
             .maybe_add_attribute("background", self.attribute("background-url"))
     }
 
-    fn render_full_width(&self, opts: &Options) -> Result<String, Error> {
+    fn render_full_width(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let table = self.get_full_width_table();
         let tbody = Tag::tbody();
         let tr = Tag::tr();
         let td = Tag::td();
-        let content = self.render_section(opts)?;
-        let content =
-            self.render_wrap(END_CONDITIONAL_TAG.to_string() + &content + START_CONDITIONAL_TAG);
-        let content = if self.has_background() {
-            self.render_with_background(content)
-        } else {
-            content
+        buf.push_str(&table.open());
+        buf.push_str(&tbody.open());
+        buf.push_str(&tr.open());
+        buf.push_str(&td.open());
+        let wrap = |buf: &mut String| {
+            self.render_wrap(buf, |buf| {
+                buf.push_str(END_CONDITIONAL_TAG);
+                self.render_section(buf, opts)?;
+                buf.push_str(START_CONDITIONAL_TAG);
+                Ok(())
+            })
         };
-        Ok(table.render(tbody.render(tr.render(td.render(content)))))
+        if self.has_background() {
+            self.render_with_background(buf, wrap)?;
+        } else {
+            wrap(buf)?;
+        }
+        buf.push_str(&td.close());
+        buf.push_str(&tr.close());
+        buf.push_str(&tbody.close());
+        buf.push_str(&table.close());
+        Ok(())
     }
 
-    fn render_simple(&self, opts: &Options) -> Result<String, Error> {
-        let section = self.render_section(opts)?;
-
-        let section = if self.has_background() {
-            self.render_with_background(section)
-        } else {
-            END_CONDITIONAL_TAG.to_string() + &section + START_CONDITIONAL_TAG
-        };
-        Ok(self.render_wrap(section))
+    fn render_simple(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
+        self.render_wrap(buf, |buf| {
+            if self.has_background() {
+                self.render_with_background(buf, |buf| self.render_section(buf, opts))
+            } else {
+                buf.push_str(END_CONDITIONAL_TAG);
+                self.render_section(buf, opts)?;
+                buf.push_str(START_CONDITIONAL_TAG);
+                Ok(())
+            }
+        })
     }
 }
 
@@ -452,7 +503,7 @@ impl<'e, 'h> Render<'h> for MjSectionRender<'e, 'h> {
             "background-position" => Some("top center"),
             "background-repeat" => Some("repeat"),
             "background-size" => Some("auto"),
-            "direction" => Some("ltr"),
+            "direction" => Some(self.header().direction()),
             "padding" => Some("20px 0"),
             "text-align" => Some("center"),
             "text-padding" => Some("4px 4px 4px 0"),
@@ -477,10 +528,16 @@ impl<'e, 'h> Render<'h> for MjSectionRender<'e, 'h> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
+        let mut buf = String::default();
+        self.render_into(&mut buf, opts)?;
+        Ok(buf)
+    }
+
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         if self.is_full_width() {
-            self.render_full_width(opts)
+            self.render_full_width(buf, opts)
         } else {
-            self.render_simple(opts)
+            self.render_simple(buf, opts)
         }
     }
 }
@@ -0,0 +1,48 @@
+use super::{MjSocial, MjSocialChild, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("border-radius", Grammar::SIZE),
+        ("color", Grammar::Color),
+        ("container-background-color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-style", Grammar::String),
+        ("font-weight", Grammar::String),
+        ("icon-height", Grammar::SIZE),
+        ("icon-padding", Grammar::SPACING),
+        ("icon-size", Grammar::SIZE),
+        ("inner-padding", Grammar::SPACING),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("mode", Grammar::Enum(&["horizontal", "vertical"])),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("table-layout", Grammar::Enum(&["auto", "fixed"])),
+        ("text-decoration", Grammar::String),
+        ("text-padding", Grammar::SPACING),
+    ],
+};
+
+impl Validate for MjSocial {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
+
+impl Validate for MjSocialChild {
+    fn validate_with(&self, validator: &mut Validator) {
+        match self {
+            Self::Comment(elt) => elt.validate_with(validator),
+            Self::MjSocialElement(elt) => elt.validate_with(validator),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub const DEFAULT_ICON_ORIGIN: &str = "https://www.mailjet.com/images/theme/v1/icons/ico-social/";
+
 pub struct SocialNetwork {
     background_color: String,
     share_url: Option<String>,
@@ -1,4 +1,4 @@
-use super::network::SocialNetwork;
+use super::network::{SocialNetwork, DEFAULT_ICON_ORIGIN};
 use super::{MjSocialElement, NAME};
 use crate::helper::size::{Pixel, Size};
 use crate::helper::tag::Tag;
@@ -7,8 +7,6 @@ use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
 use std::rc::Rc;
 
-const DEFAULT_ICON_ORIGIN: &str = "https://www.mailjet.com/images/theme/v1/icons/ico-social/";
-
 struct MjSocialElementRender<'e, 'h> {
     header: Rc<RefCell<Header<'h>>>,
     element: &'e MjSocialElement,
@@ -86,6 +84,7 @@ impl<'e, 'h> MjSocialElementRender<'e, 'h> {
 
     fn set_style_text(&self, tag: Tag) -> Tag {
         tag.maybe_add_style("color", self.attribute("color"))
+            .maybe_add_class(self.dark_class("color", "color"))
             .maybe_add_style("font-size", self.attribute("font-size"))
             .maybe_add_style("font-weight", self.attribute("font-weight"))
             .maybe_add_style("font-style", self.attribute("font-style"))
@@ -94,15 +93,15 @@ impl<'e, 'h> MjSocialElementRender<'e, 'h> {
             .maybe_add_style("text-decoration", self.attribute("text-decoration"))
     }
 
-    fn get_href(&self) -> Option<String> {
-        self.attribute("href")
-            .map(|href| {
-                self.network
-                    .as_ref()
-                    .and_then(|net| net.share_url(&href))
-                    .or(Some(href))
-            })
-            .unwrap_or_default()
+    fn get_href(&self, opts: &Options) -> Option<String> {
+        self.attribute("href").map(|href| {
+            let link = self
+                .network
+                .as_ref()
+                .and_then(|net| net.share_url(&href))
+                .unwrap_or(href);
+            self.rewrite_link(link, opts)
+        })
     }
 
     fn render_icon(&self, href: &Option<String>, opts: &Options) -> String {
@@ -163,7 +162,7 @@ impl<'e, 'h> MjSocialElementRender<'e, 'h> {
 impl<'e, 'h> Render<'h> for MjSocialElementRender<'e, 'h> {
     fn default_attribute(&self, key: &str) -> Option<&str> {
         match key {
-            "align" => Some("left"),
+            "align" => Some(self.header().start_side()),
             "color" => Some("#000"),
             "border-radius" => Some("3px"),
             "font-family" => Some("Ubuntu, Helvetica, Arial, sans-serif"),
@@ -172,7 +171,11 @@ impl<'e, 'h> Render<'h> for MjSocialElementRender<'e, 'h> {
             "padding" => Some("4px"),
             "target" => Some("_blank"),
             "text-decoration" => Some("none"),
-            "text-padding" => Some("4px 4px 4px 0"),
+            "text-padding" => Some(if self.header().is_rtl() {
+                "4px 0 4px 4px"
+            } else {
+                "4px 4px 4px 0"
+            }),
             "vertical-align" => Some("middle"),
             _ => None,
         }
@@ -203,7 +206,7 @@ impl<'e, 'h> Render<'h> for MjSocialElementRender<'e, 'h> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
-        let href = self.get_href();
+        let href = self.get_href(opts);
         let tr = Tag::tr().maybe_add_class(self.attribute("css-class"));
         let td = self.set_style_td(Tag::td());
 
@@ -0,0 +1,44 @@
+use super::{MjSocialElement, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_social::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("alt", Grammar::String),
+        ("background-color", Grammar::Color),
+        ("border-radius", Grammar::PIXEL),
+        ("color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-style", Grammar::String),
+        ("font-weight", Grammar::String),
+        ("href", Grammar::String),
+        ("icon-height", Grammar::SIZE),
+        ("icon-padding", Grammar::SPACING),
+        ("icon-size", Grammar::SIZE),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("name", Grammar::String),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("rel", Grammar::String),
+        ("sizes", Grammar::String),
+        ("src", Grammar::String),
+        ("srcset", Grammar::String),
+        ("target", Grammar::String),
+        ("text-decoration", Grammar::String),
+        ("text-padding", Grammar::SPACING),
+        ("title", Grammar::String),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+    ],
+};
+
+impl Validate for MjSocialElement {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
@@ -0,0 +1,27 @@
+use super::{MjSpacer, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("border", Grammar::String),
+        ("border-bottom", Grammar::String),
+        ("border-left", Grammar::String),
+        ("border-right", Grammar::String),
+        ("border-top", Grammar::String),
+        ("container-background-color", Grammar::Color),
+        ("height", Grammar::SIZE),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+    ],
+};
+
+impl Validate for MjSpacer {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
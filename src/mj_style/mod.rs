@@ -4,10 +4,12 @@ mod json;
 mod parse;
 #[cfg(feature = "print")]
 mod print;
+#[cfg(feature = "validate")]
+mod validate;
 
 pub const NAME: &str = "mj-style";
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintAttributes))]
 pub struct MjStyleAttributes {
@@ -15,7 +17,7 @@ pub struct MjStyleAttributes {
     pub inline: Option<String>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "print", derive(mrml_print_macros::MrmlPrintComponent))]
 #[cfg_attr(feature = "print", mrml_print(tag = "NAME", indent_children = false))]
 #[cfg_attr(feature = "json", derive(mrml_json_macros::MrmlJsonComponent))]
@@ -29,6 +31,10 @@ impl MjStyle {
     pub fn children(&self) -> &str {
         &self.children
     }
+
+    pub fn is_inline(&self) -> bool {
+        self.attributes.inline.as_deref() == Some("inline")
+    }
 }
 
 impl From<String> for MjStyle {
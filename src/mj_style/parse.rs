@@ -1,5 +1,5 @@
 use super::{MjStyle, MjStyleAttributes};
-use crate::prelude::parse::{Error, Parsable, Parser, ParserOptions};
+use crate::prelude::parse::{attribute_value, Error, Parsable, Parser, ParserContext};
 use std::rc::Rc;
 use xmlparser::{StrSpan, Tokenizer};
 
@@ -10,7 +10,7 @@ struct MjStyleParser {
 }
 
 impl MjStyleParser {
-    fn new(_opts: Rc<ParserOptions>) -> Self {
+    fn new(_ctx: Rc<ParserContext>) -> Self {
         Self {
             attributes: Default::default(),
             children: Default::default(),
@@ -30,10 +30,10 @@ impl Parser for MjStyleParser {
 
     fn parse_attribute<'a>(&mut self, name: StrSpan<'a>, value: StrSpan<'a>) -> Result<(), Error> {
         if name.as_str() == "inline" {
-            self.attributes.inline = Some(value.to_string());
+            self.attributes.inline = Some(attribute_value(value));
             Ok(())
         } else {
-            Err(Error::UnexpectedAttribute(name.start()))
+            Err(Error::UnexpectedAttribute(name.into()))
         }
     }
 
@@ -47,9 +47,11 @@ impl Parsable for MjStyle {
     fn parse(
         _tag: StrSpan,
         tokenizer: &mut Tokenizer,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error> {
-        MjStyleParser::new(opts).parse(tokenizer)?.build()
+        MjStyleParser::new(ctx.clone())
+            .parse(tokenizer, &ctx)?
+            .build()
     }
 }
 
@@ -0,0 +1,12 @@
+use super::{MjStyle, NAME};
+use crate::prelude::validate::{Grammar, Validate, Validator};
+
+impl Validate for MjStyle {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mj_head::NAME], |validator| {
+            if let Some(ref inline) = self.attributes.inline {
+                validator.attribute("inline", inline, Grammar::Enum(&["inline"]));
+            }
+        });
+    }
+}
@@ -18,6 +18,7 @@ impl<'e, 'h> WithMjSectionBackground<'h> for MjTableRender<'e, 'h> {}
 impl<'e, 'h> MjTableRender<'e, 'h> {
     fn set_style_table(&self, tag: Tag) -> Tag {
         tag.maybe_add_style("color", self.attribute("color"))
+            .maybe_add_class(self.dark_class("color", "color"))
             .maybe_add_style("font-family", self.attribute("font-family"))
             .maybe_add_style("font-size", self.attribute("font-size"))
             .maybe_add_style("line-height", self.attribute("line-height"))
@@ -30,7 +31,7 @@ impl<'e, 'h> MjTableRender<'e, 'h> {
 impl<'e, 'h> Render<'h> for MjTableRender<'e, 'h> {
     fn default_attribute(&self, name: &str) -> Option<&str> {
         match name {
-            "align" => Some("left"),
+            "align" => Some(self.header().start_side()),
             "border" => Some("none"),
             "cellpadding" => Some("0"),
             "cellspacing" => Some("0"),
@@ -0,0 +1,37 @@
+use super::{MjTable, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        ("align", Grammar::ALIGN),
+        ("border", Grammar::String),
+        ("cellpadding", Grammar::Integer),
+        ("cellspacing", Grammar::Integer),
+        ("color", Grammar::Color),
+        ("container-background-color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-weight", Grammar::String),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("role", Grammar::Enum(&["none", "presentation"])),
+        (
+            "table-layout",
+            Grammar::Enum(&["auto", "fixed", "initial", "inherit"]),
+        ),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+        ("width", Grammar::String),
+    ],
+};
+
+impl Validate for MjTable {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
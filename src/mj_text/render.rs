@@ -23,6 +23,7 @@ impl<'e, 'h> MjTextRender<'e, 'h> {
             .maybe_add_style("text-decoration", self.attribute("text-decoration"))
             .maybe_add_style("text-transform", self.attribute("text-transform"))
             .maybe_add_style("color", self.attribute("color"))
+            .maybe_add_class(self.dark_class("color", "color"))
             .maybe_add_style("height", self.attribute("height"))
     }
 
@@ -54,7 +55,7 @@ impl<'e, 'h> MjTextRender<'e, 'h> {
 impl<'e, 'h> Render<'h> for MjTextRender<'e, 'h> {
     fn default_attribute(&self, key: &str) -> Option<&str> {
         match key {
-            "align" => Some("left"),
+            "align" => Some(self.header().start_side()),
             "color" => Some("#000000"),
             "font-family" => Some("Ubuntu, Helvetica, Arial, sans-serif"),
             "font-size" => Some("13px"),
@@ -0,0 +1,36 @@
+use super::{MjText, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_column::NAME, crate::mj_hero::NAME],
+    attributes: &[
+        (
+            "align",
+            Grammar::Enum(&["left", "right", "center", "justify"]),
+        ),
+        ("color", Grammar::Color),
+        ("container-background-color", Grammar::Color),
+        ("font-family", Grammar::String),
+        ("font-size", Grammar::PIXEL),
+        ("font-style", Grammar::String),
+        ("font-weight", Grammar::String),
+        ("height", Grammar::SIZE),
+        ("letter-spacing", Grammar::String),
+        ("line-height", Grammar::LINE_HEIGHT),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("text-decoration", Grammar::String),
+        ("text-transform", Grammar::String),
+        ("vertical-align", Grammar::VERTICAL_ALIGN),
+    ],
+};
+
+impl Validate for MjText {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |_| {});
+    }
+}
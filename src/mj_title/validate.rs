@@ -0,0 +1,8 @@
+use super::{MjTitle, NAME};
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for MjTitle {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[crate::mj_head::NAME], |_| {});
+    }
+}
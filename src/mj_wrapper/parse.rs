@@ -1,16 +1,16 @@
 #[cfg(test)]
 mod tests {
     use crate::mj_wrapper::MjWrapper;
-    use crate::prelude::parse::{Parsable, ParserOptions};
+    use crate::prelude::parse::{Parsable, ParserContext};
     use std::rc::Rc;
 
     #[test]
     fn parse_br_element() {
-        let opts = Rc::new(ParserOptions::default());
+        let ctx = Rc::new(ParserContext::default());
         let content = "<mj-wrapper><h1>hello</h1><br><h2>world</h2></mj-wrapper>";
         let mut tokenizer = xmlparser::Tokenizer::from(content);
         let _ = tokenizer.next().unwrap();
         let tag = xmlparser::StrSpan::from("<mj-wrapper");
-        MjWrapper::parse(tag, &mut tokenizer, opts).unwrap();
+        MjWrapper::parse(tag, &mut tokenizer, ctx).unwrap();
     }
 }
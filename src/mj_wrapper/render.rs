@@ -2,6 +2,7 @@ use super::{MjWrapper, NAME};
 use crate::helper::condition::{END_CONDITIONAL_TAG, START_CONDITIONAL_TAG};
 use crate::helper::size::Pixel;
 use crate::helper::tag::Tag;
+use crate::mj_body::MjBodyChild;
 use crate::mj_section::{SectionLikeRender, WithMjSectionBackground};
 use crate::prelude::hash::Map;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
@@ -39,36 +40,35 @@ impl<'e, 'h> SectionLikeRender<'h> for MjWrapperRender<'e, 'h> {
         &self.container_width
     }
 
-    fn render_wrapped_children(&self, opts: &Options) -> Result<String, Error> {
+    fn render_wrapped_children(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         let tr = Tag::tr();
         let siblings = self.get_siblings();
         let raw_siblings = self.get_raw_siblings();
         let current_width = self.current_width();
         let container_width = self.container_width.as_ref().map(|v| v.to_string());
-        let mut result = String::default();
-        for child in self.children().iter() {
+        for child in MjBodyChild::flatten_renderables(self.children()).iter() {
             let mut renderer = child.renderer(Rc::clone(&self.header));
             renderer.set_siblings(siblings);
             renderer.set_raw_siblings(raw_siblings);
             renderer.set_container_width(current_width.clone());
             if child.is_raw() {
-                result.push_str(&renderer.render(opts)?);
+                renderer.render_into(buf, opts)?;
             } else {
                 let td = renderer
                     .set_style("td-outlook", Tag::td())
                     .maybe_add_attribute("align", renderer.attribute("align"))
                     .maybe_add_attribute("width", container_width.as_ref().cloned())
                     .maybe_add_suffixed_class(renderer.attribute("css-class"), "outlook");
-                result.push_str(&tr.open());
-                result.push_str(&td.open());
-                result.push_str(END_CONDITIONAL_TAG);
-                result.push_str(&renderer.render(opts)?);
-                result.push_str(START_CONDITIONAL_TAG);
-                result.push_str(&td.close());
-                result.push_str(&tr.close());
+                buf.push_str(&tr.open());
+                buf.push_str(&td.open());
+                buf.push_str(END_CONDITIONAL_TAG);
+                renderer.render_into(buf, opts)?;
+                buf.push_str(START_CONDITIONAL_TAG);
+                buf.push_str(&td.close());
+                buf.push_str(&tr.close());
             }
         }
-        Ok(result)
+        Ok(())
     }
 }
 
@@ -78,7 +78,7 @@ impl<'e, 'h> Render<'h> for MjWrapperRender<'e, 'h> {
             "background-position" => Some("top center"),
             "background-repeat" => Some("repeat"),
             "background-size" => Some("auto"),
-            "direction" => Some("ltr"),
+            "direction" => Some(self.header().direction()),
             "padding" => Some("20px 0"),
             "text-align" => Some("center"),
             "text-padding" => Some("4px 4px 4px 0"),
@@ -103,10 +103,16 @@ impl<'e, 'h> Render<'h> for MjWrapperRender<'e, 'h> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
+        let mut buf = String::default();
+        self.render_into(&mut buf, opts)?;
+        Ok(buf)
+    }
+
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
         if self.is_full_width() {
-            self.render_full_width(opts)
+            self.render_full_width(buf, opts)
         } else {
-            self.render_simple(opts)
+            self.render_simple(buf, opts)
         }
     }
 }
@@ -0,0 +1,39 @@
+use super::{MjWrapper, NAME};
+use crate::prelude::validate::{Grammar, Rule, Validate, Validator};
+
+const RULE: Rule = Rule {
+    tag: NAME,
+    parents: &[crate::mj_body::NAME],
+    attributes: &[
+        ("background-color", Grammar::Color),
+        ("background-position", Grammar::String),
+        ("background-position-x", Grammar::String),
+        ("background-position-y", Grammar::String),
+        ("background-repeat", Grammar::Enum(&["repeat", "no-repeat"])),
+        ("background-size", Grammar::String),
+        ("background-url", Grammar::String),
+        ("border", Grammar::String),
+        ("border-bottom", Grammar::String),
+        ("border-left", Grammar::String),
+        ("border-radius", Grammar::String),
+        ("border-right", Grammar::String),
+        ("border-top", Grammar::String),
+        ("direction", Grammar::DIRECTION),
+        ("full-width", Grammar::Enum(&["full-width", "false"])),
+        ("padding", Grammar::SPACING),
+        ("padding-bottom", Grammar::SIZE),
+        ("padding-left", Grammar::SIZE),
+        ("padding-right", Grammar::SIZE),
+        ("padding-top", Grammar::SIZE),
+        ("text-align", Grammar::ALIGN),
+        ("text-padding", Grammar::SPACING),
+    ],
+};
+
+impl Validate for MjWrapper {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.component(&RULE, &self.attributes, |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
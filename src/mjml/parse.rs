@@ -1,19 +1,24 @@
 use super::Mjml;
 use crate::mj_body::{MjBody, NAME as MJ_BODY};
 use crate::mj_head::{MjHead, NAME as MJ_HEAD};
-use crate::prelude::parse::{is_element_start, next_token, Error, Parsable, Parser};
+use crate::prelude::parse::{
+    attribute_value, is_element_start, next_token, token_span, Error, Parsable, ParseOutput,
+    Parser, ParserContext, ParserOptions,
+};
+use std::rc::Rc;
+use std::sync::Arc;
 use xmlparser::{StrSpan, Tokenizer};
 
 #[derive(Debug)]
 struct MjmlParser {
-    opts: std::rc::Rc<crate::prelude::parse::ParserOptions>,
+    ctx: Rc<ParserContext>,
     element: Mjml,
 }
 
 impl MjmlParser {
-    fn new(opts: std::rc::Rc<crate::prelude::parse::ParserOptions>) -> Self {
+    fn new(ctx: Rc<ParserContext>) -> Self {
         Self {
-            opts,
+            ctx,
             element: Default::default(),
         }
     }
@@ -28,10 +33,10 @@ impl Parser for MjmlParser {
 
     fn parse_attribute<'a>(&mut self, name: StrSpan<'a>, value: StrSpan<'a>) -> Result<(), Error> {
         match name.as_str() {
-            "dir" => self.element.attributes.dir = Some(value.to_string()),
-            "lang" => self.element.attributes.lang = Some(value.to_string()),
-            "owa" => self.element.attributes.owa = Some(value.to_string()),
-            _ => return Err(Error::UnexpectedAttribute(name.start())),
+            "dir" => self.element.attributes.dir = Some(attribute_value(value)),
+            "lang" => self.element.attributes.lang = Some(attribute_value(value)),
+            "owa" => self.element.attributes.owa = Some(attribute_value(value)),
+            _ => return Err(Error::UnexpectedAttribute(name.into())),
         };
         Ok(())
     }
@@ -43,14 +48,14 @@ impl Parser for MjmlParser {
     ) -> Result<(), Error> {
         match tag.as_str() {
             MJ_BODY => {
-                let elt = MjBody::parse(tag, tokenizer, self.opts.clone())?;
+                let elt = MjBody::parse(tag, tokenizer, self.ctx.clone())?;
                 self.element.children.body = Some(elt);
             }
             MJ_HEAD => {
-                let elt = MjHead::parse(tag, tokenizer, self.opts.clone())?;
+                let elt = MjHead::parse(tag, tokenizer, self.ctx.clone())?;
                 self.element.children.head = Some(elt);
             }
-            _ => return Err(Error::UnexpectedElement(tag.start())),
+            _ => return Err(Error::UnexpectedElement(tag.into())),
         };
         Ok(())
     }
@@ -68,10 +73,11 @@ impl Mjml {
     /// use mrml::mjml::Mjml;
     /// use mrml::prelude::parse::ParserOptions;
     /// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
-    /// use std::rc::Rc;
+    /// use std::sync::Arc;
     ///
-    /// let options = Rc::new(ParserOptions {
+    /// let options = Arc::new(ParserOptions {
     ///     include_loader: Box::new(MemoryIncludeLoader::default()),
+    ///     ..Default::default()
     /// });
     /// match Mjml::parse_with_options("<mjml><mj-head /><mj-body /></mjml>", options) {
     ///     Ok(_) => println!("Success!"),
@@ -80,15 +86,121 @@ impl Mjml {
     /// ```
     pub fn parse_with_options<T: AsRef<str>>(
         value: T,
-        opts: std::rc::Rc<crate::prelude::parse::ParserOptions>,
+        opts: Arc<ParserOptions>,
     ) -> Result<Self, Error> {
-        let mut tokenizer = Tokenizer::from(value.as_ref());
-        let token = next_token(&mut tokenizer)?;
-        if is_element_start(&token).is_some() {
-            MjmlParser::new(opts).parse(&mut tokenizer)?.build()
-        } else {
-            Err(Error::InvalidFormat)
-        }
+        let ctx = Rc::new(ParserContext::new(opts));
+        Self::parse_with_context(value.as_ref(), ctx)
+    }
+
+    #[cfg(feature = "async")]
+    /// Function to parse a raw mjml template with an [asynchronous loader](crate::prelude::parse::loader::AsyncIncludeLoader).
+    ///
+    /// The included templates are loaded first, without blocking, then the template is parsed
+    /// like with [`parse_with_options`](Self::parse_with_options).
+    ///
+    /// ```rust
+    /// use mrml::mjml::Mjml;
+    /// use mrml::prelude::parse::AsyncParserOptions;
+    /// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
+    /// use std::sync::Arc;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let options = Arc::new(AsyncParserOptions {
+    ///     include_loader: Box::new(MemoryIncludeLoader::default()),
+    ///     ..Default::default()
+    /// });
+    /// match Mjml::parse_with_options_async("<mjml><mj-head /><mj-body /></mjml>", options).await {
+    ///     Ok(_) => println!("Success!"),
+    ///     Err(err) => eprintln!("Something went wrong: {err:?}"),
+    /// }
+    /// # })
+    /// ```
+    pub async fn parse_with_options_async<T: AsRef<str>>(
+        value: T,
+        opts: Arc<crate::prelude::parse::AsyncParserOptions>,
+    ) -> Result<Self, Error> {
+        let loader =
+            crate::prelude::parse::prefetch::PrefetchedIncludeLoader::load(value.as_ref(), opts)
+                .await;
+        Self::parse_with_options(value, Arc::new(loader.into_options()))
+    }
+
+    /// Function to parse a raw mjml template and get the errors that have been skipped
+    /// when the [`lenient`](crate::prelude::parse::ParserSettings::lenient) mode is enabled.
+    ///
+    /// ```rust
+    /// use mrml::mjml::Mjml;
+    /// use mrml::prelude::parse::{ParserOptions, ParserSettings};
+    /// use std::sync::Arc;
+    ///
+    /// let options = Arc::new(ParserOptions {
+    ///     settings: ParserSettings {
+    ///         lenient: true,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// });
+    /// let template = "<mjml><mj-head><mj-text>Hello</mj-text></mj-head><mj-body /></mjml>";
+    /// let output = Mjml::parse_with_diagnostics(template, options).unwrap();
+    /// assert!(output.element.body().is_some());
+    /// assert_eq!(output.diagnostics.len(), 1);
+    /// ```
+    pub fn parse_with_diagnostics<T: AsRef<str>>(
+        value: T,
+        opts: Arc<ParserOptions>,
+    ) -> Result<ParseOutput<Self>, Error> {
+        let source = value.as_ref();
+        let ctx = Rc::new(ParserContext::new(opts));
+        let element = Self::parse_with_context(source, ctx.clone())?;
+        ctx.resolve_diagnostics(0, source, None);
+        Ok(ParseOutput {
+            element,
+            diagnostics: ctx.take_diagnostics(),
+        })
+    }
+
+    #[cfg(feature = "print")]
+    /// Function to parse a raw mjml template while keeping its source, to print it back
+    /// after editing it without losing its formatting.
+    ///
+    /// ```rust
+    /// use mrml::mjml::Mjml;
+    /// use mrml::prelude::parse::ParserOptions;
+    /// use std::sync::Arc;
+    ///
+    /// let template = "<mjml>\n  <mj-body width='500px'>\n  </mj-body>\n</mjml>";
+    /// let mut output = Mjml::parse_lossless(template, Arc::new(ParserOptions::default())).unwrap();
+    /// output.element.attributes.lang = Some("fr".into());
+    /// assert_eq!(
+    ///     output.print().unwrap(),
+    ///     "<mjml lang=\"fr\">\n  <mj-body width='500px'>\n  </mj-body>\n</mjml>"
+    /// );
+    /// ```
+    pub fn parse_lossless<T: AsRef<str>>(
+        value: T,
+        opts: Arc<ParserOptions>,
+    ) -> Result<crate::prelude::print::lossless::LosslessOutput<Self>, Error> {
+        let element = Self::parse_with_options(value.as_ref(), opts)?;
+        let source_map = crate::prelude::print::lossless::SourceMap::new(value.as_ref())?;
+        Ok(crate::prelude::print::lossless::LosslessOutput {
+            element,
+            source_map,
+        })
+    }
+
+    fn parse_with_context(source: &str, ctx: Rc<ParserContext>) -> Result<Self, Error> {
+        let mut tokenizer = Tokenizer::from(source);
+        next_token(&mut tokenizer)
+            .and_then(|token| {
+                if is_element_start(&token).is_some() {
+                    MjmlParser::new(ctx.clone())
+                        .parse(&mut tokenizer, &ctx)?
+                        .build()
+                } else {
+                    Err(Error::InvalidFormat(token_span(&token)))
+                }
+            })
+            .map_err(|err| err.resolve(source, None))
     }
 
     /// Function to parse a raw mjml template using the default parsing [options](crate::prelude::parse::ParserOptions).
@@ -102,7 +214,7 @@ impl Mjml {
     /// }
     /// ```
     pub fn parse<T: AsRef<str>>(value: T) -> Result<Self, Error> {
-        let opts = std::rc::Rc::new(crate::prelude::parse::ParserOptions::default());
+        let opts = Arc::new(ParserOptions::default());
         Self::parse_with_options(value, opts)
     }
 }
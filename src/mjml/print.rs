@@ -28,4 +28,49 @@ mod tests {
         assert_eq!("<mjml><mj-body /></mjml>", item.dense_print());
         assert_eq!("<mjml>\n  <mj-body />\n</mjml>\n", item.pretty_print());
     }
+
+    #[test]
+    fn should_escape_attributes() {
+        let mut item = Mjml::default();
+        item.attributes.lang = Some(r#"a "b" & <c>"#.to_string());
+        assert_eq!(
+            "<mjml lang=\"a &quot;b&quot; &amp; &lt;c>\" />",
+            item.dense_print()
+        );
+    }
+
+    #[cfg(all(feature = "json", feature = "parse"))]
+    fn assert_round_trip(json: &str) {
+        let expected: serde_json::Value = serde_json::from_str(json).unwrap();
+        let item: Mjml = serde_json::from_str(json).unwrap();
+        for printed in [item.dense_print(), item.pretty_print()] {
+            let parsed = crate::parse(&printed).unwrap();
+            assert_eq!(
+                serde_json::to_value(&parsed).unwrap(),
+                expected,
+                "{printed}"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "json", feature = "parse"))]
+    #[test]
+    fn should_round_trip_json() {
+        assert_round_trip(
+            r#"{"type":"mjml","attributes":{"lang":"\"fr\" & <en>"},"children":[{"type":"mj-head","children":[{"type":"mj-attributes","children":[{"type":"mj-class","name":"a&b","attributes":{"color":"red"}}]},{"type":"mj-font","attributes":{"name":"Font \"Sans\"","href":"https://fonts/?family=a&subset=b"}},{"type":"mj-title","children":"Tom &amp; Jerry"}]},{"type":"mj-body","children":[{"type":"mj-section","attributes":{"css-class":"a&b \"c\""},"children":[{"type":"mj-column","children":[{"type":"mj-image","attributes":{"src":"https://img/?a=1&b=<2>","alt":"it's \"quoted\" &#8856;"}},{"type":"mj-button","attributes":{"href":"https://host/?a=1&amp=2","title":"a &amp; b"}}]}]}]}]}"#,
+        );
+    }
+
+    #[cfg(all(feature = "json", feature = "parse"))]
+    #[test]
+    fn should_print_parsable_text() {
+        let item: Mjml = serde_json::from_str(
+            r#"{"type":"mjml","children":[{"type":"mj-body","children":[{"type":"mj-text","children":["1 < 2 &amp; 3 &nbsp;& co"]}]}]}"#,
+        )
+        .unwrap();
+        let expected =
+            "<mjml><mj-body><mj-text>1 &lt; 2 &amp; 3 &nbsp;& co</mj-text></mj-body></mjml>";
+        assert_eq!(item.dense_print(), expected);
+        assert_eq!(crate::parse(expected).unwrap().dense_print(), expected);
+    }
 }
@@ -1,4 +1,7 @@
 use super::Mjml;
+use crate::helper::css_inline::inline_styles;
+use crate::helper::escape::escape_attribute;
+use crate::helper::minify::minify;
 use crate::mj_head::MjHead;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
@@ -9,38 +12,51 @@ pub struct MjmlRender<'e, 'h> {
     element: &'e Mjml,
 }
 
+impl<'e, 'h> MjmlRender<'e, 'h> {
+    /// Writes the document, the body being rendered first as the head depends on it.
+    fn write<W: std::fmt::Write>(&self, writer: &mut W, opts: &Options) -> Result<(), Error> {
+        let mut body_content = String::default();
+        if let Some(body) = self.element.body() {
+            body.renderer(Rc::clone(&self.header))
+                .render_into(&mut body_content, opts)?;
+        } else {
+            body_content.push_str("<body></body>");
+        }
+        let head = match self.element.head() {
+            Some(head) => head.renderer(Rc::clone(&self.header)).render(opts)?,
+            None => MjHead::default()
+                .renderer(Rc::clone(&self.header))
+                .render(opts)?,
+        };
+        let mut lang = String::default();
+        if let Some(ref value) = self.element.attributes.lang {
+            lang.push_str(&format!("lang=\"{}\" ", escape_attribute(value)));
+        }
+        if let Some(ref value) = self.element.attributes.dir {
+            lang.push_str(&format!("dir=\"{}\" ", escape_attribute(value)));
+        }
+        write!(
+            writer,
+            "<!doctype html><html {lang}xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:v=\"urn:schemas-microsoft-com:vml\" xmlns:o=\"urn:schemas-microsoft-com:office:office\">{head}{body_content}</html>"
+        )
+        .map_err(Error::Format)
+    }
+}
+
 impl<'e, 'h> Render<'h> for MjmlRender<'e, 'h> {
     fn header(&self) -> Ref<Header<'h>> {
         self.header.borrow()
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error> {
-        let body_content = if let Some(body) = self.element.body() {
-            body.renderer(Rc::clone(&self.header)).render(opts)?
-        } else {
-            String::from("<body></body>")
-        };
-        let mut buf = String::from("<!doctype html>");
-        buf.push_str("<html ");
-        if let Some(ref lang) = self.element.attributes.lang {
-            buf.push_str("lang=\"");
-            buf.push_str(lang);
-            buf.push_str("\" ");
-        }
-        buf.push_str("xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:v=\"urn:schemas-microsoft-com:vml\" xmlns:o=\"urn:schemas-microsoft-com:office:office\">");
-        if let Some(head) = self.element.head() {
-            buf.push_str(&head.renderer(Rc::clone(&self.header)).render(opts)?);
-        } else {
-            buf.push_str(
-                &MjHead::default()
-                    .renderer(Rc::clone(&self.header))
-                    .render(opts)?,
-            );
-        }
-        buf.push_str(&body_content);
-        buf.push_str("</html>");
+        let mut buf = String::default();
+        self.render_into(&mut buf, opts)?;
         Ok(buf)
     }
+
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
+        self.write(buf, opts)
+    }
 }
 
 impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for Mjml {
@@ -52,12 +68,97 @@ impl<'r, 'e: 'r, 'h: 'r> Renderable<'r, 'e, 'h> for Mjml {
     }
 }
 
+/// Adapter writing into an [`std::io::Write`], keeping the error it returns.
+struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write> std::fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, value: &str) -> std::fmt::Result {
+        self.inner.write_all(value.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            std::fmt::Error
+        })
+    }
+}
+
 impl Mjml {
+    /// Renders the template. The state of the rendering is local to each call, so a template
+    /// can be rendered from several threads at the same time.
     pub fn render(&self, opts: &Options) -> Result<String, Error> {
+        let mut buf = String::default();
+        self.render_to(&mut buf, opts)?;
+        Ok(buf)
+    }
+
+    /// Renders the template into a [`std::fmt::Write`], like a `String` reused between calls.
+    ///
+    /// The body is rendered in a single buffer before being written, as the head depends on it.
+    /// When some styles have to be inlined or the output minified, the whole document is buffered.
+    ///
+    /// ```rust
+    /// let root = mrml::parse("<mjml><mj-body><mj-text>Hello</mj-text></mj-body></mjml>").unwrap();
+    /// let opts = mrml::prelude::render::Options::default();
+    /// let mut output = String::with_capacity(4096);
+    /// root.render_to(&mut output, &opts).unwrap();
+    /// assert!(output.contains("Hello"));
+    /// ```
+    pub fn render_to<W: std::fmt::Write>(
+        &self,
+        writer: &mut W,
+        opts: &Options,
+    ) -> Result<(), Error> {
         let mut header = Header::new(&self.children.head);
         header.maybe_set_lang(self.attributes.lang.clone());
-        let header = Rc::new(RefCell::new(header));
-        self.renderer(header).render(opts)
+        header.maybe_set_dir(self.attributes.dir.clone());
+        header.maybe_set_owa(self.attributes.owa.clone());
+        header.set_id_strategy(&opts.id_strategy);
+        let renderer = MjmlRender {
+            element: self,
+            header: Rc::new(RefCell::new(header)),
+        };
+        let styles = self
+            .head()
+            .map(|head| head.build_inline_styles())
+            .unwrap_or_default();
+        if styles.is_empty() && !opts.minify {
+            return renderer.write(writer, opts);
+        }
+        let mut buf = String::default();
+        renderer.write(&mut buf, opts)?;
+        if !styles.is_empty() {
+            buf = inline_styles(&buf, &styles);
+        }
+        if opts.minify {
+            buf = minify(&buf);
+        }
+        writer.write_str(&buf).map_err(Error::Format)
+    }
+
+    /// Renders the template into a [`std::io::Write`], like a file or the body of a mime part.
+    ///
+    /// ```rust
+    /// let root = mrml::parse("<mjml><mj-body><mj-text>Hello</mj-text></mj-body></mjml>").unwrap();
+    /// let opts = mrml::prelude::render::Options::default();
+    /// let mut output: Vec<u8> = Vec::new();
+    /// root.render_to_writer(&mut output, &opts).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains("Hello"));
+    /// ```
+    pub fn render_to_writer<W: std::io::Write>(
+        &self,
+        writer: W,
+        opts: &Options,
+    ) -> Result<(), Error> {
+        let mut writer = IoWriter {
+            inner: writer,
+            error: None,
+        };
+        self.render_to(&mut writer, opts)
+            .map_err(|err| match (err, writer.error.take()) {
+                (Error::Format(_), Some(cause)) => Error::Io(cause),
+                (err, _) => err,
+            })
     }
 
     pub fn get_title(&self) -> Option<String> {
@@ -87,6 +188,135 @@ mod tests {
         html_compare::assert_similar(expected, root.render(&opts).unwrap().as_str());
     }
 
+    #[test]
+    fn should_render_to_writers() {
+        let opts = Options::default();
+        let template = include_str!("../../resources/template/air-astana.mjml");
+        let root = Mjml::parse(template).unwrap();
+        let expected = root.render(&opts).unwrap();
+        let mut output = String::from("prefix:");
+        root.render_to(&mut output, &opts).unwrap();
+        assert_eq!(output, format!("prefix:{expected}"));
+        let mut output: Vec<u8> = Vec::new();
+        root.render_to_writer(&mut output, &opts).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[test]
+    fn should_return_writer_errors() {
+        struct Failing;
+
+        impl std::io::Write for Failing {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "closed",
+                ))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let root = Mjml::parse("<mjml><mj-body /></mjml>").unwrap();
+        match root.render_to_writer(Failing, &Options::default()) {
+            Err(crate::prelude::render::Error::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_minify() {
+        let template = include_str!("../../resources/template/air-astana.mjml");
+        let root = Mjml::parse(template).unwrap();
+        let expected = root.render(&Options::default()).unwrap();
+        let opts = Options {
+            minify: true,
+            ..Default::default()
+        };
+        let result = root.render(&opts).unwrap();
+        assert!(result.len() < expected.len());
+        assert!(!result.contains("\n    "));
+        // the conditional comments are kept untouched
+        let start = expected.find("<!--[if mso | IE]>").unwrap();
+        let end = start + expected[start..].find("<![endif]-->").unwrap();
+        assert!(result.contains(&expected[start..end]));
+        assert_eq!(crate::helper::minify::minify(&result), result);
+    }
+
+    #[test]
+    fn should_minify_quoted_font_family() {
+        let template = r#"<mjml><mj-body><mj-section><mj-column><mj-text font-family='"Open Sans", Arial'>Hello</mj-text></mj-column></mj-section></mj-body></mjml>"#;
+        let root = Mjml::parse(template).unwrap();
+        let opts = Options {
+            minify: true,
+            ..Default::default()
+        };
+        let result = root.render(&opts).unwrap();
+        assert!(result.contains("font-family:&quot;Open Sans&quot;, Arial;font-size:13px;"));
+    }
+
+    #[test]
+    fn should_render_right_to_left() {
+        let template = r#"<mjml lang="ar" dir="rtl"><mj-body><mj-section><mj-column><mj-text>مرحبا</mj-text></mj-column><mj-column><mj-text align="left">left</mj-text></mj-column></mj-section></mj-body></mjml>"#;
+        let root = Mjml::parse(template).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(result.contains(r#"<html lang="ar" dir="rtl" "#));
+        assert!(result.contains(r#"lang="ar" dir="rtl">"#));
+        assert!(result.contains("direction:rtl;"));
+        assert!(!result.contains("direction:ltr;"));
+        assert!(result.contains(
+            r#"<table border="0" cellpadding="0" cellspacing="0" role="presentation" dir="rtl">"#
+        ));
+        assert!(result.contains("font-size:0px;text-align:right;direction:rtl;"));
+        assert!(result.contains(r#"<td align="right""#));
+        assert!(result.contains(r#"<td align="left""#));
+    }
+
+    #[test]
+    fn should_rewrite_links() {
+        use crate::prelude::render::LinkContext;
+        use std::sync::Arc;
+
+        let template = r#"<mjml><mj-body><mj-section><mj-column>
+  <mj-button href="https://a.com" css-class="cta">Go</mj-button>
+  <mj-image href="https://b.com" src="https://b.com/b.png" />
+  <mj-navbar base-url="https://c.com"><mj-navbar-link href="/c">C</mj-navbar-link></mj-navbar>
+  <mj-social><mj-social-element href="https://d.com">D</mj-social-element></mj-social>
+  <mj-carousel><mj-carousel-image href="https://e.com" src="https://e.com/e.png" /></mj-carousel>
+  <mj-raw><a href="https://f.com">F</a></mj-raw>
+</mj-column></mj-section></mj-body></mjml>"#;
+        let root = Mjml::parse(template).unwrap();
+        let opts = Options {
+            link_rewriter: Some(Arc::new(|url: &str, ctx: &LinkContext| {
+                format!(
+                    "https://track.com/?tag={}&class={}&url={url}",
+                    ctx.tag,
+                    ctx.css_class.unwrap_or_default()
+                )
+            })),
+            ..Default::default()
+        };
+        let result = root.render(&opts).unwrap();
+        assert!(result.contains(
+            r#"href="https://track.com/?tag=mj-button&amp;class=cta&amp;url=https://a.com""#
+        ));
+        for (tag, url) in [
+            ("mj-image", "https://b.com"),
+            ("mj-navbar-link", "https://c.com/c"),
+            ("mj-social-element", "https://d.com"),
+            ("mj-carousel-image", "https://e.com"),
+        ] {
+            let expected =
+                format!(r#"href="https://track.com/?tag={tag}&amp;class=&amp;url={url}""#);
+            assert!(result.contains(&expected), "missing {}", expected);
+        }
+        assert!(result.contains(r#"<a href="https://f.com">F</a>"#));
+    }
+
     #[test]
     fn template_amario() {
         let opts = Options::default();
@@ -118,4 +348,45 @@ mod tests {
 
         assert_eq!(output_1, output_2);
     }
+
+    #[test]
+    fn render_from_several_threads() {
+        let template = include_str!("../../resources/template/air-astana.mjml");
+        let expected = include_str!("../../resources/template/air-astana.html");
+        let root = std::sync::Arc::new(Mjml::parse(template).unwrap());
+        let opts = std::sync::Arc::new(Options::default());
+        let handles = (0..4)
+            .map(|_| {
+                let root = root.clone();
+                let opts = opts.clone();
+                std::thread::spawn(move || root.render(&opts).unwrap())
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            html_compare::assert_similar(expected, handle.join().unwrap().as_str());
+        }
+    }
+
+    #[test]
+    fn inline_style() {
+        let source = r#"<mjml>
+  <mj-head>
+    <mj-style inline="inline">.red-text div { color: red !important; }</mj-style>
+    <mj-style>.blue-text div { color: blue; }</mj-style>
+  </mj-head>
+  <mj-body>
+    <mj-section>
+      <mj-column>
+        <mj-text css-class="red-text">Hello</mj-text>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"#;
+        let root = Mjml::parse(source).unwrap();
+        let result = root.render(&Options::default()).unwrap();
+        assert!(!result.contains(".red-text div"));
+        assert!(result.contains(".blue-text div { color: blue; }"));
+        assert!(result.contains("color:red;"));
+        assert!(!result.contains("color:#000000;"));
+    }
 }
@@ -0,0 +1,172 @@
+use super::{Mjml, NAME};
+use crate::prelude::validate::{Grammar, Validate, Validator};
+
+impl Validate for Mjml {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(NAME, &[], |validator| {
+            if let Some(ref dir) = self.attributes.dir {
+                validator.attribute("dir", dir, Grammar::Enum(&["ltr", "rtl", "auto"]));
+            }
+            if let Some(ref head) = self.children.head {
+                head.validate_with(validator);
+            }
+            if let Some(ref body) = self.children.body {
+                body.validate_with(validator);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mjml::Mjml;
+    use crate::prelude::validate::{Grammar, PathItem, Validate, WarningKind};
+
+    #[test]
+    fn should_accept_valid_template() {
+        let template = include_str!("../../resources/template/air-astana.mjml");
+        let root = Mjml::parse(template).unwrap();
+        assert!(root.validate().is_empty());
+    }
+
+    #[test]
+    fn should_warn_about_invalid_parent() {
+        let root = Mjml::parse(
+            r#"<mjml><mj-body><mj-section><mj-text>Hello</mj-text></mj-section></mj-body></mjml>"#,
+        )
+        .unwrap();
+        let warnings = root.validate();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].path,
+            vec![
+                PathItem {
+                    tag: "mjml".into(),
+                    index: None,
+                },
+                PathItem {
+                    tag: "mj-body".into(),
+                    index: None,
+                },
+                PathItem {
+                    tag: "mj-section".into(),
+                    index: Some(0),
+                },
+                PathItem {
+                    tag: "mj-text".into(),
+                    index: Some(0),
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].kind,
+            WarningKind::InvalidParent {
+                tag: "mj-text".into(),
+                parent: "mj-section".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn should_warn_about_attributes() {
+        let root = Mjml::parse(
+            r##"<mjml>
+  <mj-head>
+    <mj-breakpoint width="320" />
+  </mj-head>
+  <mj-body>
+    <mj-section>
+      <mj-column padding="10px 20px" css-class="foo" mj-class="bar">
+        <mj-button align="middle" color="#zzz" foo="bar">Hello</mj-button>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"##,
+        )
+        .unwrap();
+        let warnings = root
+            .validate()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                "mjml > mj-head > mj-breakpoint[0]: attribute \"width\" has invalid value \"320\", expected unit(px)",
+                "mjml > mj-body > mj-section[0] > mj-column[0] > mj-button[0]: attribute \"align\" has invalid value \"middle\", expected enum(left,center,right)",
+                "mjml > mj-body > mj-section[0] > mj-column[0] > mj-button[0]: attribute \"color\" has invalid value \"#zzz\", expected color",
+                "mjml > mj-body > mj-section[0] > mj-column[0] > mj-button[0]: attribute \"foo\" is illegal for mj-button",
+            ]
+        );
+        let root = Mjml::parse(r#"<mjml dir="up"><mj-body /></mjml>"#).unwrap();
+        assert_eq!(
+            root.validate()[0].kind,
+            WarningKind::InvalidAttributeValue {
+                name: "dir".into(),
+                value: "up".into(),
+                expected: Grammar::Enum(&["ltr", "rtl", "auto"]),
+            }
+        );
+    }
+
+    #[test]
+    fn should_accept_dark_color_variants() {
+        let root = Mjml::parse(
+            r##"<mjml>
+  <mj-body dark-background-color="#000">
+    <mj-section>
+      <mj-column>
+        <mj-button color="#000" dark-color="#fff" dark-background-color="#zzz" dark-align="left">Hello</mj-button>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"##,
+        )
+        .unwrap();
+        let warnings = root
+            .validate()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                "mjml > mj-body > mj-section[0] > mj-column[0] > mj-button[0]: attribute \"dark-align\" is illegal for mj-button",
+                "mjml > mj-body > mj-section[0] > mj-column[0] > mj-button[0]: attribute \"dark-background-color\" has invalid value \"#zzz\", expected color",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_validate_included_elements_in_place() {
+        use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
+        use crate::prelude::parse::ParserOptions;
+        use std::sync::Arc;
+
+        let loader = MemoryIncludeLoader::from(vec![
+            (
+                "column.mjml",
+                "<mj-column><mj-text>Hi</mj-text></mj-column>",
+            ),
+            ("text.mjml", "<mj-text>Hi</mj-text>"),
+        ]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(loader),
+            ..Default::default()
+        });
+        let root = Mjml::parse_with_options(
+            r#"<mjml><mj-body><mj-section><mj-include path="column.mjml" /><mj-include path="text.mjml" /></mj-section></mj-body></mjml>"#,
+            opts,
+        )
+        .unwrap();
+        let warnings = root
+            .validate()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec!["mjml > mj-body > mj-section[0] > mj-include[1] > mj-text[0]: mj-text cannot be used inside mj-section"]
+        );
+    }
+}
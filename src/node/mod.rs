@@ -3,13 +3,15 @@ use crate::prelude::hash::Map;
 #[cfg(feature = "json")]
 mod json;
 #[cfg(feature = "parse")]
-mod parse;
+pub(crate) mod parse;
 #[cfg(feature = "print")]
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "json", derive(mrml_json_macros::MrmlJsonComponent))]
 #[cfg_attr(feature = "json", mrml_json(tag_field = "tag"))]
 pub struct Node<T> {
@@ -6,18 +6,39 @@ use crate::text::Text;
 use crate::{parse_attribute, parse_comment, parse_text};
 use xmlparser::{StrSpan, Tokenizer};
 
+/// Elements like `<br>` or `<meta>` that have no closing element.
+pub(crate) fn is_void_element(tag: &str) -> bool {
+    matches!(
+        tag,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
 #[derive(Debug)]
 struct NodeParser<T> {
-    opts: std::rc::Rc<crate::prelude::parse::ParserOptions>,
+    ctx: std::rc::Rc<crate::prelude::parse::ParserContext>,
     tag: String,
     attributes: Map<String, String>,
     children: Vec<T>,
 }
 
 impl<T> NodeParser<T> {
-    pub fn new(tag: String, opts: std::rc::Rc<crate::prelude::parse::ParserOptions>) -> Self {
+    pub fn new(tag: String, ctx: std::rc::Rc<crate::prelude::parse::ParserContext>) -> Self {
         Self {
-            opts,
+            ctx,
             tag,
             attributes: Default::default(),
             children: Vec::new(),
@@ -42,23 +63,7 @@ where
     }
 
     fn should_ignore_children(&self) -> bool {
-        matches!(
-            self.tag.as_str(),
-            "area"
-                | "base"
-                | "br"
-                | "col"
-                | "embed"
-                | "hr"
-                | "img"
-                | "input"
-                | "link"
-                | "meta"
-                | "param"
-                | "source"
-                | "track"
-                | "wbr"
-        )
+        is_void_element(self.tag.as_str())
     }
 
     parse_attribute!();
@@ -69,7 +74,7 @@ where
         tokenizer: &mut xmlparser::Tokenizer<'a>,
     ) -> Result<(), Error> {
         self.children
-            .push(T::parse(tag, tokenizer, self.opts.clone())?);
+            .push(T::parse(tag, tokenizer, self.ctx.clone())?);
         Ok(())
     }
 
@@ -86,10 +91,10 @@ where
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: std::rc::Rc<crate::prelude::parse::ParserOptions>,
+        ctx: std::rc::Rc<crate::prelude::parse::ParserContext>,
     ) -> Result<Self, Error> {
-        NodeParser::<T>::new(tag.to_string(), opts)
-            .parse(tokenizer)?
+        NodeParser::<T>::new(tag.to_string(), ctx.clone())
+            .parse(tokenizer, &ctx)?
             .build()
     }
 }
@@ -1,4 +1,5 @@
 use super::Node;
+use crate::helper::escape::escape_attribute;
 use crate::prelude::render::{Error, Header, Options, Render, Renderable};
 use std::cell::{Ref, RefCell};
 use std::rc::Rc;
@@ -27,7 +28,7 @@ where
             buf.push(' ');
             buf.push_str(key);
             buf.push_str("=\"");
-            buf.push_str(value);
+            buf.push_str(&escape_attribute(value));
             buf.push('"');
         }
         if self.element.children.is_empty() {
@@ -0,0 +1,10 @@
+use super::Node;
+use crate::prelude::validate::{Validate, Validator};
+
+impl<T: Validate> Validate for Node<T> {
+    fn validate_with(&self, validator: &mut Validator) {
+        validator.element(&self.tag, &[], |validator| {
+            validator.children(&self.children);
+        });
+    }
+}
@@ -4,5 +4,7 @@ pub mod parse;
 pub mod print;
 #[cfg(feature = "render")]
 pub mod render;
+#[cfg(feature = "validate")]
+pub mod validate;
 
 pub mod hash;
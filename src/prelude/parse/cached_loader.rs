@@ -0,0 +1,401 @@
+//! Module containing a loader that keeps in memory the templates loaded by another loader.
+
+use super::loader::{IncludeLoaderError, ParsedInclude};
+use crate::prelude::parse::loader::IncludeLoader;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_CAPACITY: usize = 128;
+
+#[derive(Debug)]
+struct CacheEntry {
+    content: String,
+    /// Element parsed out of the content, once it has been parsed.
+    parsed: Option<ParsedInclude>,
+    version: Option<String>,
+    loaded_at: Instant,
+    /// Value of the access counter of the cache the last time the entry was used.
+    last_used: u64,
+}
+
+#[derive(Debug)]
+/// This struct is an [`IncludeLoader`](crate::prelude::parse::loader::IncludeLoader) that
+/// wraps another loader and keeps the templates it loads in memory, so that using the same
+/// partial in several templates doesn't load it again.
+///
+/// - the number of cached templates is bounded by the capacity, the least recently used
+///   template being dropped first,
+/// - a template can expire after a given duration,
+/// - an expired template can be revalidated using the
+///   [`version`](crate::prelude::parse::loader::IncludeLoader::version) of the inner loader,
+///   like the modification time of a file or the `ETag` of a remote template, and be kept
+///   if it didn't change,
+/// - a template can be explicitly dropped from the cache with [`invalidate`](Self::invalidate).
+///
+/// The element parsed out of a template is cached along with its content and follows the
+/// same expiration, so that the partials used by many templates are parsed only once.
+/// A template including other templates is parsed every time, to check the include limits.
+///
+/// # Example
+/// ```rust
+/// use mrml::prelude::parse::cached_loader::CachedIncludeLoader;
+/// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
+/// use mrml::prelude::parse::ParserOptions;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let inner = MemoryIncludeLoader::from(vec![("footer.mjml", "<mj-text>Footer</mj-text>")]);
+/// let resolver = CachedIncludeLoader::new(inner)
+///     .with_capacity(64)
+///     .with_ttl(Duration::from_secs(60))
+///     .with_revalidation(true);
+/// let opts = Arc::new(ParserOptions {
+///     include_loader: Box::new(resolver),
+///     ..Default::default()
+/// });
+/// let template = r#"<mjml>
+///   <mj-body>
+///     <mj-include path="footer.mjml" />
+///   </mj-body>
+/// </mjml>"#;
+/// for _ in 0..10 {
+///     // the footer is only loaded once
+///     match mrml::parse_with_options(template, opts.clone()) {
+///         Ok(_) => println!("Success!"),
+///         Err(err) => eprintln!("Couldn't parse template: {err:?}"),
+///     }
+/// }
+/// ```
+pub struct CachedIncludeLoader<L> {
+    inner: L,
+    capacity: usize,
+    ttl: Option<Duration>,
+    revalidate: bool,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    counter: AtomicU64,
+}
+
+impl<L: IncludeLoader> CachedIncludeLoader<L> {
+    /// Creates a cache of [`DEFAULT_CAPACITY`] templates that never expire.
+    pub fn new(inner: L) -> Self {
+        Self {
+            inner,
+            capacity: DEFAULT_CAPACITY,
+            ttl: None,
+            revalidate: false,
+            entries: Mutex::default(),
+            counter: AtomicU64::default(),
+        }
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the duration after which a cached template expires.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// When enabled, an expired template is kept if its version didn't change.
+    /// With a TTL of zero, the version is checked every time the template is used.
+    pub fn with_revalidation(mut self, revalidate: bool) -> Self {
+        self.revalidate = revalidate;
+        self
+    }
+
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// Number of templates currently cached.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Drops the template from the cache, it will be loaded again the next time it's used.
+    pub fn invalidate(&self, path: &str) {
+        self.entries().remove(path);
+    }
+
+    pub fn invalidate_all(&self) {
+        self.entries().clear();
+    }
+
+    /// The cache only contains copies of the templates, it stays usable
+    /// even if a thread panicked while holding the lock.
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn next_access(&self) -> u64 {
+        self.counter.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        self.ttl
+            .map(|ttl| entry.loaded_at.elapsed() >= ttl)
+            .unwrap_or(false)
+    }
+
+    /// Returns the cached content when still valid.
+    fn get(&self, path: &str) -> Option<String> {
+        let version = {
+            let mut entries = self.entries();
+            let entry = entries.get_mut(path)?;
+            if !self.is_expired(entry) {
+                entry.last_used = self.next_access();
+                return Some(entry.content.clone());
+            }
+            match entry.version.clone() {
+                Some(version) if self.revalidate => version,
+                _ => {
+                    entries.remove(path);
+                    return None;
+                }
+            }
+        };
+        // the lock is released while checking the version, which can take some time
+        let unchanged = self.inner.version(path).as_ref() == Some(&version);
+        let mut entries = self.entries();
+        match entries.get_mut(path) {
+            Some(entry) if unchanged => {
+                entry.loaded_at = Instant::now();
+                entry.last_used = self.next_access();
+                Some(entry.content.clone())
+            }
+            _ => {
+                entries.remove(path);
+                None
+            }
+        }
+    }
+
+    fn insert(&self, path: &str, content: String, version: Option<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries();
+        if !entries.contains_key(path) && entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            path.to_string(),
+            CacheEntry {
+                content,
+                parsed: None,
+                version,
+                loaded_at: Instant::now(),
+                last_used: self.next_access(),
+            },
+        );
+    }
+}
+
+impl<L: IncludeLoader> IncludeLoader for CachedIncludeLoader<L> {
+    fn resolve_path(&self, path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+        self.inner.resolve_path(path, parent)
+    }
+
+    fn version(&self, path: &str) -> Option<String> {
+        self.inner.version(path)
+    }
+
+    fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        if let Some(content) = self.get(path) {
+            return Ok(content);
+        }
+        // the version is read before loading so that a change happening in between
+        // gets detected on the next revalidation
+        let version = if self.revalidate {
+            self.inner.version(path)
+        } else {
+            None
+        };
+        let content = self.inner.resolve(path)?;
+        self.insert(path, content.clone(), version);
+        Ok(content)
+    }
+
+    /// The parsed element is only returned for the content it comes from, which is the
+    /// one just returned by [`resolve`](Self::resolve) unless it got reloaded in between.
+    fn parsed(&self, path: &str, content: &str) -> Option<ParsedInclude> {
+        self.entries()
+            .get(path)
+            .filter(|entry| entry.content == content)
+            .and_then(|entry| entry.parsed.clone())
+    }
+
+    fn store_parsed(&self, path: &str, content: &str, parsed: ParsedInclude) {
+        if let Some(entry) = self
+            .entries()
+            .get_mut(path)
+            .filter(|entry| entry.content == content)
+        {
+            entry.parsed = Some(parsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedIncludeLoader;
+    use crate::prelude::parse::loader::{IncludeLoader, IncludeLoaderError, ParsedInclude};
+    use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
+    use crate::prelude::parse::ParserOptions;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[derive(Debug, Default)]
+    struct CountingIncludeLoader {
+        version: Mutex<Option<String>>,
+        loads: Mutex<HashMap<String, usize>>,
+        checks: AtomicUsize,
+    }
+
+    impl CountingIncludeLoader {
+        fn loads(&self, path: &str) -> usize {
+            self.loads
+                .lock()
+                .unwrap()
+                .get(path)
+                .copied()
+                .unwrap_or_default()
+        }
+    }
+
+    impl IncludeLoader for CountingIncludeLoader {
+        fn version(&self, _path: &str) -> Option<String> {
+            self.checks.fetch_add(1, Ordering::SeqCst);
+            self.version.lock().unwrap().clone()
+        }
+
+        fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+            if path == "missing.mjml" {
+                return Err(IncludeLoaderError::not_found(path));
+            }
+            let mut loads = self.loads.lock().unwrap();
+            let count = loads.entry(path.to_string()).or_default();
+            *count += 1;
+            Ok(format!("{path}#{count}"))
+        }
+    }
+
+    #[test]
+    fn should_load_once() {
+        let loader = CachedIncludeLoader::new(CountingIncludeLoader::default());
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#1");
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#1");
+        assert_eq!(loader.inner().loads("a.mjml"), 1);
+        assert!(loader.resolve("missing.mjml").is_err());
+        assert_eq!(loader.len(), 1);
+    }
+
+    #[test]
+    fn should_drop_least_recently_used() {
+        let loader = CachedIncludeLoader::new(CountingIncludeLoader::default()).with_capacity(2);
+        loader.resolve("a.mjml").unwrap();
+        loader.resolve("b.mjml").unwrap();
+        loader.resolve("a.mjml").unwrap();
+        loader.resolve("c.mjml").unwrap();
+        assert_eq!(loader.len(), 2);
+        loader.resolve("a.mjml").unwrap();
+        assert_eq!(loader.inner().loads("a.mjml"), 1);
+        assert_eq!(loader.resolve("b.mjml").unwrap(), "b.mjml#2");
+    }
+
+    #[test]
+    fn should_disable_cache_without_capacity() {
+        let loader = CachedIncludeLoader::new(CountingIncludeLoader::default()).with_capacity(0);
+        loader.resolve("a.mjml").unwrap();
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#2");
+        assert!(loader.is_empty());
+    }
+
+    #[test]
+    fn should_expire() {
+        let loader = CachedIncludeLoader::new(CountingIncludeLoader::default())
+            .with_ttl(Duration::from_secs(3600));
+        loader.resolve("a.mjml").unwrap();
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#1");
+        let loader =
+            CachedIncludeLoader::new(CountingIncludeLoader::default()).with_ttl(Duration::ZERO);
+        loader.resolve("a.mjml").unwrap();
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#2");
+        // without revalidation, the version is never checked
+        assert_eq!(loader.inner().checks.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn should_revalidate() {
+        let inner = CountingIncludeLoader::default();
+        *inner.version.lock().unwrap() = Some("v1".into());
+        let loader = CachedIncludeLoader::new(inner)
+            .with_ttl(Duration::ZERO)
+            .with_revalidation(true);
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#1");
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#1");
+        *loader.inner().version.lock().unwrap() = Some("v2".into());
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#2");
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#2");
+        // no version means no way to revalidate
+        *loader.inner().version.lock().unwrap() = None;
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#3");
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#4");
+    }
+
+    #[test]
+    fn should_invalidate() {
+        let loader = CachedIncludeLoader::new(CountingIncludeLoader::default());
+        loader.resolve("a.mjml").unwrap();
+        loader.resolve("b.mjml").unwrap();
+        loader.invalidate("a.mjml");
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "a.mjml#2");
+        assert_eq!(loader.resolve("b.mjml").unwrap(), "b.mjml#1");
+        loader.invalidate_all();
+        assert!(loader.is_empty());
+        assert_eq!(loader.resolve("b.mjml").unwrap(), "b.mjml#2");
+    }
+
+    #[test]
+    fn should_keep_parsed_partials() {
+        let footer = "<mj-text>Footer</mj-text>";
+        let wrapper = r#"<mj-wrapper><mj-include path="footer.mjml" /></mj-wrapper>"#;
+        let inner =
+            MemoryIncludeLoader::from(vec![("footer.mjml", footer), ("wrapper.mjml", wrapper)]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(CachedIncludeLoader::new(inner)),
+            ..Default::default()
+        });
+        let template = r#"<mjml><mj-body><mj-include path="footer.mjml" /><mj-include path="wrapper.mjml" /></mj-body></mjml>"#;
+        let first = crate::mjml::Mjml::parse_with_options(template, opts.clone()).unwrap();
+        let loader = &opts.include_loader;
+        assert!(matches!(
+            loader.parsed("footer.mjml", footer),
+            Some(ParsedInclude::Body(_))
+        ));
+        assert!(loader
+            .parsed("footer.mjml", "<mj-text>Other</mj-text>")
+            .is_none());
+        // including another template, it's parsed every time
+        assert!(loader.parsed("wrapper.mjml", wrapper).is_none());
+        let second = crate::mjml::Mjml::parse_with_options(template, opts.clone()).unwrap();
+        assert_eq!(format!("{first:?}"), format!("{second:?}"));
+    }
+}
@@ -4,76 +4,388 @@ use super::loader::IncludeLoaderError;
 use crate::prelude::parse::loader::IncludeLoader;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::io::ErrorKind;
+use std::io::{ErrorKind, Read};
+use std::time::Duration;
 
-pub trait HttpFetcher: Default + Debug {
-    fn fetch(
-        &self,
-        url: &str,
-        headers: &HashMap<String, String>,
-    ) -> Result<String, IncludeLoaderError>;
+/// Maximum duration of a request, used by default.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum size of a template, in bytes, used by default.
+pub const DEFAULT_MAX_SIZE: usize = 1024 * 1024;
+/// Maximum number of redirections followed when loading a template, used by default.
+pub const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+/// Request sent by the [`HttpIncludeLoader`] to its fetcher.
+#[derive(Debug)]
+pub struct HttpRequest<'a> {
+    pub url: &'a str,
+    pub headers: &'a HashMap<String, String>,
+    /// Maximum duration of the request, reading the body included.
+    pub timeout: Option<Duration>,
+    /// Maximum size of the body, in bytes.
+    pub max_size: Option<usize>,
 }
 
-#[cfg(feature = "http-loader-reqwest")]
+/// Response received by a fetcher, the redirections not being followed.
 #[derive(Debug, Default)]
-pub struct ReqwestFetcher(reqwest::blocking::Client);
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub location: Option<String>,
+    /// Content of the response, only read when the request is successful.
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    fn is_redirection(&self) -> bool {
+        matches!(self.status, 301 | 302 | 303 | 307 | 308)
+    }
+
+    fn is_retryable(&self) -> bool {
+        self.status == 429 || self.status >= 500
+    }
+}
+
+/// Error of a fetcher that couldn't get a response, because of a timeout when `timeout` is true.
+pub fn fetch_error(
+    url: &str,
+    timeout: bool,
+    cause: Box<dyn std::error::Error + Send + Sync + 'static>,
+) -> IncludeLoaderError {
+    if timeout {
+        IncludeLoaderError::new(url, ErrorKind::TimedOut)
+            .with_message("the request took too long")
+            .with_cause(cause)
+    } else {
+        IncludeLoaderError::new(url, ErrorKind::NotFound)
+            .with_message("unable to fetch template")
+            .with_cause(cause)
+    }
+}
+
+fn too_large_error(url: &str) -> IncludeLoaderError {
+    IncludeLoaderError::new(url, ErrorKind::FileTooLarge)
+        .with_message("the template exceeds the maximum size")
+}
+
+/// Checks the announced size of the body, before reading it.
+pub fn check_content_length(
+    url: &str,
+    length: Option<u64>,
+    max_size: Option<usize>,
+) -> Result<(), IncludeLoaderError> {
+    match (length, max_size) {
+        (Some(length), Some(max_size)) if length > max_size as u64 => Err(too_large_error(url)),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the body, stopping as soon as it exceeds the maximum size.
+pub fn read_body<R: Read>(
+    url: &str,
+    reader: R,
+    max_size: Option<usize>,
+) -> Result<String, IncludeLoaderError> {
+    let mut buffer = Vec::new();
+    let result = match max_size {
+        Some(max_size) => reader.take(max_size as u64 + 1).read_to_end(&mut buffer),
+        None => { reader }.read_to_end(&mut buffer),
+    };
+    result.map_err(|err| {
+        let timeout = matches!(err.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock);
+        fetch_error(url, timeout, Box::new(err))
+    })?;
+    into_body(url, buffer, max_size)
+}
+
+fn into_body(
+    url: &str,
+    buffer: Vec<u8>,
+    max_size: Option<usize>,
+) -> Result<String, IncludeLoaderError> {
+    if max_size
+        .map(|max_size| buffer.len() > max_size)
+        .unwrap_or(false)
+    {
+        return Err(too_large_error(url));
+    }
+    String::from_utf8(buffer).map_err(|err| {
+        IncludeLoaderError::new(url, ErrorKind::InvalidData)
+            .with_message("unable to convert remote template as string")
+            .with_cause(Box::new(err))
+    })
+}
+
+pub trait HttpFetcher: Default + Debug + Send + Sync {
+    /// Sends a `GET` request.
+    ///
+    /// The redirections must not be followed, the loader follows them to check each location
+    /// against its origin list. The body is only read for successful responses, within the
+    /// maximum size of the request.
+    fn fetch(&self, request: &HttpRequest) -> Result<HttpResponse, IncludeLoaderError>;
+
+    /// Fetches the `ETag` of the remote template, without its content.
+    fn etag(&self, _request: &HttpRequest) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+/// Asynchronous version of the [`HttpFetcher`], used by the [`HttpIncludeLoader`] when
+/// parsing with [`parse_with_options_async`](crate::parse_with_options_async).
+pub trait AsyncHttpFetcher: Default + Debug + Send + Sync {
+    /// Sends a `GET` request, following the same rules as [`HttpFetcher::fetch`].
+    async fn fetch(&self, request: &HttpRequest<'_>) -> Result<HttpResponse, IncludeLoaderError>;
+
+    /// Waits before retrying a request, using the timer of the runtime.
+    async fn sleep(&self, duration: Duration);
+}
+
+#[cfg(any(feature = "http-loader-reqwest", feature = "http-loader-async-reqwest"))]
+fn client_error(url: &str, message: &str) -> IncludeLoaderError {
+    IncludeLoaderError::new(url, ErrorKind::Other)
+        .with_message("unable to build the http client")
+        .with_cause(message.into())
+}
 
 #[cfg(feature = "http-loader-reqwest")]
-impl HttpFetcher for ReqwestFetcher {
-    fn fetch(
+#[derive(Debug)]
+/// Fetcher using the blocking client of `reqwest`.
+///
+/// When the client can't be built by [`Default`], each request fails with the reason,
+/// use [`ReqwestFetcher::new`] to get the error upfront.
+pub struct ReqwestFetcher(Result<reqwest::blocking::Client, String>);
+
+#[cfg(feature = "http-loader-reqwest")]
+impl Default for ReqwestFetcher {
+    fn default() -> Self {
+        Self::new().unwrap_or_else(|err| Self(Err(err.to_string())))
+    }
+}
+
+#[cfg(feature = "http-loader-reqwest")]
+impl ReqwestFetcher {
+    /// Builds the client, which fails when its TLS backend can't be initialized.
+    pub fn new() -> Result<Self, reqwest::Error> {
+        // the redirections are followed by the loader
+        reqwest::blocking::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map(|client| Self(Ok(client)))
+    }
+
+    fn request(
         &self,
-        url: &str,
-        headers: &HashMap<String, String>,
-    ) -> Result<String, IncludeLoaderError> {
-        let req = self.0.get(url);
-        let req = headers
+        method: reqwest::Method,
+        request: &HttpRequest,
+    ) -> Result<reqwest::blocking::RequestBuilder, IncludeLoaderError> {
+        let client = self
+            .0
+            .as_ref()
+            .map_err(|message| client_error(request.url, message))?;
+        let req = client.request(method, request.url);
+        let req = match request.timeout {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
+        };
+        Ok(request
+            .headers
+            .iter()
+            .fold(req, |r, (key, value)| r.header(key, value)))
+    }
+}
+
+#[cfg(feature = "http-loader-reqwest")]
+impl HttpFetcher for ReqwestFetcher {
+    fn fetch(&self, request: &HttpRequest) -> Result<HttpResponse, IncludeLoaderError> {
+        let res = self
+            .request(reqwest::Method::GET, request)?
+            .send()
+            .map_err(|err| fetch_error(request.url, err.is_timeout(), Box::new(err)))?;
+        let mut response = reqwest_response(res.status(), res.headers());
+        if response.is_success() {
+            check_content_length(request.url, res.content_length(), request.max_size)?;
+            response.body = read_body(request.url, res, request.max_size)?;
+        }
+        Ok(response)
+    }
+
+    fn etag(&self, request: &HttpRequest) -> Option<String> {
+        let res = self
+            .request(reqwest::Method::HEAD, request)
+            .ok()?
+            .send()
+            .ok()?
+            .error_for_status()
+            .ok()?;
+        res.headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+    }
+}
+
+#[cfg(any(feature = "http-loader-reqwest", feature = "http-loader-async-reqwest"))]
+fn reqwest_response(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> HttpResponse {
+    let header = |name: reqwest::header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+    };
+    HttpResponse {
+        status: status.as_u16(),
+        content_type: header(reqwest::header::CONTENT_TYPE),
+        location: header(reqwest::header::LOCATION),
+        body: String::default(),
+    }
+}
+
+#[cfg(feature = "http-loader-async-reqwest")]
+#[derive(Debug)]
+/// Fetcher using the asynchronous client of `reqwest`, to use within a `tokio` runtime.
+///
+/// When the client can't be built by [`Default`], each request fails with the reason,
+/// use [`AsyncReqwestFetcher::new`] to get the error upfront.
+pub struct AsyncReqwestFetcher(Result<reqwest::Client, String>);
+
+#[cfg(feature = "http-loader-async-reqwest")]
+impl Default for AsyncReqwestFetcher {
+    fn default() -> Self {
+        Self::new().unwrap_or_else(|err| Self(Err(err.to_string())))
+    }
+}
+
+#[cfg(feature = "http-loader-async-reqwest")]
+impl AsyncReqwestFetcher {
+    /// Builds the client, which fails when its TLS backend can't be initialized.
+    pub fn new() -> Result<Self, reqwest::Error> {
+        // the redirections are followed by the loader
+        reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map(|client| Self(Ok(client)))
+    }
+}
+
+#[cfg(feature = "http-loader-async-reqwest")]
+#[async_trait::async_trait]
+impl AsyncHttpFetcher for AsyncReqwestFetcher {
+    async fn fetch(&self, request: &HttpRequest<'_>) -> Result<HttpResponse, IncludeLoaderError> {
+        let client = self
+            .0
+            .as_ref()
+            .map_err(|message| client_error(request.url, message))?;
+        let req = client.get(request.url);
+        let req = match request.timeout {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
+        };
+        let req = request
+            .headers
             .iter()
             .fold(req, |r, (key, value)| r.header(key, value));
-        let res = req.send().map_err(|err| {
-            IncludeLoaderError::new(url, ErrorKind::NotFound)
-                .with_message("unable to fetch template")
-                .with_cause(Box::new(err))
-        })?;
-        let res = res.error_for_status().map_err(|err| {
-            IncludeLoaderError::new(url, ErrorKind::NotFound)
-                .with_message("unable to fetch template")
-                .with_cause(Box::new(err))
-        })?;
-        res.text().map_err(|err| {
-            IncludeLoaderError::new(url, ErrorKind::InvalidData)
-                .with_message("unable to convert remote template as string")
-                .with_cause(Box::new(err))
-        })
+        let mut res = req
+            .send()
+            .await
+            .map_err(|err| fetch_error(request.url, err.is_timeout(), Box::new(err)))?;
+        let mut response = reqwest_response(res.status(), res.headers());
+        if response.is_success() {
+            check_content_length(request.url, res.content_length(), request.max_size)?;
+            let mut buffer = Vec::new();
+            while let Some(chunk) = res
+                .chunk()
+                .await
+                .map_err(|err| fetch_error(request.url, err.is_timeout(), Box::new(err)))?
+            {
+                buffer.extend_from_slice(&chunk);
+                if let Some(max_size) = request.max_size {
+                    if buffer.len() > max_size {
+                        return Err(too_large_error(request.url));
+                    }
+                }
+            }
+            response.body = into_body(request.url, buffer, request.max_size)?;
+        }
+        Ok(response)
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
     }
 }
 
 #[cfg(feature = "http-loader-ureq")]
-#[derive(Debug, Default)]
-pub struct UreqFetcher;
+#[derive(Debug)]
+pub struct UreqFetcher(ureq::Agent);
 
 #[cfg(feature = "http-loader-ureq")]
-impl HttpFetcher for UreqFetcher {
-    fn fetch(
-        &self,
-        url: &str,
-        headers: &HashMap<String, String>,
-    ) -> Result<String, IncludeLoaderError> {
-        let req = ureq::get(url);
-        let req = headers
+impl Default for UreqFetcher {
+    fn default() -> Self {
+        // the redirections are followed by the loader
+        Self(ureq::AgentBuilder::new().redirects(0).build())
+    }
+}
+
+#[cfg(feature = "http-loader-ureq")]
+impl UreqFetcher {
+    fn request(&self, method: &str, request: &HttpRequest) -> ureq::Request {
+        let req = self.0.request(method, request.url);
+        let req = match request.timeout {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
+        };
+        request
+            .headers
             .iter()
-            .fold(req, |r, (key, value)| r.set(key.as_str(), value.as_str()));
-        req.call()
-            .map_err(|err| {
-                IncludeLoaderError::new(url, ErrorKind::NotFound)
-                    .with_message("unable to fetch template")
-                    .with_cause(Box::new(err))
-            })?
-            .into_string()
-            .map_err(|err| {
-                IncludeLoaderError::new(url, ErrorKind::InvalidData)
-                    .with_message("unable to convert remote template as string")
-                    .with_cause(Box::new(err))
-            })
+            .fold(req, |r, (key, value)| r.set(key.as_str(), value.as_str()))
+    }
+}
+
+#[cfg(feature = "http-loader-ureq")]
+impl HttpFetcher for UreqFetcher {
+    fn fetch(&self, request: &HttpRequest) -> Result<HttpResponse, IncludeLoaderError> {
+        let res = match self.request("GET", request).call() {
+            Ok(res) | Err(ureq::Error::Status(_, res)) => res,
+            Err(ureq::Error::Transport(err)) => {
+                let timeout = std::error::Error::source(&err)
+                    .and_then(|cause| cause.downcast_ref::<std::io::Error>())
+                    .map(|cause| {
+                        matches!(cause.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock)
+                    })
+                    .unwrap_or(false);
+                return Err(fetch_error(request.url, timeout, Box::new(err)));
+            }
+        };
+        let mut response = HttpResponse {
+            status: res.status(),
+            content_type: res.header("content-type").map(String::from),
+            location: res.header("location").map(String::from),
+            body: String::default(),
+        };
+        if response.is_success() {
+            let length = res
+                .header("content-length")
+                .and_then(|value| value.parse::<u64>().ok());
+            check_content_length(request.url, length, request.max_size)?;
+            response.body = read_body(request.url, res.into_reader(), request.max_size)?;
+        }
+        Ok(response)
+    }
+
+    fn etag(&self, request: &HttpRequest) -> Option<String> {
+        self.request("HEAD", request)
+            .call()
+            .ok()?
+            .header("etag")
+            .map(String::from)
     }
 }
 
@@ -103,10 +415,32 @@ impl OriginList {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 /// This struct is an [`IncludeLoader`](crate::prelude::parse::loader::IncludeLoader) where
 /// you can read a template from an http server and be able to use it with [`mj-include`](crate::mj_include).
 ///
+/// A slow or huge template shouldn't block the parsing, so the requests are limited:
+///
+/// - a request times out after [`DEFAULT_TIMEOUT`], see [`with_timeout`](Self::with_timeout),
+/// - a template can't be bigger than [`DEFAULT_MAX_SIZE`], see [`with_max_size`](Self::with_max_size),
+/// - up to [`DEFAULT_MAX_REDIRECTS`] redirections are followed, the origin of each location
+///   being checked against the origin list, see [`with_max_redirects`](Self::with_max_redirects),
+/// - a failed request isn't retried unless a [`RetryPolicy`] is given, see [`with_retry`](Self::with_retry),
+/// - any content type is accepted, unless restricted with [`with_content_types`](Self::with_content_types).
+///
+/// Each failure is reported with its own [`reason`](IncludeLoaderError::reason):
+///
+/// | reason | failure |
+/// |--------|---------|
+/// | [`InvalidInput`](ErrorKind::InvalidInput) | invalid url or origin not allowed |
+/// | [`PermissionDenied`](ErrorKind::PermissionDenied) | redirection to an origin not allowed |
+/// | [`Other`](ErrorKind::Other) | too many redirections or http client not built |
+/// | [`TimedOut`](ErrorKind::TimedOut) | the request took too long |
+/// | [`FileTooLarge`](ErrorKind::FileTooLarge) | the template exceeds the maximum size |
+/// | [`Unsupported`](ErrorKind::Unsupported) | the content type is not accepted |
+/// | [`InvalidData`](ErrorKind::InvalidData) | the template is not valid utf-8 |
+/// | [`NotFound`](ErrorKind::NotFound) | unreachable server or error status |
+///
 /// # Example with `reqwest`
 /// ```rust
 /// #[cfg(feature = "http-loader-reqwest")]
@@ -114,18 +448,21 @@ impl OriginList {
 ///     use mrml::prelude::parse::http_loader::{HttpIncludeLoader, ReqwestFetcher};
 ///     use mrml::prelude::parse::ParserOptions;
 ///     use std::collections::HashSet;
-///     use std::rc::Rc;
+///     use std::sync::Arc;
 ///
-///     let resolver = HttpIncludeLoader::<ReqwestFetcher>::new_allow(HashSet::from(["http://localhost".to_string()]));
+///     let fetcher = ReqwestFetcher::new().expect("unable to build the http client");
+///     let resolver = HttpIncludeLoader::new_allow(HashSet::from(["http://localhost".to_string()]))
+///         .with_fetcher(fetcher);
 ///     let opts = ParserOptions {
 ///         include_loader: Box::new(resolver),
+///         ..Default::default()
 ///     };
 ///     let template = r#"<mjml>
 ///       <mj-body>
 ///         <mj-include path="http://localhost/partials/mj-body.mjml" />
 ///       </mj-body>
 ///     </mjml>"#;
-///     match mrml::parse_with_options(template, Rc::new(opts)) {
+///     match mrml::parse_with_options(template, Arc::new(opts)) {
 ///         Ok(_) => println!("Success!"),
 ///         Err(err) => eprintln!("Couldn't parse template: {err:?}"),
 ///     }
@@ -139,41 +476,91 @@ impl OriginList {
 ///     use mrml::prelude::parse::http_loader::{HttpIncludeLoader, UreqFetcher};
 ///     use mrml::prelude::parse::ParserOptions;
 ///     use std::collections::HashSet;
-///     use std::rc::Rc;
+///     use std::sync::Arc;
 ///
 ///     let resolver = HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from(["http://localhost".to_string()]));
 ///     let opts = ParserOptions {
 ///         include_loader: Box::new(resolver),
+///         ..Default::default()
+///     };
+///     let template = r#"<mjml>
+///       <mj-body>
+///         <mj-include path="http://localhost/partials/mj-body.mjml" />
+///       </mj-body>
+///     </mjml>"#;
+///     match mrml::parse_with_options(template, Arc::new(opts)) {
+///         Ok(_) => println!("Success!"),
+///         Err(err) => eprintln!("Couldn't parse template: {err:?}"),
+///     }
+/// }
+/// ```
+///
+/// # Example with the asynchronous `reqwest` client
+/// ```rust
+/// #[cfg(feature = "http-loader-async-reqwest")]
+/// {
+///     use mrml::prelude::parse::http_loader::{AsyncReqwestFetcher, HttpIncludeLoader};
+///     use mrml::prelude::parse::AsyncParserOptions;
+///     use std::collections::HashSet;
+///     use std::sync::Arc;
+///
+///     let resolver = HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(HashSet::from(["http://localhost".to_string()]));
+///     let opts = AsyncParserOptions {
+///         include_loader: Box::new(resolver),
+///         ..Default::default()
 ///     };
 ///     let template = r#"<mjml>
 ///       <mj-body>
 ///         <mj-include path="http://localhost/partials/mj-body.mjml" />
 ///       </mj-body>
 ///     </mjml>"#;
-///     match mrml::parse_with_options(template, Rc::new(opts)) {
+///     # tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(async {
+///     match mrml::parse_with_options_async(template, Arc::new(opts)).await {
 ///         Ok(_) => println!("Success!"),
 ///         Err(err) => eprintln!("Couldn't parse template: {err:?}"),
 ///     }
+///     # })
 /// }
 /// ```
 pub struct HttpIncludeLoader<F> {
     origin: OriginList,
     headers: HashMap<String, String>,
+    timeout: Option<Duration>,
+    max_size: Option<usize>,
+    max_redirects: usize,
+    retry: RetryPolicy,
+    /// Accepted content types, any content type being accepted when `None`.
+    content_types: Option<HashSet<String>>,
     fetcher: F,
 }
 
-impl<F: HttpFetcher> HttpIncludeLoader<F> {
-    /// Creates a new [`HttpIncludeLoader`](crate::prelude::parse::http_loader::HttpIncludeLoader) that allows all the origins.
-    ///
-    /// If you use this method, you should be careful, you could be loading some data from anywhere.
-    pub fn allow_all() -> Self {
+impl<F: Default> Default for HttpIncludeLoader<F> {
+    fn default() -> Self {
+        Self::new(OriginList::default())
+    }
+}
+
+impl<F: Default> HttpIncludeLoader<F> {
+    fn new(origin: OriginList) -> Self {
         Self {
-            origin: OriginList::Deny(Default::default()),
+            origin,
             headers: HashMap::default(),
+            timeout: Some(DEFAULT_TIMEOUT),
+            max_size: Some(DEFAULT_MAX_SIZE),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            retry: RetryPolicy::default(),
+            content_types: None,
             fetcher: F::default(),
         }
     }
 
+    /// Creates a new [`HttpIncludeLoader`](crate::prelude::parse::http_loader::HttpIncludeLoader) that allows all the origins.
+    ///
+    /// If you use this method, you should be careful, you could be loading some data from anywhere.
+    pub fn allow_all() -> Self {
+        Self::new(OriginList::Deny(Default::default()))
+    }
+
     /// Creates a new instance with an allow list to filter the origins.
     ///
     /// # Example with `reqwest`
@@ -198,11 +585,7 @@ impl<F: HttpFetcher> HttpIncludeLoader<F> {
     /// }
     /// ```
     pub fn new_allow(origins: HashSet<String>) -> Self {
-        Self {
-            origin: OriginList::Allow(origins),
-            headers: HashMap::default(),
-            fetcher: F::default(),
-        }
+        Self::new(OriginList::Allow(origins))
     }
 
     /// Creates a new instance with an dey list to filter the origins.
@@ -229,11 +612,7 @@ impl<F: HttpFetcher> HttpIncludeLoader<F> {
     /// }
     /// ```
     pub fn new_deny(origins: HashSet<String>) -> Self {
-        Self {
-            origin: OriginList::Deny(origins),
-            headers: HashMap::default(),
-            fetcher: F::default(),
-        }
+        Self::new(OriginList::Deny(origins))
     }
 
     pub fn with_header<K: ToString, V: ToString>(mut self, name: K, value: V) -> Self {
@@ -254,6 +633,53 @@ impl<F: HttpFetcher> HttpIncludeLoader<F> {
         self.headers = headers;
     }
 
+    /// Sets the maximum duration of a request, `None` meaning no limit.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum size of a template in bytes, `None` meaning no limit.
+    pub fn with_max_size(mut self, max_size: Option<usize>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the maximum number of redirections to follow, `0` meaning that redirections are errors.
+    pub fn with_max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Only accepts the templates served with one of the given content types, like `text/html`.
+    /// The parameters of the content type, like the charset, are ignored.
+    pub fn with_content_types<I, S>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.content_types = Some(
+            content_types
+                .into_iter()
+                .map(|item| item.as_ref().to_ascii_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Uses the given fetcher, like one built with its fallible constructor.
+    pub fn with_fetcher(mut self, fetcher: F) -> Self {
+        self.fetcher = fetcher;
+        self
+    }
+}
+
+impl<F> HttpIncludeLoader<F> {
     /// Check that the given url provided by the `path` attribute in the `mj-include` complies with the filtering.
     fn check_url(&self, path: &str) -> Result<(), IncludeLoaderError> {
         let url = url::Url::parse(path).map_err(|err| {
@@ -269,19 +695,248 @@ impl<F: HttpFetcher> HttpIncludeLoader<F> {
                 .with_message("the path is not allowed by the defined list of domains"))
         }
     }
+
+    fn request<'a>(&'a self, url: &'a str) -> HttpRequest<'a> {
+        HttpRequest {
+            url,
+            headers: &self.headers,
+            timeout: self.timeout,
+            max_size: self.max_size,
+        }
+    }
+
+    fn check_content_type(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+    ) -> Result<(), IncludeLoaderError> {
+        let accepted = match self.content_types {
+            Some(ref accepted) => accepted,
+            None => return Ok(()),
+        };
+        let essence = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        match essence {
+            Some(essence) if accepted.contains(&essence) => Ok(()),
+            _ => Err(IncludeLoaderError::new(path, ErrorKind::Unsupported)
+                .with_message("the content type of the template is not accepted")),
+        }
+    }
+
+    fn follow(&self, attempt: &mut Attempt, location: &str) -> Result<Next, IncludeLoaderError> {
+        if attempt.redirects >= self.max_redirects {
+            return Err(IncludeLoaderError::new(attempt.path, ErrorKind::Other)
+                .with_message("too many redirections"));
+        }
+        let url = url::Url::parse(&attempt.url)
+            .and_then(|base| base.join(location))
+            .map_err(|err| {
+                IncludeLoaderError::new(attempt.path, ErrorKind::InvalidInput)
+                    .with_message("unable to parse the redirection url")
+                    .with_cause(Box::new(err))
+            })?;
+        if !self.origin.is_allowed(&url.origin().ascii_serialization()) {
+            return Err(
+                IncludeLoaderError::new(attempt.path, ErrorKind::PermissionDenied)
+                    .with_message("redirected to a domain not allowed by the defined list"),
+            );
+        }
+        attempt.redirects += 1;
+        attempt.url = url.into();
+        Ok(Next::Fetch)
+    }
+
+    /// Decides what to do with the result of a request.
+    fn next(
+        &self,
+        attempt: &mut Attempt,
+        result: Result<HttpResponse, IncludeLoaderError>,
+    ) -> Result<Next, IncludeLoaderError> {
+        let response = match result {
+            Ok(value) => value,
+            // the server couldn't be reached or was too slow
+            Err(err) if matches!(err.reason, ErrorKind::NotFound | ErrorKind::TimedOut) => {
+                return attempt.retry(&self.retry, err)
+            }
+            Err(err) => return Err(err),
+        };
+        if response.is_redirection() {
+            return match response.location {
+                Some(ref location) => self.follow(attempt, location),
+                None => Err(IncludeLoaderError::not_found(attempt.path)
+                    .with_message("redirection without location")),
+            };
+        }
+        if !response.is_success() {
+            let err = IncludeLoaderError::not_found(attempt.path)
+                .with_message("unable to fetch template")
+                .with_cause(format!("unexpected status {}", response.status).into());
+            return if response.is_retryable() {
+                attempt.retry(&self.retry, err)
+            } else {
+                Err(err)
+            };
+        }
+        self.check_content_type(attempt.path, response.content_type.as_deref())?;
+        Ok(Next::Done(response.body))
+    }
+}
+
+/// Delays between the attempts of a failed request.
+///
+/// A request is retried when the server can't be reached, takes too long to answer or
+/// answers with a `429` or `5xx` status. The delay doubles after each attempt, starting at
+/// `initial_backoff`, without exceeding `max_backoff`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Number of attempts after the first one, none by default.
+    pub max_retries: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Delay before the given retry, starting at `0`.
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let factor = 2_u32.saturating_pow(retry.min(31) as u32);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Progress of the loading of a template, shared by the blocking and asynchronous loaders.
+struct Attempt<'a> {
+    /// Path given by the `mj-include`, used in the errors.
+    path: &'a str,
+    /// Url of the next request, changed by the redirections.
+    url: String,
+    redirects: usize,
+    retries: usize,
+}
+
+impl<'a> Attempt<'a> {
+    fn new(path: &'a str) -> Self {
+        Self {
+            path,
+            url: path.to_string(),
+            redirects: 0,
+            retries: 0,
+        }
+    }
+
+    fn retry(
+        &mut self,
+        policy: &RetryPolicy,
+        err: IncludeLoaderError,
+    ) -> Result<Next, IncludeLoaderError> {
+        if self.retries >= policy.max_retries {
+            return Err(err);
+        }
+        let delay = policy.backoff(self.retries);
+        self.retries += 1;
+        Ok(Next::Retry(delay))
+    }
+}
+
+enum Next {
+    Done(String),
+    Fetch,
+    Retry(Duration),
+}
+
+/// Resolves a relative url against the url of the including template.
+fn join_url(path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+    match parent {
+        Some(parent) => url::Url::parse(parent)
+            .and_then(|base| base.join(path))
+            .map(String::from)
+            .map_err(|err| {
+                IncludeLoaderError::new(path, ErrorKind::InvalidInput)
+                    .with_message("unable to parse the provided url")
+                    .with_cause(Box::new(err))
+            }),
+        None => Ok(path.to_string()),
+    }
 }
 
 impl<F: HttpFetcher> IncludeLoader for HttpIncludeLoader<F> {
+    /// Relative urls are resolved against the url of the including template.
+    fn resolve_path(&self, path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+        join_url(path, parent)
+    }
+
+    /// Uses the `ETag` of the remote template, fetched with a `HEAD` request.
+    fn version(&self, path: &str) -> Option<String> {
+        self.check_url(path).ok()?;
+        self.fetcher.etag(&self.request(path))
+    }
+
     fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
         self.check_url(path)?;
-        self.fetcher.fetch(path, &self.headers)
+        let mut attempt = Attempt::new(path);
+        loop {
+            let result = self.fetcher.fetch(&self.request(&attempt.url));
+            match self.next(&mut attempt, result)? {
+                Next::Done(content) => return Ok(content),
+                Next::Fetch => {}
+                Next::Retry(delay) => std::thread::sleep(delay),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<F: AsyncHttpFetcher> super::loader::AsyncIncludeLoader for HttpIncludeLoader<F> {
+    /// Relative urls are resolved against the url of the including template.
+    fn resolve_path(&self, path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+        join_url(path, parent)
+    }
+
+    async fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        self.check_url(path)?;
+        let mut attempt = Attempt::new(path);
+        loop {
+            let result = self.fetcher.fetch(&self.request(&attempt.url)).await;
+            match self.next(&mut attempt, result)? {
+                Next::Done(content) => return Ok(content),
+                Next::Fetch => {}
+                Next::Retry(delay) => self.fetcher.sleep(delay).await,
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod common_tests {
-    use super::OriginList;
+    use super::{OriginList, RetryPolicy};
     use std::collections::HashSet;
+    use std::time::Duration;
 
     #[test]
     fn origin_list_is_allowed() {
@@ -291,14 +946,26 @@ mod common_tests {
         assert!(!OriginList::Deny(HashSet::from(["somewhere".to_string()])).is_allowed("somewhere"));
         assert!(OriginList::Deny(HashSet::default()).is_allowed("somewhere"));
     }
+
+    #[test]
+    fn retry_policy_should_double_backoff() {
+        let policy = RetryPolicy::new(10)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(100), Duration::from_millis(500));
+    }
 }
 
 #[cfg(all(test, feature = "http-loader-ureq"))]
 mod ureq_tests {
-    use super::{HttpIncludeLoader, UreqFetcher};
+    use super::{HttpIncludeLoader, RetryPolicy, UreqFetcher};
     use crate::prelude::parse::loader::IncludeLoader;
     use std::collections::{HashMap, HashSet};
     use std::io::ErrorKind;
+    use std::time::Duration;
 
     #[test]
     fn include_loader_should_implement_debug() {
@@ -362,6 +1029,81 @@ mod ureq_tests {
         m.assert();
     }
 
+    #[test]
+    fn include_loader_should_resolve_relative_urls() {
+        let loader = HttpIncludeLoader::<UreqFetcher>::allow_all();
+        let parent = Some("https://somewhere/partials/header.mjml");
+        assert_eq!(
+            loader.resolve_path("./logo.mjml", parent).unwrap(),
+            "https://somewhere/partials/logo.mjml"
+        );
+        assert_eq!(
+            loader.resolve_path("../shared/logo.mjml", parent).unwrap(),
+            "https://somewhere/shared/logo.mjml"
+        );
+        assert_eq!(
+            loader
+                .resolve_path("http://localhost/logo.mjml", parent)
+                .unwrap(),
+            "http://localhost/logo.mjml"
+        );
+        assert_eq!(
+            loader.resolve_path("./logo.mjml", None).unwrap(),
+            "./logo.mjml"
+        );
+    }
+
+    #[test]
+    fn include_loader_should_resolve_nested_relative_includes() {
+        use crate::prelude::parse::ParserOptions;
+        use std::sync::Arc;
+
+        let mut mock_server = mockito::Server::new();
+        let header = mock_server
+            .mock("GET", "/partials/header.mjml")
+            .with_status(200)
+            .with_body("<mj-section><mj-include path=\"./column.mjml\" /></mj-section>")
+            .create();
+        let column = mock_server
+            .mock("GET", "/partials/column.mjml")
+            .with_status(200)
+            .with_body("<mj-column><mj-text>Hello</mj-text></mj-column>")
+            .create();
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from(
+                [mock_server.url()],
+            ))),
+            ..Default::default()
+        });
+        let template = format!(
+            "<mjml><mj-body><mj-include path=\"{}/partials/header.mjml\" /></mj-body></mjml>",
+            mock_server.url()
+        );
+        assert!(crate::mjml::Mjml::parse_with_options(template, opts).is_ok());
+        header.assert();
+        column.assert();
+    }
+
+    #[test]
+    fn include_loader_should_use_etag_as_version() {
+        let mut mock_server = mockito::Server::new();
+        let m = mock_server
+            .mock("HEAD", "/partial.mjml")
+            .with_status(200)
+            .with_header("etag", "\"abcd\"")
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        assert_eq!(
+            loader
+                .version(&format!("{}/partial.mjml", mock_server.url()))
+                .as_deref(),
+            Some("\"abcd\"")
+        );
+        assert_eq!(loader.version("http://somewhere/partial.mjml"), None);
+        m.assert();
+    }
+
     #[test]
     fn include_loader_should_resolve_with_not_found() {
         let mut mock_server = mockito::Server::new();
@@ -401,6 +1143,176 @@ mod ureq_tests {
         assert_eq!(err.reason, ErrorKind::NotFound);
         m.assert();
     }
+
+    #[test]
+    fn include_loader_should_timeout() {
+        let mut mock_server = mockito::Server::new();
+        let _m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_secs(1));
+                w.write_all(b"<mj-text>Hello World!</mj-text>")
+            })
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_timeout(Some(Duration::from_millis(100)));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn include_loader_should_limit_size() {
+        let mut mock_server = mockito::Server::new();
+        let _m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create();
+        let _chunked = mock_server
+            .mock("GET", "/chunked.mjml")
+            .with_status(200)
+            .with_chunked_body(|w| w.write_all(b"<mj-text>Hello World!</mj-text>"))
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_max_size(Some(10));
+        for path in ["partial.mjml", "chunked.mjml"] {
+            let err = loader
+                .resolve(&format!("{}/{path}", mock_server.url()))
+                .unwrap_err();
+            assert_eq!(err.reason, ErrorKind::FileTooLarge);
+        }
+        let loader = loader.with_max_size(None);
+        assert!(loader
+            .resolve(&format!("{}/chunked.mjml", mock_server.url()))
+            .is_ok());
+    }
+
+    #[test]
+    fn include_loader_should_follow_redirections() {
+        let mut mock_server = mockito::Server::new();
+        let redirect = mock_server
+            .mock("GET", "/old.mjml")
+            .with_status(301)
+            .with_header("location", "/partial.mjml")
+            .create();
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        assert_eq!(
+            loader
+                .resolve(&format!("{}/old.mjml", mock_server.url()))
+                .unwrap(),
+            "<mj-text>Hello World!</mj-text>"
+        );
+        redirect.assert();
+        m.assert();
+        let err = loader
+            .with_max_redirects(0)
+            .resolve(&format!("{}/old.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::Other);
+        assert_eq!(err.message, Some("too many redirections"));
+    }
+
+    #[test]
+    fn include_loader_should_check_redirection_origin() {
+        let mut mock_server = mockito::Server::new();
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(302)
+            .with_header("location", "http://somewhere/partial.mjml")
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::PermissionDenied);
+        m.assert();
+    }
+
+    #[test]
+    fn include_loader_should_retry() {
+        let mut mock_server = mockito::Server::new();
+        let unavailable = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(503)
+            .expect(1)
+            .create();
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_retry(
+                    RetryPolicy::new(2)
+                        .with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+                );
+        assert!(loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .is_ok());
+        unavailable.assert();
+        m.assert();
+    }
+
+    #[test]
+    fn include_loader_should_stop_retrying() {
+        let mut mock_server = mockito::Server::new();
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(503)
+            .expect(3)
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_retry(
+                    RetryPolicy::new(2)
+                        .with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+                );
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::NotFound);
+        m.assert();
+    }
+
+    #[test]
+    fn include_loader_should_check_content_type() {
+        let mut mock_server = mockito::Server::new();
+        let _text = mock_server
+            .mock("GET", "/partial.txt")
+            .with_status(200)
+            .with_header("content-type", "text/plain")
+            .with_body("Hello World!")
+            .create();
+        let _html = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_header("content-type", "Text/HTML; charset=utf-8")
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create();
+        let loader =
+            HttpIncludeLoader::<UreqFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_content_types(["text/html"]);
+        let err = loader
+            .resolve(&format!("{}/partial.txt", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::Unsupported);
+        assert!(loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .is_ok());
+    }
 }
 
 #[cfg(all(test, feature = "http-loader-reqwest"))]
@@ -409,12 +1321,23 @@ mod reqwest_tests {
     use crate::prelude::parse::loader::IncludeLoader;
     use std::collections::{HashMap, HashSet};
     use std::io::ErrorKind;
+    use std::time::Duration;
 
     #[test]
     fn include_loader_should_implement_debug() {
         let _ = format!("{:?}", HttpIncludeLoader::<ReqwestFetcher>::default());
     }
 
+    #[test]
+    fn fetcher_should_report_client_error() {
+        let loader = HttpIncludeLoader::allow_all()
+            .with_fetcher(ReqwestFetcher(Err("no tls backend".to_string())));
+        let err = loader.resolve("http://localhost/partial.mjml").unwrap_err();
+        assert_eq!(err.reason, ErrorKind::Other);
+        assert_eq!(err.message, Some("unable to build the http client"));
+        assert_eq!(err.cause.unwrap().to_string(), "no tls backend");
+    }
+
     #[test]
     fn include_loader_should_validate_url() {
         // allow everything
@@ -472,6 +1395,26 @@ mod reqwest_tests {
         m.assert();
     }
 
+    #[test]
+    fn include_loader_should_use_etag_as_version() {
+        let mut mock_server = mockito::Server::new();
+        let m = mock_server
+            .mock("HEAD", "/partial.mjml")
+            .with_status(200)
+            .with_header("etag", "\"abcd\"")
+            .create();
+        let loader =
+            HttpIncludeLoader::<ReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        assert_eq!(
+            loader
+                .version(&format!("{}/partial.mjml", mock_server.url()))
+                .as_deref(),
+            Some("\"abcd\"")
+        );
+        assert_eq!(loader.version("http://somewhere/partial.mjml"), None);
+        m.assert();
+    }
+
     #[test]
     fn include_loader_should_resolve_with_not_found() {
         let mut mock_server = mockito::Server::new();
@@ -511,4 +1454,239 @@ mod reqwest_tests {
         assert_eq!(err.reason, ErrorKind::NotFound);
         m.assert();
     }
+
+    #[test]
+    fn include_loader_should_timeout() {
+        let mut mock_server = mockito::Server::new();
+        let _m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_secs(1));
+                w.write_all(b"<mj-text>Hello World!</mj-text>")
+            })
+            .create();
+        let loader =
+            HttpIncludeLoader::<ReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_timeout(Some(Duration::from_millis(100)));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn include_loader_should_limit_size() {
+        let mut mock_server = mockito::Server::new();
+        let _m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_chunked_body(|w| w.write_all(b"<mj-text>Hello World!</mj-text>"))
+            .create();
+        let loader =
+            HttpIncludeLoader::<ReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_max_size(Some(10));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::FileTooLarge);
+    }
+
+    #[test]
+    fn include_loader_should_check_redirection_origin() {
+        let mut mock_server = mockito::Server::new();
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(307)
+            .with_header("location", "http://somewhere/partial.mjml")
+            .create();
+        let loader =
+            HttpIncludeLoader::<ReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::PermissionDenied);
+        m.assert();
+    }
+}
+
+#[cfg(all(test, feature = "http-loader-async-reqwest"))]
+mod async_reqwest_tests {
+    use super::{AsyncReqwestFetcher, HttpIncludeLoader, RetryPolicy};
+    use crate::prelude::parse::loader::AsyncIncludeLoader;
+    use crate::prelude::parse::{AsyncParserOptions, Error};
+    use std::collections::{HashMap, HashSet};
+    use std::io::ErrorKind;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn include_loader_should_resolve_with_content() {
+        let partial = "<mj-text>Hello World!</mj-text>";
+        let mut mock_server = mockito::Server::new_async().await;
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create_async()
+            .await;
+        let loader =
+            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        let resolved = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .await
+            .unwrap();
+        assert_eq!(partial, resolved);
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn include_loader_should_resolve_with_not_found() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(404)
+            .with_body("Not Found")
+            .create_async()
+            .await;
+        let loader =
+            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::NotFound);
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn include_loader_should_resolve_with_headers() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .match_header("user-agent", "mrml-test")
+            .with_status(200)
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create_async()
+            .await;
+        let loader =
+            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_headers(HashMap::from([(
+                    "user-agent".to_string(),
+                    "mrml-test".to_string(),
+                )]));
+        assert!(loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .await
+            .is_ok());
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn include_loader_should_retry_and_follow_redirections() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let unavailable = mock_server
+            .mock("GET", "/old.mjml")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let redirect = mock_server
+            .mock("GET", "/old.mjml")
+            .with_status(308)
+            .with_header("location", "/partial.mjml")
+            .create_async()
+            .await;
+        let m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_body("<mj-text>Hello World!</mj-text>")
+            .create_async()
+            .await;
+        let loader =
+            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_retry(
+                    RetryPolicy::new(1)
+                        .with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+                );
+        assert_eq!(
+            loader
+                .resolve(&format!("{}/old.mjml", mock_server.url()))
+                .await
+                .unwrap(),
+            "<mj-text>Hello World!</mj-text>"
+        );
+        unavailable.assert_async().await;
+        redirect.assert_async().await;
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn include_loader_should_limit_size() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let _m = mock_server
+            .mock("GET", "/partial.mjml")
+            .with_status(200)
+            .with_chunked_body(|w| w.write_all(b"<mj-text>Hello World!</mj-text>"))
+            .create_async()
+            .await;
+        let loader =
+            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(HashSet::from([mock_server.url()]))
+                .with_max_size(Some(10));
+        let err = loader
+            .resolve(&format!("{}/partial.mjml", mock_server.url()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.reason, ErrorKind::FileTooLarge);
+    }
+
+    #[tokio::test]
+    async fn should_parse_nested_relative_includes() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let header = mock_server
+            .mock("GET", "/partials/header.mjml")
+            .with_status(200)
+            .with_body(r#"<mj-wrapper><mj-include path="./logo.mjml" /></mj-wrapper>"#)
+            .create_async()
+            .await;
+        let logo = mock_server
+            .mock("GET", "/partials/logo.mjml")
+            .with_status(200)
+            .with_body(
+                r#"<mj-section><mj-column><mj-image src="logo.png" /></mj-column></mj-section>"#,
+            )
+            .create_async()
+            .await;
+        let opts = Arc::new(AsyncParserOptions {
+            include_loader: Box::new(HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(
+                HashSet::from([mock_server.url()]),
+            )),
+            ..Default::default()
+        });
+        let template = format!(
+            r#"<mjml><mj-body><mj-include path="{}/partials/header.mjml" /></mj-body></mjml>"#,
+            mock_server.url()
+        );
+        let root = crate::parse_with_options_async(template, opts)
+            .await
+            .unwrap();
+        assert_eq!(root.body().unwrap().children.len(), 1);
+        header.assert_async().await;
+        logo.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn should_not_load_forbidden_origins() {
+        let opts = Arc::new(AsyncParserOptions {
+            include_loader: Box::new(HttpIncludeLoader::<AsyncReqwestFetcher>::default()),
+            ..Default::default()
+        });
+        let template = r#"<mjml><mj-body><mj-include path="http://localhost/partial.mjml" /></mj-body></mjml>"#;
+        match crate::parse_with_options_async(template, opts).await {
+            Err(Error::IncludeLoaderError(origin, _)) => {
+                assert_eq!(origin.reason, ErrorKind::InvalidInput);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
 }
@@ -1,13 +1,16 @@
 //! Module containing the trait for implementing an [`IncludeLoader`](crate::prelude::parse::loader::IncludeLoader).
 
-use super::ParserOptions;
+use super::ParserContext;
+use crate::mj_include::body::MjIncludeBodyChild;
+use crate::mj_include::head::MjIncludeHeadChild;
 use crate::{
     comment::Comment,
-    prelude::parse::{next_token, Error, Parsable},
+    prelude::parse::{next_token, token_span, Error, Parsable},
     text::Text,
 };
 use std::io::ErrorKind;
 use std::rc::Rc;
+use std::sync::Arc;
 use xmlparser::Token;
 
 #[derive(Debug)]
@@ -70,24 +73,145 @@ impl std::error::Error for IncludeLoaderError {
     }
 }
 
-pub trait IncludeLoader: std::fmt::Debug {
+/// Loader of the templates included with `mj-include`. A loader is shared by all the
+/// templates parsed with the same [`ParserOptions`](super::ParserOptions), possibly from
+/// several threads, which is why it needs to be `Send` and `Sync`.
+pub trait IncludeLoader: std::fmt::Debug + Send + Sync {
     /// This function is used to fetch the included template using the `path` attribute.
     ///
     /// You can have an example of simple resolve function with the [`MemoryIncludeLoader`](crate::prelude::parse::memory_loader::MemoryIncludeLoader).
     fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError>;
+
+    /// This function builds the path of the template to load out of the `path` attribute of
+    /// an `mj-include` and the path of the included template containing it, if any.
+    ///
+    /// By default, the `path` attribute is used as is. Loaders can override it to support
+    /// paths relative to the including template, like mjml does.
+    fn resolve_path(
+        &self,
+        path: &str,
+        _parent: Option<&str>,
+    ) -> Result<String, IncludeLoaderError> {
+        Ok(path.to_string())
+    }
+
+    /// This function returns a value that changes with the content of the template,
+    /// like its modification time or its `ETag`, without loading it.
+    ///
+    /// It's used by the [`CachedIncludeLoader`](crate::prelude::parse::cached_loader::CachedIncludeLoader)
+    /// to check that a cached template is still up to date. By default, no version is available.
+    fn version(&self, _path: &str) -> Option<String> {
+        None
+    }
+
+    /// This function returns the element parsed out of the given content of the template,
+    /// as kept with [`store_parsed`](Self::store_parsed), so that it's not parsed again.
+    ///
+    /// It's used by the [`CachedIncludeLoader`](crate::prelude::parse::cached_loader::CachedIncludeLoader).
+    /// By default, nothing is kept.
+    fn parsed(&self, _path: &str, _content: &str) -> Option<ParsedInclude> {
+        None
+    }
+
+    /// This function is called with the element parsed out of the content of the template.
+    fn store_parsed(&self, _path: &str, _content: &str, _parsed: ParsedInclude) {}
+}
+
+/// Element parsed out of an included template, shared with the loaders keeping it.
+#[derive(Clone, Debug)]
+pub enum ParsedInclude {
+    Body(Arc<MjIncludeBodyChild>),
+    Head(Arc<MjIncludeHeadChild>),
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+/// Asynchronous version of the [`IncludeLoader`], to load the templates without blocking
+/// when parsing with [`parse_with_options_async`](crate::parse_with_options_async).
+pub trait AsyncIncludeLoader: std::fmt::Debug + Send + Sync {
+    /// This function is used to fetch the included template using the `path` attribute.
+    async fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError>;
+
+    /// Same as [`IncludeLoader::resolve_path`], building the path doesn't load anything.
+    fn resolve_path(
+        &self,
+        path: &str,
+        _parent: Option<&str>,
+    ) -> Result<String, IncludeLoaderError> {
+        Ok(path.to_string())
+    }
+}
+
+/// Element that can be parsed out of an included template and kept by the loader.
+pub(crate) trait IncludedElement: Parsable + From<Comment> + From<Text> + Clone {
+    fn wrap(value: Arc<Self>) -> ParsedInclude;
+    fn unwrap(value: ParsedInclude) -> Option<Arc<Self>>;
+}
+
+impl IncludedElement for MjIncludeBodyChild {
+    fn wrap(value: Arc<Self>) -> ParsedInclude {
+        ParsedInclude::Body(value)
+    }
+
+    fn unwrap(value: ParsedInclude) -> Option<Arc<Self>> {
+        match value {
+            ParsedInclude::Body(inner) => Some(inner),
+            ParsedInclude::Head(_) => None,
+        }
+    }
+}
+
+impl IncludedElement for MjIncludeHeadChild {
+    fn wrap(value: Arc<Self>) -> ParsedInclude {
+        ParsedInclude::Head(value)
+    }
+
+    fn unwrap(value: ParsedInclude) -> Option<Arc<Self>> {
+        match value {
+            ParsedInclude::Head(inner) => Some(inner),
+            ParsedInclude::Body(_) => None,
+        }
+    }
+}
+
+/// Parses the content of an included template, reusing the element kept by the loader
+/// when the content didn't change.
+///
+/// A template including other templates or raising diagnostics is not kept, so that the
+/// include limits are checked and the diagnostics are reported for every parsed template.
+pub(crate) fn parse_included<T: IncludedElement>(
+    path: &str,
+    content: &str,
+    ctx: Rc<ParserContext>,
+) -> Result<T, Error> {
+    let loader = &ctx.options.include_loader;
+    if let Some(element) = loader.parsed(path, content).and_then(T::unwrap) {
+        return Ok(element.as_ref().clone());
+    }
+    let diagnostics = ctx.diagnostics_count();
+    let includes = ctx.include_count();
+    let element =
+        parse::<T>(content, ctx.clone()).map_err(|err| err.resolve(content, Some(path)))?;
+    ctx.resolve_diagnostics(diagnostics, content, Some(path));
+    if ctx.diagnostics_count() > diagnostics || ctx.include_count() > includes {
+        return Ok(element);
+    }
+    let element = Arc::new(element);
+    loader.store_parsed(path, content, T::wrap(element.clone()));
+    Ok(Arc::try_unwrap(element).unwrap_or_else(|element| element.as_ref().clone()))
 }
 
 pub fn parse<T: Parsable + From<Comment> + From<Text>>(
     include: &str,
-    opts: Rc<ParserOptions>,
+    ctx: Rc<ParserContext>,
 ) -> Result<T, Error> {
     let mut tokenizer = xmlparser::Tokenizer::from(include);
     let token = next_token(&mut tokenizer)?;
     match token {
         Token::Comment { text, span: _ } => Ok(Comment::from(text.to_string()).into()),
         Token::Text { text } => Ok(Text::from(text.to_string()).into()),
-        Token::ElementStart { local, .. } => T::parse(local, &mut tokenizer, opts),
-        _ => Err(Error::InvalidFormat),
+        Token::ElementStart { local, .. } => T::parse(local, &mut tokenizer, ctx),
+        other => Err(Error::InvalidFormat(token_span(&other))),
     }
 }
 
@@ -4,6 +4,8 @@ use super::loader::IncludeLoaderError;
 use crate::prelude::parse::loader::IncludeLoader;
 use std::{io::ErrorKind, path::PathBuf};
 
+const PREFIX: &str = "file:///";
+
 #[derive(Debug, Default)]
 /// This struct is an [`IncludeLoader`](crate::prelude::parse::loader::IncludeLoader) where
 /// you can read a template for the filesystem and be able to use it with [`mj-include`](crate::mj_include).
@@ -11,7 +13,7 @@ use std::{io::ErrorKind, path::PathBuf};
 /// # Example
 /// ```rust
 /// use std::path::PathBuf;
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use mrml::mj_include::body::MjIncludeBodyKind;
 /// use mrml::prelude::parse::local_loader::LocalIncludeLoader;
 /// use mrml::prelude::parse::ParserOptions;
@@ -23,18 +25,22 @@ use std::{io::ErrorKind, path::PathBuf};
 /// let resolver = LocalIncludeLoader::new(root);
 /// let opts = ParserOptions {
 ///     include_loader: Box::new(resolver),
+///     ..Default::default()
 /// };
 /// let template = r#"<mjml>
 ///   <mj-body>
 ///     <mj-include path="file:///mj-accordion.mjml" />
 ///   </mj-body>
 /// </mjml>"#;
-/// match mrml::parse_with_options(template, Rc::new(opts)) {
+/// match mrml::parse_with_options(template, Arc::new(opts)) {
 ///     Ok(_) => println!("Success!"),
 ///     Err(err) => eprintln!("Couldn't parse template: {err:?}"),
 /// }
 /// ```
 ///
+/// The included templates can include other templates using paths relative to their own
+/// location, like `./logo.mjml` or `../shared/logo.mjml`.
+///
 /// About the security: this loader doesn't allow to go fetch a template that
 /// is in a parent directory of the root directory.
 pub struct LocalIncludeLoader {
@@ -48,7 +54,7 @@ impl LocalIncludeLoader {
 
     fn build_path(&self, url: &str) -> Result<PathBuf, IncludeLoaderError> {
         let path = url
-            .strip_prefix("file:///")
+            .strip_prefix(PREFIX)
             .map(|p| self.root.join(p))
             .ok_or_else(|| {
                 IncludeLoaderError::new(url, ErrorKind::InvalidInput)
@@ -68,6 +74,44 @@ impl LocalIncludeLoader {
 }
 
 impl IncludeLoader for LocalIncludeLoader {
+    /// Paths that don't start with `file:///` are relative to the directory of the
+    /// including template or, for the root template, to the root directory of the loader.
+    fn resolve_path(&self, path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+        if path.starts_with(PREFIX) {
+            return Ok(path.to_string());
+        }
+        let directory = parent
+            .filter(|_| !path.starts_with('/'))
+            .and_then(|parent| parent.strip_prefix(PREFIX))
+            .and_then(|parent| parent.rsplit_once('/'))
+            .map(|(directory, _)| directory)
+            .unwrap_or_default();
+        let mut segments: Vec<&str> = Vec::new();
+        for segment in directory.split('/').chain(path.split('/')) {
+            match segment {
+                "" | "." => {}
+                // going above the root is kept so that it gets rejected when loading
+                ".." if segments.last().map(|last| *last != "..").unwrap_or(false) => {
+                    segments.pop();
+                }
+                other => segments.push(other),
+            }
+        }
+        Ok(format!("{PREFIX}{}", segments.join("/")))
+    }
+
+    /// Uses the modification time of the file.
+    fn version(&self, url: &str) -> Option<String> {
+        let path = self.build_path(url).ok()?;
+        let modified = std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()?;
+        modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|value| value.as_nanos().to_string())
+    }
+
     fn resolve(&self, url: &str) -> Result<String, IncludeLoaderError> {
         let path = self.build_path(url)?;
         std::fs::read_to_string(path).map_err(|err| {
@@ -139,6 +183,70 @@ mod tests {
         assert_eq!(err.to_string(), "Unable to load template file:///../partial.mjml: the path should stay in the context of the loader (entity not found)");
     }
 
+    #[test]
+    fn should_use_modification_time_as_version() {
+        let loader = LocalIncludeLoader::current_dir();
+        assert!(loader
+            .version("file:///resources/compare/success/mj-body.mjml")
+            .is_some());
+        assert!(loader.version("file:///resources/missing.mjml").is_none());
+    }
+
+    #[test]
+    fn should_resolve_relative_paths() {
+        let loader = LocalIncludeLoader::default();
+        let parent = Some("file:///partials/header.mjml");
+        let cases = [
+            ("file:///other/logo.mjml", parent, "file:///other/logo.mjml"),
+            ("./logo.mjml", parent, "file:///partials/logo.mjml"),
+            ("logo.mjml", parent, "file:///partials/logo.mjml"),
+            ("../shared/./logo.mjml", parent, "file:///shared/logo.mjml"),
+            ("/logo.mjml", parent, "file:///logo.mjml"),
+            ("../../logo.mjml", parent, "file:///../logo.mjml"),
+            ("partials/logo.mjml", None, "file:///partials/logo.mjml"),
+        ];
+        for (path, parent, expected) in cases {
+            assert_eq!(loader.resolve_path(path, parent).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn should_resolve_nested_relative_includes() {
+        use crate::mj_include::body::MjIncludeBodyChild;
+        use crate::prelude::parse::ParserOptions;
+        use std::sync::Arc;
+
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("resources")
+            .join("partials");
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(LocalIncludeLoader::new(root)),
+            ..Default::default()
+        });
+        let template =
+            r#"<mjml><mj-body><mj-include path="file:///layout/header.mjml" /></mj-body></mjml>"#;
+        let root = crate::mjml::Mjml::parse_with_options(template, opts).unwrap();
+        let body = root.body().unwrap();
+        let header = body.children[0].as_mj_include().unwrap();
+        let section = match header.children.first() {
+            Some(MjIncludeBodyChild::MjSection(inner)) => inner,
+            other => panic!("expected a section, got {:?}", other),
+        };
+        let column = section.children[0].as_mj_column().unwrap();
+        let navigation = column.children[0].as_mj_include().unwrap();
+        assert_eq!(navigation.attributes.path, "./navigation.mjml");
+        assert!(matches!(
+            navigation.children.first(),
+            Some(MjIncludeBodyChild::MjNavbar(_))
+        ));
+        let logo = column.children[1].as_mj_include().unwrap();
+        assert_eq!(logo.attributes.path, "../shared/logo.mjml");
+        assert!(matches!(
+            logo.children.first(),
+            Some(MjIncludeBodyChild::MjImage(_))
+        ));
+    }
+
     #[test]
     fn should_resolve_path() {
         let loader = LocalIncludeLoader::current_dir();
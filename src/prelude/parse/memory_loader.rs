@@ -10,7 +10,7 @@ use crate::prelude::parse::loader::IncludeLoader;
 ///
 /// # Example
 /// ```rust
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use mrml::mj_include::body::MjIncludeBodyKind;
 /// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
 /// use mrml::prelude::parse::ParserOptions;
@@ -18,13 +18,14 @@ use crate::prelude::parse::loader::IncludeLoader;
 /// let resolver = MemoryIncludeLoader::from(vec![("basic.mjml", "<mj-button>Hello</mj-button>")]);
 /// let opts = ParserOptions {
 ///     include_loader: Box::new(resolver),
+///     ..Default::default()
 /// };
 /// let json = r#"<mjml>
 ///   <mj-body>
 ///     <mj-include path="basic.mjml" />
 ///   </mj-body>
 /// </mjml>"#;
-/// match mrml::parse_with_options(json, Rc::new(opts)) {
+/// match mrml::parse_with_options(json, Arc::new(opts)) {
 ///     Ok(_) => println!("Success!"),
 ///     Err(err) => eprintln!("Couldn't parse template: {err:?}"),
 /// }
@@ -57,3 +58,11 @@ impl IncludeLoader for MemoryIncludeLoader {
             .ok_or_else(|| IncludeLoaderError::not_found(path))
     }
 }
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl super::loader::AsyncIncludeLoader for MemoryIncludeLoader {
+    async fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        IncludeLoader::resolve(self, path)
+    }
+}
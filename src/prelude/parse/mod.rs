@@ -1,15 +1,22 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Range;
 use std::rc::Rc;
-use xmlparser::{StrSpan, Token, Tokenizer};
+use std::sync::Arc;
+use xmlparser::{ElementEnd, StrSpan, Token, Tokenizer};
 
 use self::loader::IncludeLoaderError;
 
+pub mod cached_loader;
 #[cfg(feature = "http-loader-base")]
 pub mod http_loader;
 pub mod loader;
 #[cfg(feature = "local-loader")]
 pub mod local_loader;
 pub mod memory_loader;
+pub mod multi_loader;
 pub mod noop_loader;
+#[cfg(feature = "async")]
+pub(crate) mod prefetch;
 
 #[macro_export]
 macro_rules! parse_attribute {
@@ -19,7 +26,10 @@ macro_rules! parse_attribute {
             name: xmlparser::StrSpan<'a>,
             value: xmlparser::StrSpan<'a>,
         ) -> Result<(), Error> {
-            self.attributes.insert(name.to_string(), value.to_string());
+            self.attributes.insert(
+                name.to_string(),
+                $crate::prelude::parse::attribute_value(value),
+            );
             Ok(())
         }
     };
@@ -34,7 +44,7 @@ macro_rules! parse_child {
             tokenizer: &mut xmlparser::Tokenizer<'a>,
         ) -> Result<(), Error> {
             self.children
-                .push($child_parser::parse(tag, tokenizer, self.opts.clone())?);
+                .push($child_parser::parse(tag, tokenizer, self.ctx.clone())?);
             Ok(())
         }
     };
@@ -62,43 +72,360 @@ macro_rules! parse_text {
     };
 }
 
+/// Location of a token in a template.
+///
+/// The line and the column are computed once the error reaches the template
+/// it comes from, so they stay at `0` when a component is parsed on its own.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the beginning of the token.
+    pub start: usize,
+    /// Byte offset of the end of the token.
+    pub end: usize,
+    /// Line of the beginning of the token, starting at 1.
+    pub line: usize,
+    /// Column of the beginning of the token, in characters, starting at 1.
+    pub column: usize,
+    /// Path of the included template containing the token, `None` for the root template.
+    pub include_path: Option<String>,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end,
+            ..Default::default()
+        }
+    }
+
+    /// Builds the span of a text, without its surrounding whitespaces.
+    pub(crate) fn trimmed(value: StrSpan<'_>) -> Self {
+        let text = value.as_str();
+        let start = value.start() + (text.len() - text.trim_start().len());
+        let end = value.end() - (text.len() - text.trim_end().len());
+        Self::new(start, end.max(start))
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.line > 0
+    }
+
+    fn from_text_pos(source: &str, row: usize, col: usize) -> Self {
+        let line_start = source
+            .split_inclusive('\n')
+            .take(row.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+        let start = source[line_start..]
+            .char_indices()
+            .nth(col.saturating_sub(1))
+            .map(|(index, _)| line_start + index)
+            .unwrap_or(source.len());
+        Self::new(start, start)
+    }
+
+    fn resolve(&mut self, source: &str, include_path: Option<&str>) {
+        if self.is_resolved() {
+            return;
+        }
+        self.start = self.start.min(source.len());
+        self.end = self.end.clamp(self.start, source.len());
+        let before = &source[..self.start];
+        self.line = before.matches('\n').count() + 1;
+        self.column = before
+            .rsplit('\n')
+            .next()
+            .map(|line| line.chars().count())
+            .unwrap_or_default()
+            + 1;
+        self.include_path = include_path.map(String::from);
+    }
+}
+
+impl<'a> From<StrSpan<'a>> for Span {
+    fn from(value: StrSpan<'a>) -> Self {
+        Self::new(value.start(), value.end())
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.is_resolved() {
+            return write!(f, "position {}", self.start);
+        }
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if let Some(ref path) = self.include_path {
+            write!(f, " of {path:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors of the parser, each of them located with a [`Span`].
+///
+/// New variants may be added in a minor version, a `match` on it needs a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("unexpected attribute at position {0}")]
-    UnexpectedAttribute(usize),
-    #[error("unexpected element at position {0}")]
-    UnexpectedElement(usize),
-    #[error("unexpected comment at position {0}")]
-    UnexpectedComment(usize),
-    #[error("unexpected text at position {0}")]
-    UnexpectedText(usize),
-    #[error("missing attribute {0}")]
-    MissingAttribute(&'static str),
-    #[error("invalid element: {0}")]
-    InvalidElement(String),
-    #[error("invalid format")]
-    InvalidFormat,
+    #[error("unexpected attribute at {0}")]
+    UnexpectedAttribute(Span),
+    #[error("unexpected element at {0}")]
+    UnexpectedElement(Span),
+    #[error("unexpected comment at {0}")]
+    UnexpectedComment(Span),
+    #[error("unexpected text at {0}")]
+    UnexpectedText(Span),
+    #[error("missing attribute {0} at {1}")]
+    MissingAttribute(&'static str, Span),
+    #[error("invalid element at {1}: {0}")]
+    InvalidElement(String, Span),
+    #[error("invalid format at {0}")]
+    InvalidFormat(Span),
+    /// The template ended before the element was closed.
+    #[error("unexpected end of template at {0}")]
+    EndOfStream(Span),
     /// The input string should be smaller than 4GiB.
-    #[error("size limit reached")]
-    SizeLimit,
+    #[error("size limit reached at {0}")]
+    SizeLimit(Span),
     /// Errors detected by the `xmlparser` crate.
-    #[error("unable to load included template")]
-    ParserError(#[from] xmlparser::Error),
+    #[error("unable to parse template at {1}")]
+    ParserError(#[source] xmlparser::Error, Span),
     /// The Mjml document must have at least one element.
-    #[error("no root node found")]
-    NoRootNode,
-    #[error("unable to load included template")]
-    IncludeLoaderError(#[from] IncludeLoaderError),
+    #[error("no root node found at {0}")]
+    NoRootNode(Span),
+    #[error("unable to load included template at {1}")]
+    IncludeLoaderError(#[source] IncludeLoaderError, Span),
+    /// The includes are looping or going beyond the limits defined in the
+    /// [`ParserOptions`]. The chain lists the paths of the includes leading
+    /// to the failing one, which comes last.
+    #[error("{0} at {2}, include chain: {}", .1.join(" > "))]
+    IncludeLimit(IncludeLimitReason, Vec<String>, Span),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IncludeLimitReason {
+    /// The template is including itself, directly or through other includes.
+    Cycle,
+    /// The includes are nested deeper than the given maximum depth.
+    Depth(usize),
+    /// The template contains more includes than the given maximum.
+    Count(usize),
+}
+
+impl std::fmt::Display for IncludeLimitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle => f.write_str("include cycle detected"),
+            Self::Depth(max) => write!(f, "maximum include depth of {max} reached"),
+            Self::Count(max) => write!(f, "maximum number of {max} includes reached"),
+        }
+    }
+}
+
+impl Error {
+    pub fn span(&self) -> &Span {
+        match self {
+            Self::UnexpectedAttribute(span)
+            | Self::UnexpectedElement(span)
+            | Self::UnexpectedComment(span)
+            | Self::UnexpectedText(span)
+            | Self::MissingAttribute(_, span)
+            | Self::InvalidElement(_, span)
+            | Self::InvalidFormat(span)
+            | Self::EndOfStream(span)
+            | Self::SizeLimit(span)
+            | Self::ParserError(_, span)
+            | Self::NoRootNode(span)
+            | Self::IncludeLoaderError(_, span)
+            | Self::IncludeLimit(_, _, span) => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Self::UnexpectedAttribute(span)
+            | Self::UnexpectedElement(span)
+            | Self::UnexpectedComment(span)
+            | Self::UnexpectedText(span)
+            | Self::MissingAttribute(_, span)
+            | Self::InvalidElement(_, span)
+            | Self::InvalidFormat(span)
+            | Self::EndOfStream(span)
+            | Self::SizeLimit(span)
+            | Self::ParserError(_, span)
+            | Self::NoRootNode(span)
+            | Self::IncludeLoaderError(_, span)
+            | Self::IncludeLimit(_, _, span) => span,
+        }
+    }
+
+    /// Whether the parser can skip the invalid token and continue in lenient mode.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(
+            self,
+            Self::InvalidFormat(_)
+                | Self::EndOfStream(_)
+                | Self::SizeLimit(_)
+                | Self::ParserError(..)
+                | Self::NoRootNode(_)
+                | Self::IncludeLimit(..)
+        )
+    }
+
+    pub(crate) fn with_span<S: Into<Span>>(mut self, span: S) -> Self {
+        *self.span_mut() = span.into();
+        self
+    }
+
+    /// Computes the line and the column of the error, if not already done, using the
+    /// template it comes from.
+    pub(crate) fn resolve(mut self, source: &str, include_path: Option<&str>) -> Self {
+        if !self.span().is_resolved() {
+            match self {
+                Self::EndOfStream(ref mut span) => {
+                    *span = Span::new(source.len(), source.len());
+                }
+                Self::ParserError(ref inner, ref mut span) => {
+                    let pos = inner.pos();
+                    *span = Span::from_text_pos(source, pos.row as usize, pos.col as usize);
+                }
+                _ => {}
+            }
+        }
+        self.span_mut().resolve(source, include_path);
+        self
+    }
+
+    /// Renders an excerpt of the template around the error, pointing at the
+    /// offending token.
+    ///
+    /// The `source` must be the content of the template the error comes from, which means
+    /// the content of the [`include_path`](Span::include_path) when the error occurred in
+    /// an included template. Given another source, the excerpt is meaningless and the
+    /// caret falls back to a single character.
+    ///
+    /// ```rust
+    /// let template = "<mjml>\n  <mj-foo />\n</mjml>";
+    /// let error = mrml::parse(template).unwrap_err();
+    /// assert_eq!(
+    ///     error.code_frame(template),
+    ///     "  1 | <mjml>\n> 2 |   <mj-foo />\n    |    ^^^^^^\n  3 | </mjml>\n",
+    /// );
+    /// ```
+    pub fn code_frame(&self, source: &str) -> String {
+        const CONTEXT: usize = 2;
+
+        let mut span = self.span().clone();
+        if !span.is_resolved() {
+            span.resolve(source, None);
+        }
+        let lines = source.split('\n').collect::<Vec<_>>();
+        let first = span.line.saturating_sub(CONTEXT).max(1);
+        let last = (span.line + CONTEXT).min(lines.len());
+        let width = last.to_string().len();
+
+        let mut buffer = String::default();
+        for (index, line) in lines.iter().enumerate().take(last).skip(first - 1) {
+            let number = index + 1;
+            let line = line.trim_end_matches('\r');
+            let marker = if number == span.line { '>' } else { ' ' };
+            buffer.push_str(&format!("{marker} {number:>width$} | {line}\n"));
+            if number == span.line {
+                let length = source
+                    .get(span.range())
+                    .and_then(|item| item.split('\n').next())
+                    .map(|item| item.chars().count())
+                    .unwrap_or_default()
+                    .max(1);
+                buffer.push_str(&format!(
+                    "  {:width$} | {}{}\n",
+                    "",
+                    " ".repeat(span.column.saturating_sub(1)),
+                    "^".repeat(length)
+                ));
+            }
+        }
+        buffer
+    }
+}
+
+pub(crate) fn token_span(token: &Token<'_>) -> Span {
+    match token {
+        Token::Declaration { span, .. }
+        | Token::ProcessingInstruction { span, .. }
+        | Token::Comment { span, .. }
+        | Token::DtdStart { span, .. }
+        | Token::EmptyDtd { span, .. }
+        | Token::EntityDeclaration { span, .. }
+        | Token::DtdEnd { span }
+        | Token::ElementStart { span, .. }
+        | Token::Attribute { span, .. }
+        | Token::ElementEnd { span, .. }
+        | Token::Cdata { span, .. } => Span::from(*span),
+        Token::Text { text } => Span::from(*text),
+    }
 }
 
 pub(crate) fn next_token<'a>(tokenizer: &mut Tokenizer<'a>) -> Result<Token<'a>, Error> {
-    if let Some(token) = tokenizer.next() {
-        Ok(token?)
-    } else {
-        Err(Error::InvalidFormat)
+    match tokenizer.next() {
+        Some(Ok(token)) => Ok(token),
+        Some(Err(err)) => Err(Error::ParserError(err, Span::default())),
+        None => Err(Error::EndOfStream(Span::default())),
+    }
+}
+
+/// Consumes the tokens of an element, children included, once its start has been read.
+fn skip_element(tag: StrSpan<'_>, tokenizer: &mut Tokenizer<'_>) -> Result<(), Error> {
+    // the attributes, up to the end of the start tag
+    loop {
+        match next_token(tokenizer)? {
+            Token::ElementEnd {
+                end: ElementEnd::Empty,
+                ..
+            } => return Ok(()),
+            Token::ElementEnd {
+                end: ElementEnd::Open,
+                ..
+            } if crate::node::parse::is_void_element(tag.as_str()) => return Ok(()),
+            Token::ElementEnd {
+                end: ElementEnd::Open,
+                ..
+            } => break,
+            _ => {}
+        }
+    }
+    // the children, up to the matching closing tag
+    let mut current = tag;
+    let mut depth = 1;
+    loop {
+        match next_token(tokenizer)? {
+            Token::ElementStart { local, .. } => current = local,
+            Token::ElementEnd {
+                end: ElementEnd::Open,
+                ..
+            } if !crate::node::parse::is_void_element(current.as_str()) => depth += 1,
+            Token::ElementEnd {
+                end: ElementEnd::Close(..),
+                ..
+            } => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return Ok(());
+        }
     }
 }
 
+/// Value of an attribute, with the predefined xml entities decoded.
+pub(crate) fn attribute_value(value: StrSpan<'_>) -> String {
+    crate::helper::escape::unescape(value.as_str()).into_owned()
+}
+
 pub(crate) fn is_element_start<'a>(token: &'a Token<'a>) -> Option<&'a StrSpan<'a>> {
     match token {
         Token::ElementStart { local, .. } => Some(local),
@@ -118,30 +445,46 @@ pub trait Parser: Sized {
     }
 
     fn parse_attribute<'a>(&mut self, name: StrSpan<'a>, _value: StrSpan<'a>) -> Result<(), Error> {
-        Err(Error::UnexpectedAttribute(name.start()))
+        Err(Error::UnexpectedAttribute(name.into()))
     }
 
-    fn parse_children(&mut self, tokenizer: &mut Tokenizer<'_>) -> Result<(), Error> {
+    fn parse_children(
+        &mut self,
+        tokenizer: &mut Tokenizer<'_>,
+        ctx: &ParserContext,
+    ) -> Result<(), Error> {
         loop {
             let token = next_token(tokenizer)?;
             match token {
                 Token::Comment { text, span: _ } => {
-                    self.parse_child_comment(text)?;
+                    if let Err(err) = self.parse_child_comment(text) {
+                        ctx.recover(err)?;
+                    }
                 }
                 Token::Text { text } => {
                     if !text.trim().is_empty() {
-                        self.parse_child_text(text)?;
+                        if let Err(err) = self.parse_child_text(text) {
+                            ctx.recover(err)?;
+                        }
                     }
                 }
                 Token::ElementStart {
                     prefix: _,
                     local,
                     span: _,
-                } => {
-                    self.parse_child_element(local, tokenizer)?;
-                }
+                } => match self.parse_child_element(local, tokenizer) {
+                    // the element has been rejected before reading any of its tokens
+                    Err(Error::UnexpectedElement(span))
+                        if ctx.is_lenient() && span == Span::from(local) =>
+                    {
+                        skip_element(local, tokenizer)?;
+                        ctx.recover(Error::UnexpectedElement(span))?;
+                    }
+                    Err(err) => ctx.recover(err)?,
+                    Ok(_) => {}
+                },
                 Token::ElementEnd { end: _, span: _ } => return Ok(()),
-                _ => return Err(Error::InvalidFormat),
+                other => return Err(Error::InvalidFormat(token_span(&other))),
             };
         }
     }
@@ -151,18 +494,18 @@ pub trait Parser: Sized {
         tag: StrSpan<'a>,
         _tokenizer: &mut Tokenizer<'a>,
     ) -> Result<(), Error> {
-        Err(Error::UnexpectedElement(tag.start()))
+        Err(Error::UnexpectedElement(tag.into()))
     }
 
     fn parse_child_comment(&mut self, value: StrSpan) -> Result<(), Error> {
-        Err(Error::UnexpectedComment(value.start()))
+        Err(Error::UnexpectedComment(value.into()))
     }
 
     fn parse_child_text(&mut self, value: StrSpan) -> Result<(), Error> {
-        Err(Error::UnexpectedText(value.start()))
+        Err(Error::UnexpectedText(Span::trimmed(value)))
     }
 
-    fn parse(mut self, tokenizer: &mut Tokenizer) -> Result<Self, Error> {
+    fn parse(mut self, tokenizer: &mut Tokenizer, ctx: &ParserContext) -> Result<Self, Error> {
         loop {
             let token = next_token(tokenizer)?;
             match token {
@@ -172,25 +515,27 @@ pub trait Parser: Sized {
                     value,
                     span: _,
                 } => {
-                    self.parse_attribute(local, value)?;
+                    if let Err(err) = self.parse_attribute(local, value) {
+                        ctx.recover(err)?;
+                    }
                 }
-                Token::ElementEnd { end, span: _ } => {
+                Token::ElementEnd { end, span } => {
                     match end {
                         xmlparser::ElementEnd::Empty => {
                             return Ok(self);
                         }
                         xmlparser::ElementEnd::Open => {
                             if !self.should_ignore_children() {
-                                self.parse_children(tokenizer)?;
+                                self.parse_children(tokenizer, ctx)?;
                             }
                             return Ok(self);
                         }
                         // unexpected
-                        _ => return Err(Error::InvalidFormat),
+                        _ => return Err(Error::InvalidFormat(span.into())),
                     }
                 }
-                _ => {
-                    return Err(Error::InvalidFormat);
+                other => {
+                    return Err(Error::InvalidFormat(token_span(&other)));
                 }
             };
         }
@@ -201,13 +546,41 @@ pub trait Parsable: Sized {
     fn parse<'a>(
         tag: StrSpan<'a>,
         tokenizer: &mut Tokenizer<'a>,
-        opts: Rc<ParserOptions>,
+        ctx: Rc<ParserContext>,
     ) -> Result<Self, Error>;
 }
 
+pub const DEFAULT_MAX_INCLUDE_DEPTH: usize = 16;
+pub const DEFAULT_MAX_INCLUDE_COUNT: usize = 256;
+
+/// Settings of the parser, shared by the [`ParserOptions`] and the `AsyncParserOptions`.
+#[derive(Clone, Copy, Debug)]
+pub struct ParserSettings {
+    /// When enabled, the unexpected attributes, elements, comments and texts are skipped
+    /// and reported as diagnostics instead of stopping the parsing.
+    pub lenient: bool,
+    /// Maximum number of nested `mj-include`, an include in the root template being at depth 1.
+    pub max_include_depth: usize,
+    /// Maximum number of `mj-include` resolved while parsing a template, nested ones included.
+    pub max_include_count: usize,
+}
+
+impl Default for ParserSettings {
+    fn default() -> Self {
+        Self {
+            lenient: false,
+            max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+            max_include_count: DEFAULT_MAX_INCLUDE_COUNT,
+        }
+    }
+}
+
+/// Options of the parser. They are given in an [`Arc`], the same options and their
+/// loader can be used to parse several templates, from several threads.
 #[derive(Debug)]
 pub struct ParserOptions {
     pub include_loader: Box<dyn loader::IncludeLoader>,
+    pub settings: ParserSettings,
 }
 
 #[allow(clippy::box_default)]
@@ -215,6 +588,495 @@ impl Default for ParserOptions {
     fn default() -> Self {
         Self {
             include_loader: Box::new(noop_loader::NoopIncludeLoader::default()),
+            settings: ParserSettings::default(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+/// Same as the [`ParserOptions`] but with an [`AsyncIncludeLoader`](loader::AsyncIncludeLoader),
+/// used by [`parse_with_options_async`](crate::parse_with_options_async).
+///
+/// The included templates are loaded before the template gets parsed, which is then done
+/// the same way as with [`ParserOptions`].
+#[derive(Debug)]
+pub struct AsyncParserOptions {
+    pub include_loader: Box<dyn loader::AsyncIncludeLoader>,
+    pub settings: ParserSettings,
+}
+
+#[cfg(feature = "async")]
+#[allow(clippy::box_default)]
+impl Default for AsyncParserOptions {
+    fn default() -> Self {
+        Self {
+            include_loader: Box::new(noop_loader::NoopIncludeLoader),
+            settings: ParserSettings::default(),
+        }
+    }
+}
+
+/// State shared by the parsers while reading a template and its includes.
+#[derive(Debug, Default)]
+pub struct ParserContext {
+    pub options: Arc<ParserOptions>,
+    diagnostics: RefCell<Vec<Error>>,
+    /// Paths of the includes being parsed, from the outermost to the innermost.
+    include_chain: RefCell<Vec<String>>,
+    include_count: Cell<usize>,
+}
+
+impl ParserContext {
+    pub fn new(options: Arc<ParserOptions>) -> Self {
+        Self {
+            options,
+            diagnostics: RefCell::default(),
+            include_chain: RefCell::default(),
+            include_count: Cell::default(),
+        }
+    }
+
+    /// Paths of the includes being parsed, from the outermost to the innermost.
+    pub fn include_chain(&self) -> Vec<String> {
+        self.include_chain.borrow().clone()
+    }
+
+    /// Builds the path of an include, relative to the include being parsed, if any.
+    pub(crate) fn include_path(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        let chain = self.include_chain.borrow();
+        self.options
+            .include_loader
+            .resolve_path(path, chain.last().map(String::as_str))
+    }
+
+    /// Registers the include until the returned guard is dropped, making sure it
+    /// doesn't loop and stays in the limits defined in the options.
+    pub(crate) fn enter_include(&self, path: &str, span: &Span) -> Result<IncludeGuard<'_>, Error> {
+        let mut chain = self.include_chain.borrow_mut();
+        let reason = if chain.iter().any(|item| item == path) {
+            Some(IncludeLimitReason::Cycle)
+        } else if chain.len() >= self.options.settings.max_include_depth {
+            Some(IncludeLimitReason::Depth(
+                self.options.settings.max_include_depth,
+            ))
+        } else if self.include_count.get() >= self.options.settings.max_include_count {
+            Some(IncludeLimitReason::Count(
+                self.options.settings.max_include_count,
+            ))
+        } else {
+            None
+        };
+        chain.push(path.to_string());
+        if let Some(reason) = reason {
+            let error = Error::IncludeLimit(reason, chain.clone(), span.clone());
+            chain.pop();
+            return Err(error);
+        }
+        self.include_count.set(self.include_count.get() + 1);
+        Ok(IncludeGuard { ctx: self })
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.options.settings.lenient
+    }
+
+    /// Keeps track of the error when it can be skipped in lenient mode, returns it otherwise.
+    pub(crate) fn recover(&self, error: Error) -> Result<(), Error> {
+        if self.is_lenient() && error.is_recoverable() {
+            self.diagnostics.borrow_mut().push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Number of includes parsed so far.
+    pub(crate) fn include_count(&self) -> usize {
+        self.include_count.get()
+    }
+
+    pub(crate) fn diagnostics_count(&self) -> usize {
+        self.diagnostics.borrow().len()
+    }
+
+    /// Computes the location of the diagnostics collected since `from` using the template
+    /// they come from.
+    pub(crate) fn resolve_diagnostics(
+        &self,
+        from: usize,
+        source: &str,
+        include_path: Option<&str>,
+    ) {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        let resolved = diagnostics
+            .drain(from..)
+            .map(|item| item.resolve(source, include_path))
+            .collect::<Vec<_>>();
+        diagnostics.extend(resolved);
+    }
+
+    pub(crate) fn take_diagnostics(&self) -> Vec<Error> {
+        self.diagnostics.take()
+    }
+}
+
+/// Removes the include from the chain of the context once it has been parsed.
+pub(crate) struct IncludeGuard<'a> {
+    ctx: &'a ParserContext,
+}
+
+impl<'a> Drop for IncludeGuard<'a> {
+    fn drop(&mut self) {
+        self.ctx.include_chain.borrow_mut().pop();
+    }
+}
+
+/// Result of a parsing in lenient mode.
+#[derive(Debug)]
+pub struct ParseOutput<T> {
+    pub element: T,
+    /// The errors that have been skipped while parsing the template.
+    pub diagnostics: Vec<Error>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::memory_loader::MemoryIncludeLoader;
+    use super::{Error, IncludeLimitReason, ParserOptions, ParserSettings, Span};
+    use std::sync::Arc;
+
+    #[test]
+    fn should_locate_unexpected_attribute() {
+        let template = "<mjml>\n  <mj-head>\n    <mj-font name=\"Foo\" href=\"#\" foo=\"bar\" />\n  </mj-head>\n</mjml>";
+        let err = crate::mjml::Mjml::parse(template).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedAttribute(_)));
+        assert_eq!(
+            err.span(),
+            &Span {
+                start: 52,
+                end: 55,
+                line: 3,
+                column: 34,
+                include_path: None,
+            }
+        );
+        assert_eq!(err.to_string(), "unexpected attribute at line 3, column 34");
+    }
+
+    #[test]
+    fn should_locate_missing_attribute() {
+        let template = "<mjml>\n  <mj-head>\n    <mj-font href=\"#\" />\n  </mj-head>\n</mjml>";
+        let err = crate::mjml::Mjml::parse(template).unwrap_err();
+        assert!(matches!(err, Error::MissingAttribute("name", _)));
+        assert_eq!((err.span().line, err.span().column), (3, 6));
+    }
+
+    #[test]
+    fn should_locate_end_of_stream() {
+        let template = "<mjml>\n  <mj-body>";
+        let err = crate::mjml::Mjml::parse(template).unwrap_err();
+        assert!(matches!(err, Error::EndOfStream(_)));
+        assert_eq!(err.span().range(), 18..18);
+        assert_eq!((err.span().line, err.span().column), (2, 12));
+    }
+
+    #[test]
+    fn should_locate_parser_error() {
+        let template = "<mjml>\n  <mj-body>\n    <mj-text foo=bar>\n";
+        let err = crate::mjml::Mjml::parse(template).unwrap_err();
+        assert!(matches!(err, Error::ParserError(..)));
+        assert_eq!((err.span().line, err.span().column), (3, 13));
+    }
+
+    #[test]
+    fn should_locate_errors_in_included_template() {
+        let resolver = MemoryIncludeLoader::from(vec![(
+            "partial.mjml",
+            "<mj-carousel>\n  <mj-carousel-image src=\"#\" />\n  Hello\n</mj-carousel>",
+        )]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            ..Default::default()
+        });
+        let template =
+            "<mjml>\n  <mj-body>\n    <mj-include path=\"partial.mjml\" />\n  </mj-body>\n</mjml>";
+        let err = crate::mjml::Mjml::parse_with_options(template, opts).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedText(_)));
+        assert_eq!(err.span().include_path.as_deref(), Some("partial.mjml"));
+        assert_eq!((err.span().line, err.span().column), (3, 3));
+        assert_eq!(
+            err.to_string(),
+            "unexpected text at line 3, column 3 of \"partial.mjml\""
+        );
+    }
+
+    #[test]
+    fn should_render_code_frame_of_included_template() {
+        let partial = "<mj-carousel>\n  <mj-carousel-image src=\"https://example.com/first.png\" />\n  <mj-carousel-image src=\"https://example.com/second.png\" />\n  Hello\n</mj-carousel>";
+        let resolver = MemoryIncludeLoader::from(vec![("partial.mjml", partial)]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            ..Default::default()
+        });
+        let template = "<mjml><mj-body><mj-include path=\"partial.mjml\" /></mj-body></mjml>";
+        let err = crate::mjml::Mjml::parse_with_options(template, opts).unwrap_err();
+        assert!(err.span().start > template.len());
+        assert_eq!(
+            err.code_frame(partial),
+            "  2 |   <mj-carousel-image src=\"https://example.com/first.png\" />\n  3 |   <mj-carousel-image src=\"https://example.com/second.png\" />\n> 4 |   Hello\n    |   ^^^^^\n  5 | </mj-carousel>\n"
+        );
+        // the root template doesn't contain the span, it should not panic
+        assert_eq!(err.code_frame(template), "");
+    }
+
+    #[test]
+    fn should_locate_include_loader_error() {
+        let template =
+            "<mjml>\n  <mj-body>\n    <mj-include path=\"partial.mjml\" />\n  </mj-body>\n</mjml>";
+        let err = crate::mjml::Mjml::parse(template).unwrap_err();
+        assert!(matches!(err, Error::IncludeLoaderError(..)));
+        assert_eq!(err.span().include_path, None);
+        assert_eq!((err.span().line, err.span().column), (3, 6));
+    }
+
+    #[test]
+    fn should_render_code_frame_with_context() {
+        let template = "<mjml>\n  <mj-body>\n    <mj-section>\n      <mj-column>\n        <mj-text foo>\n      </mj-column>\n    </mj-section>\n  </mj-body>\n</mjml>";
+        let err = crate::mjml::Mjml::parse(template).unwrap_err();
+        assert_eq!(
+            err.code_frame(template),
+            "  3 |     <mj-section>\n  4 |       <mj-column>\n> 5 |         <mj-text foo>\n    |                 ^\n  6 |       </mj-column>\n  7 |     </mj-section>\n"
+        );
+    }
+
+    fn parse_lenient(template: &str) -> super::ParseOutput<crate::mjml::Mjml> {
+        let opts = Arc::new(ParserOptions {
+            settings: ParserSettings {
+                lenient: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        crate::mjml::Mjml::parse_with_diagnostics(template, opts).unwrap()
+    }
+
+    #[test]
+    fn should_fail_on_unexpected_element_by_default() {
+        let template =
+            "<mjml><mj-head><mj-text>Hello</mj-text><mj-title>Hi</mj-title></mj-head></mjml>";
+        let opts = Arc::new(ParserOptions::default());
+        let err = crate::mjml::Mjml::parse_with_diagnostics(template, opts).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedElement(_)));
+    }
+
+    #[test]
+    fn should_skip_unexpected_tokens_in_lenient_mode() {
+        let template = r##"<mjml foo="bar">
+  <mj-head>
+    <mj-text>Hello <b>World<br></b><img src="#"></mj-text>
+    <mj-title>Hi</mj-title>
+    <mj-font name="Foo" href="#" unknown="true" />
+  </mj-head>
+  <mj-body>
+    <mj-carousel>
+      Some text
+      <mj-carousel-image src="#" />
+    </mj-carousel>
+  </mj-body>
+</mjml>"##;
+        let output = parse_lenient(template);
+        let head = output.element.head().unwrap();
+        assert_eq!(head.title().unwrap().content(), "Hi");
+        assert_eq!(head.children.len(), 2);
+        let body = output.element.body().unwrap();
+        let carousel = body.children.first().unwrap().as_mj_carousel().unwrap();
+        assert_eq!(carousel.children.len(), 1);
+        let found = output
+            .diagnostics
+            .iter()
+            .map(|item| (item.to_string(), item.span().line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("unexpected attribute at line 1, column 7".to_string(), 1),
+                ("unexpected element at line 3, column 6".to_string(), 3),
+                ("unexpected attribute at line 5, column 34".to_string(), 5),
+                ("unexpected text at line 9, column 7".to_string(), 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_skip_unexpected_elements_with_attributes_in_lenient_mode() {
+        let template = r#"<mjml><mj-head><mj-text class="x">Hello</mj-text><mj-spacer height="10px" /><mj-title>Hi</mj-title></mj-head><mj-body><mj-text>Body</mj-text></mj-body></mjml>"#;
+        let output = parse_lenient(template);
+        assert_eq!(output.element.get_title().as_deref(), Some("Hi"));
+        let body = output.element.body().unwrap();
+        assert_eq!(body.children.len(), 1);
+        let found = output
+            .diagnostics
+            .iter()
+            .map(|item| (item.to_string(), item.span().column))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("unexpected element at line 1, column 17".to_string(), 17),
+                ("unexpected element at line 1, column 51".to_string(), 51),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_diagnostics_from_included_templates() {
+        let resolver = MemoryIncludeLoader::from(vec![(
+            "partial.mjml",
+            "<mj-carousel>\n  Hello\n  <mj-carousel-image src=\"#\" />\n</mj-carousel>",
+        )]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            settings: ParserSettings {
+                lenient: true,
+                ..Default::default()
+            },
+        });
+        let template = "<mjml>\n  <mj-body>\n    <mj-include path=\"partial.mjml\" />\n    <mj-include path=\"missing.mjml\" />\n    <mj-text>Hi</mj-text>\n  </mj-body>\n</mjml>";
+        let output = crate::mjml::Mjml::parse_with_diagnostics(template, opts).unwrap();
+        let body = output.element.body().unwrap();
+        assert_eq!(body.children.len(), 2);
+        assert_eq!(output.diagnostics.len(), 2);
+        assert!(matches!(output.diagnostics[0], Error::UnexpectedText(_)));
+        assert_eq!(
+            output.diagnostics[0].span().include_path.as_deref(),
+            Some("partial.mjml")
+        );
+        assert_eq!(output.diagnostics[0].span().line, 2);
+        assert!(matches!(
+            output.diagnostics[1],
+            Error::IncludeLoaderError(..)
+        ));
+        assert_eq!(output.diagnostics[1].span().include_path, None);
+        assert_eq!(output.diagnostics[1].span().line, 4);
+    }
+
+    #[test]
+    fn should_not_recover_from_invalid_xml() {
+        let opts = Arc::new(ParserOptions {
+            settings: ParserSettings {
+                lenient: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let err =
+            crate::mjml::Mjml::parse_with_diagnostics("<mjml><mj-body></mjml>", opts).unwrap_err();
+        assert!(!err.is_recoverable());
+    }
+
+    #[test]
+    fn should_detect_include_cycle() {
+        let resolver = MemoryIncludeLoader::from(vec![
+            (
+                "a.mjml",
+                "<mj-section><mj-include path=\"b.mjml\" /></mj-section>",
+            ),
+            (
+                "b.mjml",
+                "<mj-wrapper>\n  <mj-include path=\"a.mjml\" />\n</mj-wrapper>",
+            ),
+        ]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            ..Default::default()
+        });
+        let template = "<mjml><mj-body><mj-include path=\"a.mjml\" /></mj-body></mjml>";
+        let err = crate::mjml::Mjml::parse_with_options(template, opts).unwrap_err();
+        match err {
+            Error::IncludeLimit(IncludeLimitReason::Cycle, ref chain, ref span) => {
+                assert_eq!(chain, &["a.mjml", "b.mjml", "a.mjml"]);
+                assert_eq!(span.include_path.as_deref(), Some("b.mjml"));
+                assert_eq!(span.line, 2);
+            }
+            other => panic!("expected an include cycle, got {:?}", other),
+        }
+        assert_eq!(
+            err.to_string(),
+            "include cycle detected at line 2, column 4 of \"b.mjml\", include chain: a.mjml > b.mjml > a.mjml"
+        );
+    }
+
+    #[test]
+    fn should_detect_include_cycle_in_lenient_mode() {
+        let resolver = MemoryIncludeLoader::from(vec![(
+            "self.mjml",
+            "<mj-wrapper><mj-include path=\"self.mjml\" /></mj-wrapper>",
+        )]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            settings: ParserSettings {
+                lenient: true,
+                ..Default::default()
+            },
+        });
+        let template = "<mjml><mj-body><mj-include path=\"self.mjml\" /></mj-body></mjml>";
+        let err = crate::mjml::Mjml::parse_with_diagnostics(template, opts).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::IncludeLimit(IncludeLimitReason::Cycle, ..)
+        ));
+    }
+
+    #[test]
+    fn should_limit_include_depth() {
+        let resolver = MemoryIncludeLoader::from(vec![
+            (
+                "1.mjml",
+                "<mj-wrapper><mj-include path=\"2.mjml\" /></mj-wrapper>",
+            ),
+            (
+                "2.mjml",
+                "<mj-wrapper><mj-include path=\"3.mjml\" /></mj-wrapper>",
+            ),
+            ("3.mjml", "<mj-text>Hello</mj-text>"),
+        ]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            settings: ParserSettings {
+                max_include_depth: 2,
+                ..Default::default()
+            },
+        });
+        let template = "<mjml><mj-body><mj-include path=\"1.mjml\" /></mj-body></mjml>";
+        let err = crate::mjml::Mjml::parse_with_options(template, opts).unwrap_err();
+        match err {
+            Error::IncludeLimit(IncludeLimitReason::Depth(2), chain, _) => {
+                assert_eq!(chain, vec!["1.mjml", "2.mjml", "3.mjml"]);
+            }
+            other => panic!("expected an include depth error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_limit_include_count() {
+        let resolver = MemoryIncludeLoader::from(vec![("text.mjml", "<mj-text>Hello</mj-text>")]);
+        let opts = Arc::new(ParserOptions {
+            include_loader: Box::new(resolver),
+            settings: ParserSettings {
+                max_include_count: 2,
+                ..Default::default()
+            },
+        });
+        let template = "<mjml><mj-body><mj-include path=\"text.mjml\" /><mj-include path=\"text.mjml\" /></mj-body></mjml>";
+        assert!(crate::mjml::Mjml::parse_with_options(template, opts.clone()).is_ok());
+        let template = "<mjml><mj-body><mj-include path=\"text.mjml\" /><mj-include path=\"text.mjml\" /><mj-include path=\"text.mjml\" /></mj-body></mjml>";
+        let err = crate::mjml::Mjml::parse_with_options(template, opts).unwrap_err();
+        match err {
+            Error::IncludeLimit(IncludeLimitReason::Count(2), chain, span) => {
+                assert_eq!(chain, vec!["text.mjml"]);
+                assert_eq!(span.column, 79);
+            }
+            other => panic!("expected an include count error, got {:?}", other),
         }
     }
 }
@@ -0,0 +1,318 @@
+//! Module containing a loader that dispatches the includes to other loaders depending on their path.
+
+use super::loader::{IncludeLoaderError, ParsedInclude};
+use crate::prelude::parse::loader::IncludeLoader;
+use std::io::ErrorKind;
+
+#[derive(Debug)]
+enum MultiIncludeLoaderFilter {
+    Any,
+    StartsWith(String),
+    /// Contains the scheme followed by `:`, which is removed from the path given to the loader.
+    Scheme(String),
+}
+
+impl MultiIncludeLoaderFilter {
+    /// Returns the path to give to the loader, if the filter matches.
+    fn matches<'a>(&self, path: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Any => Some(path),
+            Self::StartsWith(prefix) => Some(path).filter(|path| path.starts_with(prefix.as_str())),
+            Self::Scheme(prefix) => path.strip_prefix(prefix.as_str()),
+        }
+    }
+
+    /// Turns a path given by the loader back into a path of the template.
+    fn restore(&self, path: String) -> String {
+        match self {
+            Self::Scheme(prefix) => format!("{prefix}{path}"),
+            _ => path,
+        }
+    }
+
+    fn is_any(&self) -> bool {
+        matches!(self, Self::Any)
+    }
+}
+
+#[derive(Debug)]
+struct MultiIncludeLoaderItem {
+    filter: MultiIncludeLoaderFilter,
+    loader: Box<dyn IncludeLoader>,
+}
+
+#[derive(Debug, Default)]
+/// This struct is an [`IncludeLoader`](crate::prelude::parse::loader::IncludeLoader) that
+/// dispatches the includes to other loaders depending on the beginning of their path.
+///
+/// The loaders are tried in the order they've been added. When several loaders match
+/// a path and a loader can't find the template, the next one is tried, which allows
+/// to define fallbacks.
+///
+/// # Example
+/// ```rust
+/// use mrml::prelude::parse::memory_loader::MemoryIncludeLoader;
+/// use mrml::prelude::parse::multi_loader::MultiIncludeLoader;
+/// use mrml::prelude::parse::noop_loader::NoopIncludeLoader;
+/// use mrml::prelude::parse::ParserOptions;
+/// use std::sync::Arc;
+///
+/// let builtin = MemoryIncludeLoader::from(vec![("footer.mjml", "<mj-text>Built-in footer</mj-text>")]);
+/// let tenant = MemoryIncludeLoader::from(vec![("file:///footer.mjml", "<mj-text>Tenant footer</mj-text>")]);
+/// let resolver = MultiIncludeLoader::default()
+///     // the path given to the loader is `footer.mjml` for `memory:footer.mjml`
+///     .with_scheme("memory", Box::new(builtin))
+///     // tries the tenant templates first, then falls back on the next loader
+///     .with_starts_with("file:///", Box::new(tenant))
+///     .with_starts_with("file:///", Box::new(NoopIncludeLoader));
+/// let opts = ParserOptions {
+///     include_loader: Box::new(resolver),
+///     ..Default::default()
+/// };
+/// let template = r#"<mjml>
+///   <mj-body>
+///     <mj-include path="memory:footer.mjml" />
+///     <mj-include path="file:///footer.mjml" />
+///   </mj-body>
+/// </mjml>"#;
+/// match mrml::parse_with_options(template, Arc::new(opts)) {
+///     Ok(_) => println!("Success!"),
+///     Err(err) => eprintln!("Couldn't parse template: {err:?}"),
+/// }
+/// ```
+pub struct MultiIncludeLoader(Vec<MultiIncludeLoaderItem>);
+
+impl MultiIncludeLoader {
+    fn add_item(&mut self, filter: MultiIncludeLoaderFilter, loader: Box<dyn IncludeLoader>) {
+        self.0.push(MultiIncludeLoaderItem { filter, loader });
+    }
+
+    fn with_item(
+        mut self,
+        filter: MultiIncludeLoaderFilter,
+        loader: Box<dyn IncludeLoader>,
+    ) -> Self {
+        self.add_item(filter, loader);
+        self
+    }
+
+    /// Uses the loader for any path.
+    pub fn with_any(self, loader: Box<dyn IncludeLoader>) -> Self {
+        self.with_item(MultiIncludeLoaderFilter::Any, loader)
+    }
+
+    /// Uses the loader for the paths starting with the prefix, like `file:///` or `https://`.
+    /// The loader receives the whole path.
+    pub fn with_starts_with<S: ToString>(self, prefix: S, loader: Box<dyn IncludeLoader>) -> Self {
+        self.with_item(
+            MultiIncludeLoaderFilter::StartsWith(prefix.to_string()),
+            loader,
+        )
+    }
+
+    /// Uses the loader for the paths with the given scheme, like `memory` for `memory:footer.mjml`.
+    /// The loader receives the path without the scheme, `footer.mjml` in that case.
+    pub fn with_scheme<S: AsRef<str>>(self, scheme: S, loader: Box<dyn IncludeLoader>) -> Self {
+        self.with_item(
+            MultiIncludeLoaderFilter::Scheme(format!("{}:", scheme.as_ref())),
+            loader,
+        )
+    }
+
+    pub fn add_any(&mut self, loader: Box<dyn IncludeLoader>) {
+        self.add_item(MultiIncludeLoaderFilter::Any, loader);
+    }
+
+    pub fn add_starts_with<S: ToString>(&mut self, prefix: S, loader: Box<dyn IncludeLoader>) {
+        self.add_item(
+            MultiIncludeLoaderFilter::StartsWith(prefix.to_string()),
+            loader,
+        );
+    }
+
+    pub fn add_scheme<S: AsRef<str>>(&mut self, scheme: S, loader: Box<dyn IncludeLoader>) {
+        self.add_item(
+            MultiIncludeLoaderFilter::Scheme(format!("{}:", scheme.as_ref())),
+            loader,
+        );
+    }
+}
+
+impl IncludeLoader for MultiIncludeLoader {
+    /// A path matching one of the prefixes is kept as is, a relative one is resolved
+    /// by the loader of the including template.
+    fn resolve_path(&self, path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+        let is_absolute = self
+            .0
+            .iter()
+            .any(|item| !item.filter.is_any() && item.filter.matches(path).is_some());
+        let parent = match parent {
+            Some(parent) if !is_absolute => parent,
+            _ => return Ok(path.to_string()),
+        };
+        for item in self.0.iter() {
+            if let Some(inner) = item.filter.matches(parent) {
+                return item
+                    .loader
+                    .resolve_path(path, Some(inner))
+                    .map(|resolved| item.filter.restore(resolved));
+            }
+        }
+        Ok(path.to_string())
+    }
+
+    fn version(&self, path: &str) -> Option<String> {
+        self.0.iter().find_map(|item| {
+            item.filter
+                .matches(path)
+                .and_then(|inner| item.loader.version(inner))
+        })
+    }
+
+    fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        let mut last_error = None;
+        for item in self.0.iter() {
+            if let Some(inner) = item.filter.matches(path) {
+                match item.loader.resolve(inner) {
+                    Ok(content) => return Ok(content),
+                    Err(err) if err.reason == ErrorKind::NotFound => {
+                        last_error = Some(err);
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            IncludeLoaderError::not_found(path).with_message("no loader found for this path")
+        }))
+    }
+
+    fn parsed(&self, path: &str, content: &str) -> Option<ParsedInclude> {
+        self.0.iter().find_map(|item| {
+            item.filter
+                .matches(path)
+                .and_then(|inner| item.loader.parsed(inner, content))
+        })
+    }
+
+    /// The element is given to every matching loader, the ones that didn't load this
+    /// content ignore it.
+    fn store_parsed(&self, path: &str, content: &str, parsed: ParsedInclude) {
+        for item in self.0.iter() {
+            if let Some(inner) = item.filter.matches(path) {
+                item.loader.store_parsed(inner, content, parsed.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiIncludeLoader;
+    use crate::prelude::parse::loader::{IncludeLoader, IncludeLoaderError};
+    use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
+    use crate::prelude::parse::noop_loader::NoopIncludeLoader;
+    use std::io::ErrorKind;
+
+    #[derive(Debug)]
+    struct FailingIncludeLoader;
+
+    impl IncludeLoader for FailingIncludeLoader {
+        fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+            Err(IncludeLoaderError::new(path, ErrorKind::PermissionDenied))
+        }
+    }
+
+    #[test]
+    fn should_dispatch_on_prefix() {
+        let loader = MultiIncludeLoader::default()
+            .with_starts_with(
+                "file:///",
+                Box::new(MemoryIncludeLoader::from(vec![("file:///a.mjml", "file")])),
+            )
+            .with_starts_with(
+                "https://",
+                Box::new(MemoryIncludeLoader::from(vec![(
+                    "https://host/a.mjml",
+                    "http",
+                )])),
+            );
+        assert_eq!(loader.resolve("file:///a.mjml").unwrap(), "file");
+        assert_eq!(loader.resolve("https://host/a.mjml").unwrap(), "http");
+        let err = loader.resolve("a.mjml").unwrap_err();
+        assert_eq!(err.reason, ErrorKind::NotFound);
+        assert_eq!(err.message, Some("no loader found for this path"));
+    }
+
+    #[test]
+    fn should_strip_scheme() {
+        let mut loader = MultiIncludeLoader::default();
+        loader.add_scheme(
+            "memory",
+            Box::new(MemoryIncludeLoader::from(vec![("a.mjml", "memory")])),
+        );
+        loader.add_any(Box::new(NoopIncludeLoader));
+        assert_eq!(loader.resolve("memory:a.mjml").unwrap(), "memory");
+        assert_eq!(
+            loader.resolve("a.mjml").unwrap_err().reason,
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn should_fallback_when_not_found() {
+        let loader = MultiIncludeLoader::default()
+            .with_any(Box::new(MemoryIncludeLoader::from(vec![(
+                "a.mjml", "first",
+            )])))
+            .with_any(Box::new(MemoryIncludeLoader::from(vec![
+                ("a.mjml", "second"),
+                ("b.mjml", "second"),
+            ])));
+        assert_eq!(loader.resolve("a.mjml").unwrap(), "first");
+        assert_eq!(loader.resolve("b.mjml").unwrap(), "second");
+        assert_eq!(
+            loader.resolve("c.mjml").unwrap_err().reason,
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn should_not_fallback_on_other_errors() {
+        let loader = MultiIncludeLoader::default()
+            .with_any(Box::new(FailingIncludeLoader))
+            .with_any(Box::new(MemoryIncludeLoader::from(vec![("a.mjml", "a")])));
+        assert_eq!(
+            loader.resolve("a.mjml").unwrap_err().reason,
+            ErrorKind::PermissionDenied
+        );
+    }
+
+    #[cfg(feature = "local-loader")]
+    #[test]
+    fn should_resolve_relative_path_with_parent_loader() {
+        use crate::prelude::parse::local_loader::LocalIncludeLoader;
+
+        let loader = MultiIncludeLoader::default()
+            .with_scheme("memory", Box::new(MemoryIncludeLoader::default()))
+            .with_starts_with("file:///", Box::new(LocalIncludeLoader::default()));
+        assert_eq!(
+            loader
+                .resolve_path("./b.mjml", Some("file:///partials/a.mjml"))
+                .unwrap(),
+            "file:///partials/b.mjml"
+        );
+        assert_eq!(
+            loader
+                .resolve_path("memory:b.mjml", Some("file:///partials/a.mjml"))
+                .unwrap(),
+            "memory:b.mjml"
+        );
+        assert_eq!(
+            loader
+                .resolve_path("./b.mjml", Some("memory:a.mjml"))
+                .unwrap(),
+            "memory:./b.mjml"
+        );
+    }
+}
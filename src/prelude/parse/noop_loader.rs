@@ -10,7 +10,7 @@ use super::loader::IncludeLoaderError;
 ///
 /// # Example
 /// ```rust
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use mrml::mj_include::body::MjIncludeBodyKind;
 /// use mrml::prelude::parse::noop_loader::NoopIncludeLoader;
 /// use mrml::prelude::parse::ParserOptions;
@@ -18,13 +18,14 @@ use super::loader::IncludeLoaderError;
 /// // This could be done using `ParserOptions::default()`.
 /// let opts = ParserOptions {
 ///     include_loader: Box::new(NoopIncludeLoader::default()),
+///     ..Default::default()
 /// };
 /// let json = r#"<mjml>
 ///   <mj-body>
 ///     <mj-include path="basic.mjml" />
 ///   </mj-body>
 /// </mjml>"#;
-/// match mrml::parse_with_options(json, Rc::new(opts)) {
+/// match mrml::parse_with_options(json, Arc::new(opts)) {
 ///     Ok(_) => eprintln!("This should not happen!"),
 ///     Err(err) => println!("Couldn't parse template: {err:?}"),
 /// }
@@ -36,3 +37,11 @@ impl IncludeLoader for NoopIncludeLoader {
         Err(IncludeLoaderError::not_found(path))
     }
 }
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl super::loader::AsyncIncludeLoader for NoopIncludeLoader {
+    async fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        Err(IncludeLoaderError::not_found(path))
+    }
+}
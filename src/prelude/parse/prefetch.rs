@@ -0,0 +1,266 @@
+//! Loading of the included templates with an [`AsyncIncludeLoader`](super::loader::AsyncIncludeLoader),
+//! before parsing the template.
+
+use super::loader::{IncludeLoader, IncludeLoaderError};
+use super::{AsyncParserOptions, ParserOptions};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use xmlparser::{Token, Tokenizer};
+
+#[derive(Debug, PartialEq, Eq)]
+struct IncludeItem {
+    path: String,
+    /// Whether the included template can contain other includes.
+    nested: bool,
+}
+
+/// Lists the `mj-include` elements of the template. When the template is invalid,
+/// the includes found before the error are returned, the parser reporting the error.
+fn find_includes(source: &str) -> Vec<IncludeItem> {
+    let mut result = Vec::new();
+    let mut current: Option<(Option<String>, bool)> = None;
+    for token in Tokenizer::from(source) {
+        match token {
+            Ok(Token::ElementStart { local, .. }) => {
+                current = if local.as_str() == crate::mj_include::NAME {
+                    Some((None, true))
+                } else {
+                    None
+                };
+            }
+            Ok(Token::Attribute { local, value, .. }) => {
+                if let Some((path, nested)) = current.as_mut() {
+                    match local.as_str() {
+                        "path" => *path = Some(super::attribute_value(value)),
+                        // css files are included as is
+                        "type" => *nested = value.as_str() != "css",
+                        _ => {}
+                    }
+                }
+            }
+            Ok(Token::ElementEnd { .. }) => {
+                if let Some((Some(path), nested)) = current.take() {
+                    result.push(IncludeItem { path, nested });
+                }
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    result
+}
+
+/// [`IncludeLoader`] giving the templates loaded ahead by the
+/// [`AsyncIncludeLoader`](super::loader::AsyncIncludeLoader) to the parser.
+#[derive(Debug)]
+pub(crate) struct PrefetchedIncludeLoader {
+    options: Arc<AsyncParserOptions>,
+    templates: Mutex<HashMap<String, Result<String, IncludeLoaderError>>>,
+}
+
+impl PrefetchedIncludeLoader {
+    /// Loads the templates included by the source, and the ones they include.
+    ///
+    /// Loading stops at the limits defined in the options and a template included several times
+    /// is only loaded once. The errors are kept so that the parser reports them where they happen.
+    pub(crate) async fn load(source: &str, options: Arc<AsyncParserOptions>) -> Self {
+        let mut templates: HashMap<String, Result<String, IncludeLoaderError>> = HashMap::new();
+        // templates to look for includes, with their path and their depth
+        let mut queue: Vec<(String, Option<String>, usize)> = vec![(source.to_string(), None, 0)];
+        while let Some((content, parent, depth)) = queue.pop() {
+            if depth >= options.settings.max_include_depth {
+                continue;
+            }
+            for item in find_includes(&content) {
+                if templates.len() >= options.settings.max_include_count {
+                    break;
+                }
+                let path = match options
+                    .include_loader
+                    .resolve_path(&item.path, parent.as_deref())
+                {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                };
+                if templates.contains_key(&path) {
+                    continue;
+                }
+                let result = options.include_loader.resolve(&path).await;
+                if let (true, Ok(included)) = (item.nested, &result) {
+                    queue.push((included.clone(), Some(path.clone()), depth + 1));
+                }
+                templates.insert(path, result);
+            }
+        }
+        Self {
+            options,
+            templates: Mutex::new(templates),
+        }
+    }
+
+    pub(crate) fn into_options(self) -> ParserOptions {
+        ParserOptions {
+            settings: self.options.settings,
+            include_loader: Box::new(self),
+        }
+    }
+}
+
+impl IncludeLoader for PrefetchedIncludeLoader {
+    fn resolve_path(&self, path: &str, parent: Option<&str>) -> Result<String, IncludeLoaderError> {
+        self.options.include_loader.resolve_path(path, parent)
+    }
+
+    fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        let mut templates = self.templates.lock().unwrap_or_else(|err| err.into_inner());
+        match templates.get_mut(path) {
+            Some(Ok(content)) => Ok(content.clone()),
+            // the cause can't be cloned, it's given with the first error
+            Some(Err(err)) => Err(IncludeLoaderError {
+                path: err.path.clone(),
+                reason: err.reason,
+                message: err.message,
+                cause: err.cause.take(),
+            }),
+            None => Err(IncludeLoaderError::not_found(path)
+                .with_message("the template has not been loaded")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_includes, IncludeItem};
+    use crate::prelude::parse::loader::{AsyncIncludeLoader, IncludeLoaderError};
+    use crate::prelude::parse::memory_loader::MemoryIncludeLoader;
+    use crate::prelude::parse::{AsyncParserOptions, Error, IncludeLimitReason};
+    use std::io::ErrorKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct CountingIncludeLoader {
+        inner: MemoryIncludeLoader,
+        loads: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncIncludeLoader for CountingIncludeLoader {
+        async fn resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+            self.loads.fetch_add(1, Ordering::SeqCst);
+            AsyncIncludeLoader::resolve(&self.inner, path).await
+        }
+    }
+
+    #[test]
+    fn should_find_includes() {
+        let template = r#"<mjml>
+  <mj-head>
+    <mj-include path="style.css" type="css" css-inline="inline" />
+  </mj-head>
+  <mj-body>
+    <mj-include path="header.mjml"></mj-include>
+    <mj-text>Hello</mj-text>
+    <mj-include />
+  </mj-body>
+</mjml>"#;
+        assert_eq!(
+            find_includes(template),
+            vec![
+                IncludeItem {
+                    path: "style.css".into(),
+                    nested: false
+                },
+                IncludeItem {
+                    path: "header.mjml".into(),
+                    nested: true
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_parse_nested_includes() {
+        let loader = MemoryIncludeLoader::from(vec![
+            (
+                "header.mjml",
+                r#"<mj-section><mj-column><mj-include path="title.mjml" /></mj-column></mj-section>"#,
+            ),
+            ("title.mjml", "<mj-text>Title</mj-text>"),
+            ("style.css", ".title { color: red; }"),
+        ]);
+        let loads = Arc::new(AtomicUsize::default());
+        let opts = Arc::new(AsyncParserOptions {
+            include_loader: Box::new(CountingIncludeLoader {
+                inner: loader,
+                loads: loads.clone(),
+            }),
+            ..Default::default()
+        });
+        let template = r#"<mjml>
+  <mj-head>
+    <mj-include path="style.css" type="css" />
+  </mj-head>
+  <mj-body>
+    <mj-include path="header.mjml" />
+    <mj-include path="header.mjml" />
+  </mj-body>
+</mjml>"#;
+        let root = crate::parse_with_options_async(template, opts)
+            .await
+            .unwrap();
+        assert_eq!(root.body().unwrap().children.len(), 2);
+        assert_eq!(root.head().unwrap().children.len(), 1);
+        // each template is only loaded once
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn should_be_usable_in_multithreaded_runtime() {
+        fn assert_send<T: Send>(_: &T) {}
+        let future = crate::parse_with_options_async(
+            "<mjml><mj-body /></mjml>",
+            Arc::new(AsyncParserOptions::default()),
+        );
+        assert_send(&future);
+    }
+
+    #[tokio::test]
+    async fn should_report_loader_errors() {
+        let opts = Arc::new(AsyncParserOptions::default());
+        let template = r#"<mjml><mj-body><mj-include path="missing.mjml" /></mj-body></mjml>"#;
+        match crate::parse_with_options_async(template, opts).await {
+            Err(Error::IncludeLoaderError(origin, span)) => {
+                assert_eq!(origin.path, "missing.mjml");
+                assert_eq!(origin.reason, ErrorKind::NotFound);
+                assert_eq!(span.start, 16);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_stop_on_cycles() {
+        let loader = MemoryIncludeLoader::from(vec![
+            (
+                "a.mjml",
+                r#"<mj-wrapper><mj-include path="b.mjml" /></mj-wrapper>"#,
+            ),
+            (
+                "b.mjml",
+                r#"<mj-wrapper><mj-include path="a.mjml" /></mj-wrapper>"#,
+            ),
+        ]);
+        let opts = Arc::new(AsyncParserOptions {
+            include_loader: Box::new(loader),
+            ..Default::default()
+        });
+        let template = r#"<mjml><mj-body><mj-include path="a.mjml" /></mj-body></mjml>"#;
+        match crate::parse_with_options_async(template, opts).await {
+            Err(Error::IncludeLimit(IncludeLimitReason::Cycle, chain, _)) => {
+                assert_eq!(chain, vec!["a.mjml", "b.mjml", "a.mjml"]);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,603 @@
+//! Formatting of a template with a configurable style.
+//!
+//! Unlike [`Print::pretty_print`], the formatter limits the width of the lines by wrapping
+//! the attributes, and lets you choose the indentation, the style of the empty elements, the
+//! order of the attributes and where to put blank lines. Formatting a formatted template
+//! doesn't change it, which makes it usable to check the style of templates in a review.
+//!
+//! The content of the ending tags, like `mj-text` or `mj-raw`, is indented one level deeper than
+//! its element when it spans several lines, the closing tag being put on its own line. It's kept
+//! as is when it has a `pre` or `textarea` element, which whitespaces are meaningful.
+//!
+//! ```rust
+//! use mrml::prelude::print::format::{format_source, AttributeOrder, FormatOptions};
+//!
+//! let template = r##"<mjml><mj-body><mj-section padding="0" background-color="#fff"><mj-column><mj-text>Hello</mj-text></mj-column></mj-section></mj-body></mjml>"##;
+//! let options = FormatOptions {
+//!     attribute_order: AttributeOrder::Preserve,
+//!     ..Default::default()
+//! };
+//! assert_eq!(
+//!     format_source(template, &options).unwrap(),
+//!     r##"<mjml>
+//!   <mj-body>
+//!     <mj-section padding="0" background-color="#fff">
+//!       <mj-column>
+//!         <mj-text>Hello</mj-text>
+//!       </mj-column>
+//!     </mj-section>
+//!   </mj-body>
+//! </mjml>
+//! "##
+//! );
+//! ```
+
+use super::lossless::{build, SourceAttribute, SourceElement, SourceNode};
+use super::Print;
+use crate::helper::escape::escape_attribute;
+use crate::prelude::parse::Error;
+
+/// Columns taken by a tab when measuring the width of a line.
+const TAB_WIDTH: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndentStyle {
+    Spaces(usize),
+    Tabs,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Style of the elements without children. The void html elements, like `<br />`,
+/// are always self closing.
+pub enum SelfClosing {
+    /// `<mj-spacer />`
+    Always,
+    /// `<mj-spacer></mj-spacer>`
+    Never,
+    /// Keeps the style of the source.
+    Preserve,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeOrder {
+    Alphabetical,
+    /// The attributes identifying the component first, like the `src` of an `mj-image`,
+    /// then `mj-class` and `css-class`, then the attributes grouped by purpose: dimensions,
+    /// spacing, borders, backgrounds and typography.
+    Canonical,
+    /// Keeps the order of the source.
+    Preserve,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlankLines {
+    /// Removes all the blank lines.
+    Remove,
+    /// Keeps one blank line where the source has at least one.
+    Preserve,
+    /// Puts one blank line between `mj-head` and `mj-body` and between the sections of the
+    /// body, a comment sticking to the element it precedes.
+    BetweenSections,
+}
+
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    /// Width of the lines above which the attributes are wrapped, one per line.
+    pub max_width: usize,
+    pub indent: IndentStyle,
+    pub self_closing: SelfClosing,
+    pub attribute_order: AttributeOrder,
+    pub blank_lines: BlankLines,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            indent: IndentStyle::Spaces(2),
+            self_closing: SelfClosing::Always,
+            attribute_order: AttributeOrder::Alphabetical,
+            blank_lines: BlankLines::BetweenSections,
+        }
+    }
+}
+
+/// Elements which content isn't formatted, only indented.
+fn is_ending_element(tag: &str) -> bool {
+    matches!(
+        tag,
+        crate::mj_accordion_text::NAME
+            | crate::mj_accordion_title::NAME
+            | crate::mj_button::NAME
+            | crate::mj_navbar_link::NAME
+            | crate::mj_preview::NAME
+            | crate::mj_raw::NAME
+            | crate::mj_social_element::NAME
+            | crate::mj_style::NAME
+            | crate::mj_table::NAME
+            | crate::mj_text::NAME
+            | crate::mj_title::NAME
+    )
+}
+
+/// Contents which whitespaces are meaningful and can't be indented.
+fn is_preformatted(content: &str) -> bool {
+    let content = content.to_ascii_lowercase();
+    content.contains("<pre") || content.contains("<textarea")
+}
+
+/// Elements which children are separated by a blank line with [`BlankLines::BetweenSections`].
+fn is_sections_container(tag: &str) -> bool {
+    matches!(
+        tag,
+        crate::mjml::NAME | crate::mj_body::NAME | crate::mj_wrapper::NAME
+    )
+}
+
+/// Attributes identifying a component, put first with [`AttributeOrder::Canonical`].
+fn leading_attributes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        crate::mjml::NAME => &["lang", "dir", "owa"],
+        crate::mj_attributes_class::NAME => &["name"],
+        crate::mj_breakpoint::NAME => &["width"],
+        crate::mj_button::NAME | crate::mj_navbar_link::NAME => &["href"],
+        crate::mj_carousel_image::NAME | crate::mj_image::NAME => &["src", "alt", "href"],
+        crate::mj_font::NAME => &["name", "href"],
+        crate::mj_include::NAME => &["path", "type"],
+        crate::mj_social_element::NAME => &["name", "href", "src"],
+        crate::mj_style::NAME => &["inline"],
+        _ => &[],
+    }
+}
+
+fn attribute_group(name: &str) -> usize {
+    const GROUPS: [&[&str]; 5] = [
+        &["mj-class", "css-class"],
+        &[
+            "width",
+            "height",
+            "align",
+            "vertical-align",
+            "direction",
+            "full-width",
+        ],
+        &["padding", "inner-padding", "margin"],
+        &["border", "inner-border"],
+        &["background", "inner-background", "container-background"],
+    ];
+    let matches = |prefix: &&str| {
+        name.strip_prefix(prefix)
+            .map(|rest| rest.is_empty() || rest.starts_with('-'))
+            .unwrap_or(false)
+    };
+    if let Some(index) = GROUPS.iter().position(|group| group.iter().any(matches)) {
+        index
+    } else if ["font", "color", "line-height", "letter-spacing", "text"]
+        .iter()
+        .any(matches)
+    {
+        GROUPS.len()
+    } else {
+        GROUPS.len() + 1
+    }
+}
+
+fn sort_attributes<'a>(
+    tag: &str,
+    attributes: &'a [SourceAttribute],
+    order: AttributeOrder,
+) -> Vec<&'a SourceAttribute> {
+    let mut result: Vec<&SourceAttribute> = attributes.iter().collect();
+    match order {
+        AttributeOrder::Alphabetical => result.sort_by(|a, b| a.name.cmp(&b.name)),
+        AttributeOrder::Canonical => {
+            let leading = leading_attributes(tag);
+            result.sort_by_cached_key(|item| {
+                match leading.iter().position(|name| *name == item.name) {
+                    Some(index) => (0, index, String::default()),
+                    None => (1, attribute_group(&item.name), item.name.clone()),
+                }
+            });
+        }
+        AttributeOrder::Preserve => {}
+    }
+    result
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    options: &'a FormatOptions,
+    buffer: String,
+}
+
+impl<'a> Formatter<'a> {
+    fn indent(&self, level: usize) -> String {
+        match self.options.indent {
+            IndentStyle::Spaces(size) => " ".repeat(level * size),
+            IndentStyle::Tabs => "\t".repeat(level),
+        }
+    }
+
+    fn width(&self, level: usize, value: &str) -> usize {
+        let indent = match self.options.indent {
+            IndentStyle::Spaces(size) => level * size,
+            IndentStyle::Tabs => level * TAB_WIDTH,
+        };
+        indent + value.chars().count()
+    }
+
+    fn line(&mut self, level: usize, value: &str) {
+        self.buffer.push_str(&self.indent(level));
+        self.buffer.push_str(value);
+        self.buffer.push('\n');
+    }
+
+    fn is_blank(&self, node: &SourceNode) -> bool {
+        matches!(node, SourceNode::Text(span) if self.source[span.clone()].matches('\n').count() > 1)
+    }
+
+    /// Formats the children, one per line, the whitespaces between them excepted.
+    fn children(&mut self, parent: Option<&str>, nodes: &[SourceNode], level: usize) {
+        let between_sections = self.options.blank_lines == BlankLines::BetweenSections
+            && parent.map(is_sections_container).unwrap_or(false);
+        let mut previous: Option<&SourceNode> = None;
+        let mut blank = false;
+        for node in nodes {
+            if node.is_whitespace(self.source) {
+                blank |= self.options.blank_lines == BlankLines::Preserve && self.is_blank(node);
+                continue;
+            }
+            let section = between_sections
+                && matches!(previous, Some(SourceNode::Element(_)))
+                && matches!(node, SourceNode::Element(_) | SourceNode::Comment(_));
+            if (previous.is_some() && blank) || section {
+                self.buffer.push('\n');
+            }
+            self.node(node, level);
+            previous = Some(node);
+            blank = false;
+        }
+    }
+
+    fn node(&mut self, node: &SourceNode, level: usize) {
+        match node {
+            SourceNode::Element(element) => self.element(element, level),
+            SourceNode::Text(span) => {
+                let text = self.source[span.clone()].trim();
+                self.line(level, text);
+            }
+            SourceNode::Comment(span) | SourceNode::Other(span) => {
+                let value = &self.source[span.clone()];
+                self.line(level, value);
+            }
+        }
+    }
+
+    /// Writes the content of an ending element, following its opening tag, and its closing tag.
+    fn content(&mut self, name: &str, content: &str, level: usize) {
+        let (first_line, rest) = match content.split_once('\n') {
+            Some(value) if !is_preformatted(content) => value,
+            _ => {
+                self.buffer.push_str(content);
+                self.buffer.push_str(&format!("</{name}>\n"));
+                return;
+            }
+        };
+        self.buffer.push_str(first_line.trim_end());
+        self.buffer.push('\n');
+        let mut lines: Vec<&str> = rest.split('\n').collect();
+        // the whitespaces before the closing tag are replaced by the indentation
+        if lines
+            .last()
+            .map(|line| line.trim().is_empty())
+            .unwrap_or(false)
+        {
+            lines.pop();
+        }
+        let margin = |line: &str| line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+        let common = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| margin(line))
+            .min()
+            .unwrap_or_default();
+        for line in lines {
+            if line.trim().is_empty() {
+                self.buffer.push('\n');
+            } else {
+                self.line(level + 1, line[common..].trim_end());
+            }
+        }
+        self.line(level, &format!("</{name}>"));
+    }
+
+    fn element(&mut self, element: &SourceElement, level: usize) {
+        let name = element.name.as_str();
+        let attributes = sort_attributes(name, &element.attributes, self.options.attribute_order)
+            .into_iter()
+            .map(|item| format!("{}=\"{}\"", item.name, escape_attribute(&item.value)))
+            .collect::<Vec<_>>();
+        let ending = is_ending_element(name);
+        let content = match element.close {
+            Some(ref close) if ending => &self.source[element.open_end.end..close.start],
+            _ => "",
+        };
+        let empty = if ending {
+            content.is_empty()
+        } else {
+            element
+                .children
+                .iter()
+                .all(|child| child.is_whitespace(self.source))
+        };
+        let void = crate::node::parse::is_void_element(name);
+        let self_closing = void
+            || (empty
+                && match self.options.self_closing {
+                    SelfClosing::Always => true,
+                    SelfClosing::Never => false,
+                    SelfClosing::Preserve => element.close.is_none(),
+                });
+        let end = if self_closing { " />" } else { ">" };
+        // what follows the opening tag on its last line
+        let tail = if self_closing {
+            String::default()
+        } else if ending || empty {
+            match content.split_once('\n') {
+                Some((first_line, _)) => first_line.trim_end().to_string(),
+                None => format!("{content}</{name}>"),
+            }
+        } else {
+            String::default()
+        };
+
+        let inline = attributes
+            .iter()
+            .fold(format!("<{name}"), |res, item| res + " " + item)
+            + end;
+        if attributes.is_empty()
+            || self.width(level, &inline) + tail.len() <= self.options.max_width
+        {
+            self.buffer.push_str(&self.indent(level));
+            self.buffer.push_str(&inline);
+        } else {
+            self.line(level, &format!("<{name}"));
+            for item in attributes.iter() {
+                self.line(level + 1, item);
+            }
+            self.buffer.push_str(&self.indent(level));
+            self.buffer.push_str(end.trim_start());
+        }
+
+        if self_closing {
+            self.buffer.push('\n');
+        } else if ending || empty {
+            self.content(name, content, level);
+        } else {
+            self.buffer.push('\n');
+            self.children(Some(name), &element.children, level + 1);
+            self.line(level, &format!("</{name}>"));
+        }
+    }
+}
+
+/// Formats a raw template. Only the syntax of the template is checked, not its structure.
+///
+/// ```rust
+/// use mrml::prelude::print::format::{format_source, FormatOptions, IndentStyle};
+///
+/// let options = FormatOptions {
+///     indent: IndentStyle::Tabs,
+///     ..Default::default()
+/// };
+/// let result = format_source("<mjml><mj-body></mj-body></mjml>", &options).unwrap();
+/// assert_eq!(result, "<mjml>\n\t<mj-body />\n</mjml>\n");
+/// ```
+pub fn format_source(source: &str, options: &FormatOptions) -> Result<String, Error> {
+    let nodes = build(source).map_err(|err| err.resolve(source, None))?;
+    let mut formatter = Formatter {
+        source,
+        options,
+        buffer: String::with_capacity(source.len()),
+    };
+    formatter.children(None, &nodes, 0);
+    Ok(formatter.buffer)
+}
+
+/// Formats an element. The attributes being printed in alphabetical order,
+/// [`AttributeOrder::Preserve`] keeps them in that order.
+///
+/// The element is printed densely then formatted, which fails when the print isn't
+/// well formed, like with an invalid `mj-raw` content.
+pub fn format<P: Print>(element: &P, options: &FormatOptions) -> Result<String, Error> {
+    format_source(&element.dense_print(), options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mjml::Mjml;
+
+    const TEMPLATE: &str = r#"<mjml>
+  <mj-head><mj-title>Hello</mj-title><mj-breakpoint width="400px"></mj-breakpoint></mj-head>
+  <mj-body>
+    <!-- header -->
+    <mj-section padding="0" css-class="header" background-color="red" text-align="left" border="none">
+      <mj-column><mj-image alt="logo" width="100px" src="logo.png" /></mj-column>
+    </mj-section>
+
+
+    <mj-section><mj-column><mj-text color="red">
+      Hello <b>World</b>
+    </mj-text><mj-spacer /></mj-column></mj-section>
+  </mj-body>
+</mjml>"#;
+
+    #[test]
+    fn should_format_with_default_options() {
+        let result = format_source(TEMPLATE, &FormatOptions::default()).unwrap();
+        similar_asserts::assert_eq!(
+            result,
+            r#"<mjml>
+  <mj-head>
+    <mj-title>Hello</mj-title>
+    <mj-breakpoint width="400px" />
+  </mj-head>
+
+  <mj-body>
+    <!-- header -->
+    <mj-section
+      background-color="red"
+      border="none"
+      css-class="header"
+      padding="0"
+      text-align="left"
+    >
+      <mj-column>
+        <mj-image alt="logo" src="logo.png" width="100px" />
+      </mj-column>
+    </mj-section>
+
+    <mj-section>
+      <mj-column>
+        <mj-text color="red">
+          Hello <b>World</b>
+        </mj-text>
+        <mj-spacer />
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>
+"#
+        );
+    }
+
+    #[test]
+    fn should_wrap_attributes() {
+        let options = FormatOptions {
+            max_width: 60,
+            attribute_order: AttributeOrder::Canonical,
+            self_closing: SelfClosing::Never,
+            blank_lines: BlankLines::Preserve,
+            indent: IndentStyle::Spaces(4),
+        };
+        let result = format_source(TEMPLATE, &options).unwrap();
+        similar_asserts::assert_eq!(
+            result,
+            r#"<mjml>
+    <mj-head>
+        <mj-title>Hello</mj-title>
+        <mj-breakpoint width="400px"></mj-breakpoint>
+    </mj-head>
+    <mj-body>
+        <!-- header -->
+        <mj-section
+            css-class="header"
+            padding="0"
+            border="none"
+            background-color="red"
+            text-align="left"
+        >
+            <mj-column>
+                <mj-image
+                    src="logo.png"
+                    alt="logo"
+                    width="100px"
+                ></mj-image>
+            </mj-column>
+        </mj-section>
+
+        <mj-section>
+            <mj-column>
+                <mj-text color="red">
+                    Hello <b>World</b>
+                </mj-text>
+                <mj-spacer></mj-spacer>
+            </mj-column>
+        </mj-section>
+    </mj-body>
+</mjml>
+"#
+        );
+    }
+
+    #[test]
+    fn should_preserve_style() {
+        let options = FormatOptions {
+            indent: IndentStyle::Tabs,
+            attribute_order: AttributeOrder::Preserve,
+            self_closing: SelfClosing::Preserve,
+            blank_lines: BlankLines::Remove,
+            ..Default::default()
+        };
+        let result = format_source(
+            "<mjml><mj-body><mj-section padding=\"0\" border='none'/>\n\n<mj-wrapper></mj-wrapper></mj-body></mjml>",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            "<mjml>\n\t<mj-body>\n\t\t<mj-section padding=\"0\" border=\"none\" />\n\t\t<mj-wrapper></mj-wrapper>\n\t</mj-body>\n</mjml>\n"
+        );
+    }
+
+    #[test]
+    fn should_be_idempotent() {
+        let options = [
+            FormatOptions::default(),
+            FormatOptions {
+                max_width: 40,
+                indent: IndentStyle::Tabs,
+                self_closing: SelfClosing::Never,
+                attribute_order: AttributeOrder::Canonical,
+                blank_lines: BlankLines::Preserve,
+            },
+        ];
+        for entry in std::fs::read_dir("resources/compare/success").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().map(|ext| ext != "mjml").unwrap_or(true) {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            // the content of the ending elements is indented
+            let normalize = |value: String| value.split_whitespace().collect::<Vec<_>>().join(" ");
+            let expected = normalize(Mjml::parse(&source).unwrap().dense_print());
+            for options in options.iter() {
+                let first = format_source(&source, options).unwrap();
+                let second = format_source(&first, options).unwrap();
+                assert_eq!(first, second, "{:?}", path);
+                let formatted = Mjml::parse(&first).unwrap();
+                assert_eq!(normalize(formatted.dense_print()), expected, "{:?}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn should_indent_content() {
+        let result = format_source(
+            "<mjml><mj-body><mj-raw>\n\t\t<p>\n\t\t  Hello\n\n\t\t</p></mj-raw><mj-raw>\n  <pre>\n  Hello</pre>\n</mj-raw></mj-body></mjml>",
+            &FormatOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            "<mjml>\n  <mj-body>\n    <mj-raw>\n      <p>\n        Hello\n\n      </p>\n    </mj-raw>\n\n    <mj-raw>\n  <pre>\n  Hello</pre>\n</mj-raw>\n  </mj-body>\n</mjml>\n"
+        );
+    }
+
+    #[test]
+    fn should_format_element() {
+        let element =
+            Mjml::parse("<mjml><mj-body><mj-text>Hello</mj-text></mj-body></mjml>").unwrap();
+        assert_eq!(
+            format(&element, &FormatOptions::default()).unwrap(),
+            "<mjml>\n  <mj-body>\n    <mj-text>Hello</mj-text>\n  </mj-body>\n</mjml>\n"
+        );
+    }
+
+    #[test]
+    fn should_return_resolved_errors() {
+        let err = format_source("<mjml>\n  <mj-body =>", &FormatOptions::default()).unwrap_err();
+        assert_eq!(err.span().line, 2);
+    }
+}
@@ -0,0 +1,623 @@
+//! Printing of a template that keeps the formatting of its source.
+//!
+//! The [`Print`] implementations sort the attributes and normalise the whitespaces, which
+//! makes a noisy diff when reprinting a hand written template. The [`SourceMap`] tokenizes the
+//! source a second time, apart from the parser, to locate its elements. When printing, the
+//! dense print of the edited tree is matched with them, so that only what has been edited
+//! changes: the order and the quotes of the attributes, the whitespaces between the elements
+//! and the comments are kept from the source.
+//!
+//! ```rust
+//! use mrml::mj_body::MjBodyChild;
+//! use mrml::prelude::parse::ParserOptions;
+//! use std::sync::Arc;
+//!
+//! let template = r##"<mjml>
+//!   <mj-body>
+//!     <!-- header -->
+//!     <mj-section padding='0' background-color="#fff">
+//!       <mj-column><mj-text>Hello</mj-text></mj-column>
+//!     </mj-section>
+//!   </mj-body>
+//! </mjml>
+//! "##;
+//! let mut output = mrml::parse_lossless(template, Arc::new(ParserOptions::default())).unwrap();
+//! let body = output.element.children.body.as_mut().unwrap();
+//! if let Some(MjBodyChild::MjSection(section)) = body.children.get_mut(1) {
+//!     section.attributes.insert("padding".into(), "10px".into());
+//! }
+//! assert_eq!(output.print().unwrap(), template.replace("'0'", "'10px'"));
+//! ```
+
+use super::Print;
+use crate::helper::escape::{escape_attribute, unescape};
+use crate::prelude::parse::{Error, Span};
+use std::ops::Range;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+#[derive(Debug)]
+pub(super) struct SourceAttribute {
+    pub(super) name: String,
+    /// Value with the entities decoded.
+    pub(super) value: String,
+    pub(super) span: Range<usize>,
+}
+
+#[derive(Debug)]
+pub(super) struct SourceElement {
+    pub(super) name: String,
+    /// From the `<` to the end of the closing tag.
+    pub(super) span: Range<usize>,
+    /// End of the name of the element in the opening tag.
+    pub(super) name_end: usize,
+    pub(super) attributes: Vec<SourceAttribute>,
+    /// The `>` or `/>` ending the opening tag.
+    pub(super) open_end: Range<usize>,
+    pub(super) children: Vec<SourceNode>,
+    /// Closing tag, `None` when the element is self closing.
+    pub(super) close: Option<Range<usize>>,
+}
+
+impl SourceElement {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|item| item.name == name)
+            .map(|item| item.value.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub(super) enum SourceNode {
+    Element(SourceElement),
+    Text(Range<usize>),
+    Comment(Range<usize>),
+    /// Any other token, like a declaration or a cdata section, kept as is.
+    Other(Range<usize>),
+}
+
+impl SourceNode {
+    fn span(&self) -> Range<usize> {
+        match self {
+            Self::Element(elt) => elt.span.clone(),
+            Self::Text(span) | Self::Comment(span) | Self::Other(span) => span.clone(),
+        }
+    }
+
+    pub(super) fn is_whitespace(&self, source: &str) -> bool {
+        matches!(self, Self::Text(span) if source[span.clone()].trim().is_empty())
+    }
+
+    /// Whether both nodes can be compared, an element being only comparable
+    /// to an element with the same name.
+    fn is_comparable(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Element(left), Self::Element(right)) => left.name == right.name,
+            (Self::Text(_), Self::Text(_))
+            | (Self::Comment(_), Self::Comment(_))
+            | (Self::Other(_), Self::Other(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Builds the tree of the nodes of a template, without interpreting the elements.
+pub(super) fn build(source: &str) -> Result<Vec<SourceNode>, Error> {
+    let mut roots: Vec<SourceNode> = Vec::new();
+    let mut stack: Vec<SourceElement> = Vec::new();
+
+    fn push(roots: &mut Vec<SourceNode>, stack: &mut [SourceElement], node: SourceNode) {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+
+    fn close(roots: &mut Vec<SourceNode>, stack: &mut Vec<SourceElement>, end: usize) {
+        if let Some(mut element) = stack.pop() {
+            element.span.end = end;
+            push(roots, stack, SourceNode::Element(element));
+        }
+    }
+
+    for token in Tokenizer::from(source) {
+        let token = token.map_err(|err| Error::ParserError(err, Span::default()))?;
+        match token {
+            Token::ElementStart { span, .. } => stack.push(SourceElement {
+                name: source[span.start() + 1..span.end()].to_string(),
+                span: span.range(),
+                name_end: span.end(),
+                attributes: Vec::new(),
+                open_end: span.end()..span.end(),
+                children: Vec::new(),
+                close: None,
+            }),
+            Token::Attribute { value, span, .. } => {
+                if let Some(element) = stack.last_mut() {
+                    let name_end = source[span.range()]
+                        .find(|c: char| c == '=' || c.is_whitespace())
+                        .unwrap_or(span.len());
+                    element.attributes.push(SourceAttribute {
+                        name: source[span.start()..span.start() + name_end].to_string(),
+                        value: unescape(value.as_str()).into_owned(),
+                        span: span.range(),
+                    });
+                }
+            }
+            Token::ElementEnd { end, span } => match end {
+                ElementEnd::Open => {
+                    if let Some(element) = stack.last_mut() {
+                        element.open_end = span.range();
+                        if crate::node::parse::is_void_element(&element.name) {
+                            close(&mut roots, &mut stack, span.end());
+                        }
+                    }
+                }
+                ElementEnd::Empty => {
+                    if let Some(element) = stack.last_mut() {
+                        element.open_end = span.range();
+                    }
+                    close(&mut roots, &mut stack, span.end());
+                }
+                ElementEnd::Close(..) => {
+                    let name = source[span.start() + 2..span.end() - 1].trim();
+                    match stack.iter().rposition(|element| element.name == name) {
+                        Some(position) => {
+                            // the elements that have not been closed end with their parent
+                            while stack.len() > position + 1 {
+                                close(&mut roots, &mut stack, span.start());
+                            }
+                            if let Some(element) = stack.last_mut() {
+                                element.close = Some(span.range());
+                            }
+                            close(&mut roots, &mut stack, span.end());
+                        }
+                        None => {
+                            // closing tag of a void element, like `<br></br>`
+                            let siblings = match stack.last_mut() {
+                                Some(parent) => &mut parent.children,
+                                None => &mut roots,
+                            };
+                            match siblings.last_mut() {
+                                Some(SourceNode::Element(element))
+                                    if element.name == name && element.close.is_none() =>
+                                {
+                                    element.close = Some(span.range());
+                                    element.span.end = span.end();
+                                }
+                                _ => siblings.push(SourceNode::Other(span.range())),
+                            }
+                        }
+                    }
+                }
+            },
+            Token::Text { text } => push(&mut roots, &mut stack, SourceNode::Text(text.range())),
+            Token::Comment { span, .. } => {
+                push(&mut roots, &mut stack, SourceNode::Comment(span.range()))
+            }
+            Token::Cdata { span, .. }
+            | Token::Declaration { span, .. }
+            | Token::ProcessingInstruction { span, .. }
+            | Token::DtdStart { span, .. }
+            | Token::EmptyDtd { span, .. }
+            | Token::EntityDeclaration { span, .. }
+            | Token::DtdEnd { span } => {
+                push(&mut roots, &mut stack, SourceNode::Other(span.range()))
+            }
+        }
+    }
+    while !stack.is_empty() {
+        close(&mut roots, &mut stack, source.len());
+    }
+    Ok(roots)
+}
+
+fn find_root(nodes: &[SourceNode]) -> Option<&SourceNode> {
+    nodes
+        .iter()
+        .find(|node| matches!(node, SourceNode::Element(_)))
+}
+
+/// Merges the printed tree into the source.
+struct Merger<'a> {
+    source: &'a str,
+    printed: &'a str,
+    buffer: String,
+}
+
+impl<'a> Merger<'a> {
+    fn significant<'n>(&self, source: &str, nodes: &'n [SourceNode]) -> Vec<&'n SourceNode> {
+        nodes
+            .iter()
+            .filter(|node| !node.is_whitespace(source))
+            .collect()
+    }
+
+    /// Whether the node of the source and the printed one are equivalent, the formatting aside.
+    fn is_same(&self, old: &SourceNode, new: &SourceNode) -> bool {
+        match (old, new) {
+            (SourceNode::Element(old), SourceNode::Element(new)) => {
+                let old_children = self.significant(self.source, &old.children);
+                let new_children = self.significant(self.printed, &new.children);
+                old.name == new.name
+                    && old.attributes.len() == new.attributes.len()
+                    && old
+                        .attributes
+                        .iter()
+                        .all(|item| new.attribute(&item.name) == Some(item.value.as_str()))
+                    && old_children.len() == new_children.len()
+                    && old_children
+                        .iter()
+                        .zip(new_children.iter())
+                        .all(|(old, new)| self.is_same(old, new))
+            }
+            (SourceNode::Text(old), SourceNode::Text(new))
+            | (SourceNode::Comment(old), SourceNode::Comment(new))
+            | (SourceNode::Other(old), SourceNode::Other(new)) => {
+                self.source[old.clone()] == self.printed[new.clone()]
+            }
+            _ => false,
+        }
+    }
+
+    fn node(&mut self, old: &SourceNode, new: &SourceNode) {
+        match (old, new) {
+            _ if self.is_same(old, new) => self.buffer.push_str(&self.source[old.span()]),
+            (SourceNode::Element(old), SourceNode::Element(new)) if old.name == new.name => {
+                self.element(old, new)
+            }
+            _ => self.buffer.push_str(&self.printed[new.span()]),
+        }
+    }
+
+    fn attribute(&mut self, old: &SourceAttribute, value: &str) {
+        let raw = &self.source[old.span.clone()];
+        if old.value == value {
+            self.buffer.push_str(raw);
+            return;
+        }
+        let quote = if raw.ends_with('\'') { '\'' } else { '"' };
+        let escaped = escape_attribute(value);
+        self.buffer.push_str(&old.name);
+        self.buffer.push('=');
+        self.buffer.push(quote);
+        if quote == '\'' {
+            self.buffer.push_str(&escaped.replace('\'', "&apos;"));
+        } else {
+            self.buffer.push_str(&escaped);
+        }
+        self.buffer.push(quote);
+    }
+
+    fn element(&mut self, old: &SourceElement, new: &SourceElement) {
+        let source = self.source;
+        self.buffer.push_str(&source[old.span.start..old.name_end]);
+        let mut cursor = old.name_end;
+        for item in old.attributes.iter() {
+            if let Some(value) = new.attribute(&item.name) {
+                self.buffer.push_str(&source[cursor..item.span.start]);
+                self.attribute(item, value);
+            }
+            cursor = item.span.end;
+        }
+        for item in new.attributes.iter() {
+            if old.attribute(&item.name).is_none() {
+                self.buffer.push(' ');
+                self.buffer.push_str(&self.printed[item.span.clone()]);
+            }
+        }
+        let new_children = self.significant(self.printed, &new.children);
+        match old.close {
+            Some(ref close) => {
+                self.buffer.push_str(&source[cursor..old.open_end.end]);
+                self.children(&old.children, &new_children);
+                self.buffer.push_str(&source[close.clone()]);
+            }
+            None if new_children.is_empty() => {
+                self.buffer.push_str(&source[cursor..old.open_end.end]);
+            }
+            None => {
+                // the element was self closing, the children are added as printed
+                self.buffer
+                    .push_str(source[cursor..old.open_end.start].trim_end());
+                self.buffer.push('>');
+                for child in new_children {
+                    self.buffer.push_str(&self.printed[child.span()]);
+                }
+                self.buffer.push_str("</");
+                self.buffer.push_str(&new.name);
+                self.buffer.push('>');
+            }
+        }
+    }
+
+    /// Pairs the children of the source with the printed ones, keeping their order.
+    fn pair(&self, old: &[&SourceNode], new: &[&SourceNode]) -> Vec<Option<usize>> {
+        // longest common subsequence of comparable nodes, preferring the identical ones
+        let score = |left: &SourceNode, right: &SourceNode| -> usize {
+            if !left.is_comparable(right) {
+                0
+            } else if self.is_same(left, right) {
+                2
+            } else {
+                1
+            }
+        };
+        let mut table = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                let matched = match score(old[i], new[j]) {
+                    0 => 0,
+                    value => value + table[i + 1][j + 1],
+                };
+                table[i][j] = matched.max(table[i + 1][j]).max(table[i][j + 1]);
+            }
+        }
+        let mut result = vec![None; old.len()];
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() && j < new.len() {
+            let value = score(old[i], new[j]);
+            if value > 0 && table[i][j] == value + table[i + 1][j + 1] {
+                result[i] = Some(j);
+                i += 1;
+                j += 1;
+            } else if table[i + 1][j] >= table[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        result
+    }
+
+    fn children(&mut self, old: &[SourceNode], new: &[&SourceNode]) {
+        let source = self.source;
+        let significant = self.significant(source, old);
+        let pairs = self.pair(&significant, new);
+        // whitespaces preceding the children, used to indent the added ones
+        let indent = old
+            .iter()
+            .zip(old.iter().skip(1))
+            .find(|(_, next)| !next.is_whitespace(source))
+            .and_then(|(node, _)| match node {
+                SourceNode::Text(span) if node.is_whitespace(source) => Some(&source[span.clone()]),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let mut pending = "";
+        let mut index = 0;
+        let mut next = 0;
+        for node in old.iter() {
+            if let SourceNode::Text(span) = node {
+                if node.is_whitespace(source) {
+                    pending = &source[span.clone()];
+                    continue;
+                }
+            }
+            if let Some(position) = pairs[index] {
+                for added in new[next..position].iter() {
+                    self.buffer.push_str(pending);
+                    self.buffer.push_str(&self.printed[added.span()]);
+                    pending = indent;
+                }
+                self.buffer.push_str(pending);
+                self.node(node, new[position]);
+                next = position + 1;
+            }
+            // the whitespaces before a removed node are removed with it
+            pending = "";
+            index += 1;
+        }
+        for added in new[next..].iter() {
+            self.buffer.push_str(indent);
+            self.buffer.push_str(&self.printed[added.span()]);
+        }
+        self.buffer.push_str(pending);
+    }
+}
+
+/// Source of a template with the position of its elements, found by tokenizing it
+/// apart from the parser.
+#[derive(Debug)]
+pub struct SourceMap {
+    source: String,
+    nodes: Vec<SourceNode>,
+}
+
+impl SourceMap {
+    /// Records the nodes of the source, which must be a well formed template.
+    pub fn new<T: Into<String>>(source: T) -> Result<Self, Error> {
+        let source = source.into();
+        let nodes = build(&source).map_err(|err| err.resolve(&source, None))?;
+        Ok(Self { source, nodes })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Prints the element using the formatting of the source. The unchanged elements are
+    /// printed as they were written, the added ones are printed without indentation.
+    ///
+    /// The element is printed densely then tokenized to be matched with the source, which
+    /// fails when the dense print isn't well formed, like with an invalid `mj-raw` content,
+    /// or when it has no element. The [`dense_print`](Print::dense_print) can be used instead.
+    pub fn print<P: Print>(&self, element: &P) -> Result<String, Error> {
+        let printed = element.dense_print();
+        let new_nodes = build(&printed).map_err(|err| err.resolve(&printed, None))?;
+        let (old, new) = match (find_root(&self.nodes), find_root(&new_nodes)) {
+            (Some(old), Some(new)) => (old, new),
+            _ => return Err(Error::NoRootNode(Span::default())),
+        };
+        let mut merger = Merger {
+            source: &self.source,
+            printed: &printed,
+            buffer: String::with_capacity(self.source.len()),
+        };
+        let span = old.span();
+        merger.buffer.push_str(&self.source[..span.start]);
+        merger.node(old, new);
+        merger.buffer.push_str(&self.source[span.end..]);
+        Ok(merger.buffer)
+    }
+}
+
+/// Result of a lossless parsing, to print the edited element back with the formatting of its source.
+#[derive(Debug)]
+pub struct LosslessOutput<T> {
+    pub element: T,
+    pub source_map: SourceMap,
+}
+
+impl<T: Print> LosslessOutput<T> {
+    /// Prints the element with the formatting of its source, see [`SourceMap::print`].
+    pub fn print(&self) -> Result<String, Error> {
+        self.source_map.print(&self.element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mj_body::MjBodyChild;
+    use crate::mj_text::MjText;
+    use crate::mjml::Mjml;
+    use crate::prelude::parse::ParserOptions;
+    use crate::prelude::print::lossless::LosslessOutput;
+    use crate::text::Text;
+    use std::sync::Arc;
+
+    const TEMPLATE: &str = r##"
+<mjml lang='fr'>
+  <mj-head>
+    <mj-title>Hello</mj-title>
+  </mj-head>
+  <mj-body   background-color="#fff" width='600px'>
+    <!-- header -->
+    <mj-section css-class="a &amp; b" padding="0">
+
+      <mj-column>
+        <mj-text  color="red" align="left">Hello &amp; <b>World</b>!</mj-text>
+        <mj-divider/>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>
+"##;
+
+    fn parse() -> LosslessOutput<Mjml> {
+        Mjml::parse_lossless(TEMPLATE, Arc::new(ParserOptions::default())).unwrap()
+    }
+
+    fn column(output: &mut LosslessOutput<Mjml>) -> &mut Vec<MjBodyChild> {
+        let body = output.element.children.body.as_mut().unwrap();
+        let section = match body.children.get_mut(1) {
+            Some(MjBodyChild::MjSection(section)) => section,
+            other => panic!("unexpected child {:?}", other),
+        };
+        match section.children.get_mut(0) {
+            Some(MjBodyChild::MjColumn(column)) => &mut column.children,
+            other => panic!("unexpected child {:?}", other),
+        }
+    }
+
+    fn text(value: &str) -> MjBodyChild {
+        let mut element = MjText::default();
+        element.children.push(Text::from(value).into());
+        MjBodyChild::MjText(element)
+    }
+
+    #[test]
+    fn should_print_unchanged_template_as_is() {
+        assert_eq!(parse().print().unwrap(), TEMPLATE);
+    }
+
+    #[test]
+    fn should_print_unchanged_fixtures_as_is() {
+        let folder =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/compare/success");
+        for entry in std::fs::read_dir(folder).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("mjml") {
+                continue;
+            }
+            let template = std::fs::read_to_string(&path).unwrap();
+            let output = Mjml::parse_lossless(&template, Arc::default()).unwrap();
+            assert_eq!(output.print().unwrap(), template, "{path:?}");
+        }
+    }
+
+    #[test]
+    fn should_keep_attributes_order_and_quotes() {
+        let mut output = parse();
+        output.element.attributes.lang = Some("it's".into());
+        let body = output.element.children.body.as_mut().unwrap();
+        body.attributes.insert("width".into(), "500px".into());
+        body.attributes.remove("background-color");
+        body.attributes
+            .insert("css-class".into(), "\"body\"".into());
+        let expected = TEMPLATE
+            .replace("<mjml lang='fr'>", "<mjml lang='it&apos;s'>")
+            .replace(
+                r##"<mj-body   background-color="#fff" width='600px'>"##,
+                r#"<mj-body width='500px' css-class="&quot;body&quot;">"#,
+            );
+        assert_eq!(output.print().unwrap(), expected);
+    }
+
+    #[test]
+    fn should_remove_children_with_their_whitespaces() {
+        let mut output = parse();
+        column(&mut output).remove(0);
+        let expected = TEMPLATE.replace(
+            "\n        <mj-text  color=\"red\" align=\"left\">Hello &amp; <b>World</b>!</mj-text>",
+            "",
+        );
+        assert_eq!(output.print().unwrap(), expected);
+    }
+
+    #[test]
+    fn should_indent_added_children() {
+        let mut output = parse();
+        column(&mut output).insert(1, text("Middle"));
+        column(&mut output).push(text("Last"));
+        let expected = TEMPLATE
+            .replace(
+                "\n        <mj-divider/>",
+                "\n        <mj-text>Middle</mj-text>\n        <mj-divider/>",
+            )
+            .replace(
+                "<mj-divider/>\n",
+                "<mj-divider/>\n        <mj-text>Last</mj-text>\n",
+            );
+        assert_eq!(output.print().unwrap(), expected);
+    }
+
+    #[test]
+    fn should_replace_edited_text() {
+        let mut output = parse();
+        if let Some(MjBodyChild::MjText(element)) = column(&mut output).get_mut(0) {
+            element.children = vec![Text::from("Goodbye").into()];
+        }
+        let expected = TEMPLATE.replace("Hello &amp; <b>World</b>!", "Goodbye");
+        assert_eq!(output.print().unwrap(), expected);
+    }
+
+    #[test]
+    fn should_open_self_closing_elements() {
+        let template = "<mjml>\n  <mj-body />\n</mjml>";
+        let mut output = Mjml::parse_lossless(template, Arc::default()).unwrap();
+        let body = output.element.children.body.as_mut().unwrap();
+        body.children.push(MjBodyChild::Text(Text::from("Hello")));
+        assert_eq!(
+            output.print().unwrap(),
+            "<mjml>\n  <mj-body>Hello</mj-body>\n</mjml>"
+        );
+    }
+
+    #[test]
+    fn should_fail_when_the_print_is_not_well_formed() {
+        let mut output = parse();
+        column(&mut output).push(MjBodyChild::Node(crate::node::Node::from("1")));
+        assert!(output.print().is_err());
+    }
+}
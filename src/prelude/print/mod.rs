@@ -1,6 +1,12 @@
+use crate::helper::escape::escape_attribute;
 use crate::helper::sort::sort_by_key;
 use crate::prelude::hash::Map;
 
+#[cfg(feature = "parse")]
+pub mod format;
+#[cfg(feature = "parse")]
+pub mod lossless;
+
 pub trait Print {
     fn print(&self, pretty: bool, level: usize, indent_size: usize) -> String;
 
@@ -26,7 +32,7 @@ pub fn attributes(attrs: Option<&Map<String, String>>) -> String {
             entries.sort_by(sort_by_key);
             entries
                 .iter()
-                .map(|(key, value)| format!(" {key}=\"{value}\""))
+                .map(|(key, value)| format!(" {key}=\"{}\"", escape_attribute(value)))
                 .collect::<String>()
         })
         .unwrap_or_default()
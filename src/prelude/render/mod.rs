@@ -1,4 +1,5 @@
 use super::hash::Set;
+use crate::helper::random::IdGenerator;
 use crate::helper::size::{Pixel, Size};
 use crate::helper::spacing::Spacing;
 use crate::helper::tag::Tag;
@@ -9,11 +10,108 @@ use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::rc::Rc;
+use std::sync::Arc;
 
+pub mod resources;
+pub mod text;
+
+/// Errors of the rendering. New variants may be added in a minor version, a `match` on it
+/// needs a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("unknown fragment {0}")]
     UnknownFragment(String),
+    #[error("unable to write the rendered template")]
+    Format(#[source] std::fmt::Error),
+    #[error("unable to write the rendered template")]
+    Io(#[source] std::io::Error),
+}
+
+/// Element needing an identifier, like `mj-carousel` or `mj-navbar`.
+#[derive(Debug)]
+pub struct IdContext<'a> {
+    pub tag: &'a str,
+    /// Number of identifiers generated before this one, during the same rendering.
+    pub index: usize,
+    /// Attributes of the element and of its children, identifying its content.
+    pub content: &'a str,
+    /// Expected number of characters.
+    pub size: usize,
+}
+
+/// How the identifiers of the elements are generated.
+#[derive(Clone, Default)]
+pub enum IdStrategy {
+    /// Random identifiers, different on every rendering.
+    #[default]
+    Random,
+    /// Identifiers generated by a random generator initialised with the seed, each rendering
+    /// starting with the same sequence.
+    Seeded(u64),
+    /// Stable hash of the content of the element and of its position in the template.
+    ContentHash,
+    Custom(Arc<dyn Fn(&IdContext) -> String + Send + Sync>),
+}
+
+impl std::fmt::Debug for IdStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Random => f.write_str("Random"),
+            Self::Seeded(seed) => f.debug_tuple("Seeded").field(seed).finish(),
+            Self::ContentHash => f.write_str("ContentHash"),
+            Self::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// Component rendering a link, like `mj-button` or `mj-image`.
+#[derive(Debug)]
+pub struct LinkContext<'a> {
+    pub tag: &'a str,
+    /// Value of the `css-class` attribute of the component.
+    pub css_class: Option<&'a str>,
+}
+
+/// Rewrites the links of the components while rendering, to add tracking parameters or to go
+/// through a redirection. The links written in `mj-raw` or in the html content of the
+/// components are left untouched.
+///
+/// The rewritten links are the `href` of `mj-button`, `mj-carousel-image`, `mj-image`,
+/// `mj-navbar-link` and `mj-social-element`. An `mj-hero` has no `href` of its own, its links
+/// come from its child components, which are rewritten.
+///
+/// ```rust
+/// use mrml::prelude::render::{LinkContext, Options};
+/// use std::sync::Arc;
+///
+/// let root = mrml::parse(r#"<mjml><mj-body><mj-button href="https://example.com">Go</mj-button></mj-body></mjml>"#).unwrap();
+/// let opts = Options {
+///     link_rewriter: Some(Arc::new(|url: &str, ctx: &LinkContext| {
+///         format!("{url}?utm_source=newsletter&utm_content={}", ctx.tag)
+///     })),
+///     ..Default::default()
+/// };
+/// let html = root.render(&opts).unwrap();
+/// assert!(html.contains("https://example.com?utm_source=newsletter&amp;utm_content=mj-button"));
+/// ```
+pub trait LinkRewriter: Send + Sync {
+    fn rewrite(&self, url: &str, context: &LinkContext) -> String;
+}
+
+impl<F> LinkRewriter for F
+where
+    F: Fn(&str, &LinkContext) -> String + Send + Sync,
+{
+    fn rewrite(&self, url: &str, context: &LinkContext) -> String {
+        self(url, context)
+    }
+}
+
+impl std::fmt::Debug for dyn LinkRewriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LinkRewriter")
+    }
 }
 
 #[derive(Debug)]
@@ -21,6 +119,13 @@ pub struct Options {
     pub disable_comments: bool,
     pub social_icon_origin: Option<Cow<'static, str>>,
     pub fonts: HashMap<String, Cow<'static, str>>,
+    /// Use [`IdStrategy::Seeded`] or [`IdStrategy::ContentHash`] to render the same template
+    /// identically every time.
+    pub id_strategy: IdStrategy,
+    /// Collapses the insignificant whitespaces, removes the comments and shortens the styles
+    /// of the generated html. The conditional comments for Outlook are kept untouched.
+    pub minify: bool,
+    pub link_rewriter: Option<Arc<dyn LinkRewriter>>,
 }
 
 impl Default for Options {
@@ -28,6 +133,9 @@ impl Default for Options {
         Self {
             disable_comments: false,
             social_icon_origin: None,
+            id_strategy: IdStrategy::default(),
+            minify: false,
+            link_rewriter: None,
             fonts: HashMap::from([
                 (
                     "Open Sans".into(),
@@ -63,8 +171,14 @@ pub struct Header<'h> {
     font_families: Map<&'h str, &'h str>,
     used_font_families: Set<String>,
     media_queries: Map<String, Size>,
+    /// Css properties and values of the dark color scheme, by class name. The components
+    /// only borrow the header to compute their styles, hence the cell.
+    dark_styles: RefCell<Map<String, (String, String)>>,
     styles: Set<String>,
     lang: Option<String>,
+    dir: Option<String>,
+    owa: Option<String>,
+    ids: IdGenerator,
 }
 
 impl<'h> Header<'h> {
@@ -94,8 +208,12 @@ impl<'h> Header<'h> {
                 .unwrap_or_default(),
             used_font_families: Set::new(),
             media_queries: Map::new(),
+            dark_styles: RefCell::new(Map::new()),
             styles: Set::new(),
             lang: Default::default(),
+            dir: Default::default(),
+            owa: Default::default(),
+            ids: IdGenerator::default(),
         }
     }
 
@@ -162,6 +280,24 @@ impl<'h> Header<'h> {
         self.media_queries.insert(classname, size);
     }
 
+    pub fn dark_styles(&self) -> Ref<'_, Map<String, (String, String)>> {
+        self.dark_styles.borrow()
+    }
+
+    /// Registers a style of the dark color scheme and returns the class applying it.
+    pub fn add_dark_style(&self, property: &str, value: &str) -> String {
+        let suffix = value
+            .trim_start_matches('#')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>();
+        let classname = format!("mj-dark-{property}-{}", suffix.trim_matches('-'));
+        self.dark_styles
+            .borrow_mut()
+            .insert(classname.clone(), (property.to_string(), value.to_string()));
+        classname
+    }
+
     pub fn styles(&self) -> &Set<String> {
         &self.styles
     }
@@ -183,6 +319,61 @@ impl<'h> Header<'h> {
     pub fn maybe_set_lang(&mut self, value: Option<String>) {
         self.lang = value;
     }
+
+    pub fn dir(&self) -> Option<&str> {
+        self.dir.as_deref()
+    }
+
+    pub fn maybe_set_dir(&mut self, value: Option<String>) {
+        self.dir = value;
+    }
+
+    pub fn owa(&self) -> Option<&str> {
+        self.owa.as_deref()
+    }
+
+    pub fn maybe_set_owa(&mut self, value: Option<String>) {
+        self.owa = value;
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        self.dir.as_deref() == Some("rtl")
+    }
+
+    /// Default `direction` of the components, following the `dir` attribute of the document.
+    pub fn direction(&self) -> &'static str {
+        if self.is_rtl() {
+            "rtl"
+        } else {
+            "ltr"
+        }
+    }
+
+    /// Side where the lines start, `right` for the right-to-left documents.
+    pub fn start_side(&self) -> &'static str {
+        if self.is_rtl() {
+            "right"
+        } else {
+            "left"
+        }
+    }
+
+    /// Side where the lines end, `left` for the right-to-left documents.
+    pub fn end_side(&self) -> &'static str {
+        if self.is_rtl() {
+            "left"
+        } else {
+            "right"
+        }
+    }
+
+    pub fn set_id_strategy(&mut self, strategy: &IdStrategy) {
+        self.ids = IdGenerator::new(strategy);
+    }
+
+    pub fn generate_id(&mut self, tag: &str, content: &str, size: usize) -> String {
+        self.ids.generate(tag, content, size)
+    }
 }
 
 pub trait Render<'header> {
@@ -222,6 +413,35 @@ pub trait Render<'header> {
         self.attribute(key).is_some()
     }
 
+    /// Applies the [`LinkRewriter`] of the options to a link of the component.
+    fn rewrite_link(&self, url: String, opts: &Options) -> String {
+        match opts.link_rewriter {
+            Some(ref rewriter) => {
+                let css_class = self.attribute("css-class");
+                rewriter.rewrite(
+                    &url,
+                    &LinkContext {
+                        tag: self.tag().unwrap_or_default(),
+                        css_class: css_class.as_deref(),
+                    },
+                )
+            }
+            None => url,
+        }
+    }
+
+    /// Class applying the `dark-` variant of the attribute, like `dark-background-color`, to the
+    /// css property when the reader prefers a dark color scheme.
+    fn dark_class(&self, name: &str, property: &str) -> Option<String> {
+        let value = self.attribute(&format!("dark-{name}"))?;
+        Some(self.header().add_dark_style(property, &value))
+    }
+
+    /// Outlook ignores the `direction` style, the `dir` attribute of its tables orders the columns.
+    fn outlook_dir(&self) -> Option<String> {
+        self.attribute("direction").filter(|value| value == "rtl")
+    }
+
     fn get_border_left(&self) -> Option<Pixel> {
         self.attribute_as_pixel("border-left").or_else(|| {
             self.attribute("border")
@@ -370,6 +590,13 @@ pub trait Render<'header> {
     }
 
     fn render(&self, opts: &Options) -> Result<String, Error>;
+
+    /// Renders the element at the end of the buffer. The containers override it to render
+    /// their children in the same buffer, instead of allocating a string for each of them.
+    fn render_into(&self, buf: &mut String, opts: &Options) -> Result<(), Error> {
+        buf.push_str(&self.render(opts)?);
+        Ok(())
+    }
 }
 
 pub trait Renderable<'render, 'element: 'render, 'header: 'render> {
@@ -0,0 +1,684 @@
+//! Listing of the external resources of a template, like the images, the fonts and the links,
+//! with the path of the element referencing each of them.
+//!
+//! The attributes are resolved like when rendering, so the values coming from `mj-attributes`
+//! and the default icons of the components are listed as well.
+//!
+//! ```rust
+//! use mrml::prelude::render::resources::ResourceKind;
+//!
+//! let template = r#"<mjml><mj-body>
+//!   <mj-section background-url="https://acme.com/bg.png"><mj-column>
+//!     <mj-image src="https://acme.com/logo.png" href="https://acme.com" />
+//!   </mj-column></mj-section>
+//! </mj-body></mjml>"#;
+//! let root = mrml::parse(template).unwrap();
+//! let resources = root.resources(&Default::default());
+//! assert_eq!(resources[0].kind, ResourceKind::Background);
+//! assert_eq!(resources[0].path, "mjml > mj-body > mj-section[0]");
+//! assert_eq!(resources[2].url, "https://acme.com");
+//! assert_eq!(resources[2].path, "mjml > mj-body > mj-section[0] > mj-column[0] > mj-image[0]");
+//! ```
+
+use super::{Header, Options, Renderable};
+use crate::comment::Comment;
+use crate::mj_accordion::{MjAccordion, MjAccordionChild};
+use crate::mj_body::{MjBody, MjBodyChild};
+use crate::mj_button::MjButton;
+use crate::mj_carousel::{MjCarousel, MjCarouselChild};
+use crate::mj_head::{MjHead, MjHeadChild};
+use crate::mj_hero::MjHero;
+use crate::mj_image::MjImage;
+use crate::mj_include::body::{MjIncludeBody, MjIncludeBodyChild};
+use crate::mj_include::head::MjIncludeHeadChild;
+use crate::mj_navbar::{MjNavbar, MjNavbarChild};
+use crate::mj_raw::{MjRaw, MjRawChild};
+use crate::mj_section::MjSection;
+use crate::mj_social::{MjSocial, MjSocialChild};
+use crate::mj_social_element::network::{SocialNetwork, DEFAULT_ICON_ORIGIN};
+use crate::mj_table::MjTable;
+use crate::mj_text::MjText;
+use crate::mj_wrapper::MjWrapper;
+use crate::mjml::Mjml;
+use crate::node::Node;
+use crate::text::Text;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// Image of `mj-image`, `mj-carousel-image` or of an html `img`, `srcset` included.
+    Image,
+    /// `background-url` of `mj-section`, `mj-wrapper` or `mj-hero`.
+    Background,
+    /// Stylesheet of an `mj-font` or of a font of the options used by a component.
+    Font,
+    /// Icon of a component, like the social icons or the arrows of `mj-carousel`.
+    Icon,
+    /// Link of a component or of an html `a`.
+    Link,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Resource {
+    pub kind: ResourceKind,
+    pub url: String,
+    /// Path of the element referencing the resource, like
+    /// `mjml > mj-body > mj-section[0] > mj-column[0] > mj-image[1]`.
+    pub path: String,
+}
+
+struct Collector<'h, 'o> {
+    header: Rc<RefCell<Header<'h>>>,
+    options: &'o Options,
+    path: Vec<String>,
+    /// Position of the next element amongst its siblings.
+    index: Option<usize>,
+    output: Vec<Resource>,
+}
+
+impl<'h, 'o> Collector<'h, 'o> {
+    /// Adds a resource of the current element, unless the element already references it.
+    fn push(&mut self, kind: ResourceKind, url: Option<String>) {
+        if let Some(url) = url.filter(|url| !url.trim().is_empty()) {
+            let path = self.path.join(" > ");
+            let found = self
+                .output
+                .iter()
+                .rev()
+                .take_while(|item| item.path == path)
+                .any(|item| item.kind == kind && item.url == url);
+            if !found {
+                self.output.push(Resource { kind, url, path });
+            }
+        }
+    }
+
+    /// Adds the stylesheets of the fonts of a `font-family`, resolved through the options
+    /// like when rendering the head, the first time they are used.
+    fn push_font_families(&mut self, value: Option<String>) {
+        for name in value.iter().flat_map(|value| value.split(',')) {
+            let href = self
+                .options
+                .fonts
+                .get(name.trim())
+                .map(|href| href.to_string());
+            if let Some(href) = href {
+                let found = self
+                    .output
+                    .iter()
+                    .any(|item| item.kind == ResourceKind::Font && item.url == href);
+                if !found {
+                    self.push(ResourceKind::Font, Some(href));
+                }
+            }
+        }
+    }
+
+    /// Adds the images of a `srcset`, like `small.png 300w, large.png 600w`.
+    fn push_srcset(&mut self, value: Option<String>) {
+        for item in value.iter().flat_map(|value| value.split(',')) {
+            let url = item.split_whitespace().next().map(String::from);
+            self.push(ResourceKind::Image, url);
+        }
+    }
+
+    fn element<F: FnOnce(&mut Self)>(&mut self, tag: &str, func: F) {
+        match self.index.take() {
+            Some(index) => self.path.push(format!("{tag}[{index}]")),
+            None => self.path.push(tag.to_string()),
+        }
+        func(self);
+        self.path.pop();
+    }
+
+    fn content<T: CollectResources>(&mut self, children: &[T]) {
+        for (index, child) in children.iter().enumerate() {
+            self.index = Some(index);
+            child.collect_resources(self);
+        }
+        self.index = None;
+    }
+}
+
+trait CollectResources {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>);
+}
+
+impl CollectResources for Comment {
+    fn collect_resources(&self, _collector: &mut Collector<'_, '_>) {}
+}
+
+impl CollectResources for Text {
+    fn collect_resources(&self, _collector: &mut Collector<'_, '_>) {}
+}
+
+impl<T: CollectResources> CollectResources for Node<T> {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(&self.tag, |collector| {
+            let attribute = |name: &str| self.attributes.get(name).cloned();
+            match self.tag.as_str() {
+                "a" | "area" => collector.push(ResourceKind::Link, attribute("href")),
+                "img" => {
+                    collector.push(ResourceKind::Image, attribute("src"));
+                    collector.push_srcset(attribute("srcset"));
+                }
+                _ => {}
+            }
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjRawChild {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        match self {
+            Self::Comment(elt) => elt.collect_resources(collector),
+            Self::Node(elt) => elt.collect_resources(collector),
+            Self::Text(elt) => elt.collect_resources(collector),
+        }
+    }
+}
+
+impl CollectResources for MjBodyChild {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        match self {
+            Self::Comment(elt) => elt.collect_resources(collector),
+            Self::MjAccordion(elt) => elt.collect_resources(collector),
+            Self::MjButton(elt) => elt.collect_resources(collector),
+            Self::MjCarousel(elt) => elt.collect_resources(collector),
+            Self::MjColumn(elt) => collector.element(crate::mj_column::NAME, |collector| {
+                collector.content(&elt.children)
+            }),
+            Self::MjDivider(_) => {}
+            Self::MjGroup(elt) => collector.element(crate::mj_group::NAME, |collector| {
+                collector.content(&elt.children)
+            }),
+            Self::MjHero(elt) => elt.collect_resources(collector),
+            Self::MjInclude(elt) => elt.collect_resources(collector),
+            Self::MjImage(elt) => elt.collect_resources(collector),
+            Self::MjNavbar(elt) => elt.collect_resources(collector),
+            Self::MjRaw(elt) => elt.collect_resources(collector),
+            Self::MjSection(elt) => elt.collect_resources(collector),
+            Self::MjSocial(elt) => elt.collect_resources(collector),
+            Self::MjSpacer(_) => {}
+            Self::MjTable(elt) => elt.collect_resources(collector),
+            Self::MjText(elt) => elt.collect_resources(collector),
+            Self::MjWrapper(elt) => elt.collect_resources(collector),
+            Self::Node(elt) => elt.collect_resources(collector),
+            Self::Text(elt) => elt.collect_resources(collector),
+        }
+    }
+}
+
+impl CollectResources for MjIncludeBodyChild {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        match self {
+            Self::Comment(elt) => elt.collect_resources(collector),
+            Self::MjAccordion(elt) => elt.collect_resources(collector),
+            Self::MjButton(elt) => elt.collect_resources(collector),
+            Self::MjCarousel(elt) => elt.collect_resources(collector),
+            Self::MjColumn(elt) => collector.element(crate::mj_column::NAME, |collector| {
+                collector.content(&elt.children)
+            }),
+            Self::MjDivider(_) => {}
+            Self::MjGroup(elt) => collector.element(crate::mj_group::NAME, |collector| {
+                collector.content(&elt.children)
+            }),
+            Self::MjHero(elt) => elt.collect_resources(collector),
+            Self::MjImage(elt) => elt.collect_resources(collector),
+            Self::MjNavbar(elt) => elt.collect_resources(collector),
+            Self::MjRaw(elt) => elt.collect_resources(collector),
+            Self::MjSection(elt) => elt.collect_resources(collector),
+            Self::MjSocial(elt) => elt.collect_resources(collector),
+            Self::MjSpacer(_) => {}
+            Self::MjTable(elt) => elt.collect_resources(collector),
+            Self::MjText(elt) => elt.collect_resources(collector),
+            Self::MjWrapper(elt) => elt.collect_resources(collector),
+            Self::Node(elt) => elt.collect_resources(collector),
+            Self::Text(elt) => elt.collect_resources(collector),
+        }
+    }
+}
+
+impl CollectResources for MjIncludeBody {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_include::NAME, |collector| {
+            collector.content(&self.children)
+        });
+    }
+}
+
+impl CollectResources for MjRaw {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_raw::NAME, |collector| {
+            collector.content(&self.children)
+        });
+    }
+}
+
+impl CollectResources for MjSection {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_section::NAME, |collector| {
+            let url = self
+                .renderer(collector.header.clone())
+                .attribute("background-url");
+            collector.push(ResourceKind::Background, url);
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjWrapper {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_wrapper::NAME, |collector| {
+            let url = self
+                .renderer(collector.header.clone())
+                .attribute("background-url");
+            collector.push(ResourceKind::Background, url);
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjHero {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_hero::NAME, |collector| {
+            let url = self
+                .renderer(collector.header.clone())
+                .attribute("background-url");
+            collector.push(ResourceKind::Background, url);
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjButton {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_button::NAME, |collector| {
+            let renderer = self.renderer(collector.header.clone());
+            collector.push_font_families(renderer.attribute("font-family"));
+            collector.push(ResourceKind::Link, renderer.attribute("href"));
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjTable {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_table::NAME, |collector| {
+            let font_family = self
+                .renderer(collector.header.clone())
+                .attribute("font-family");
+            collector.push_font_families(font_family);
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjText {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_text::NAME, |collector| {
+            let font_family = self
+                .renderer(collector.header.clone())
+                .attribute("font-family");
+            collector.push_font_families(font_family);
+            collector.content(&self.children);
+        });
+    }
+}
+
+impl CollectResources for MjImage {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_image::NAME, |collector| {
+            let renderer = self.renderer(collector.header.clone());
+            collector.push(ResourceKind::Image, renderer.attribute("src"));
+            collector.push_srcset(renderer.attribute("srcset"));
+            collector.push(ResourceKind::Link, renderer.attribute("href"));
+        });
+    }
+}
+
+impl CollectResources for MjCarousel {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_carousel::NAME, |collector| {
+            let renderer = self.renderer(collector.header.clone());
+            collector.push(ResourceKind::Icon, renderer.attribute("left-icon"));
+            collector.push(ResourceKind::Icon, renderer.attribute("right-icon"));
+            for (index, child) in self.children.iter().enumerate() {
+                if let MjCarouselChild::MjCarouselImage(image) = child {
+                    collector.index = Some(index);
+                    collector.element(crate::mj_carousel_image::NAME, |collector| {
+                        let renderer = image.renderer(collector.header.clone());
+                        collector.push(ResourceKind::Image, renderer.attribute("src"));
+                        collector.push(ResourceKind::Image, renderer.attribute("thumbnails-src"));
+                        collector.push(ResourceKind::Link, renderer.attribute("href"));
+                    });
+                }
+            }
+        });
+    }
+}
+
+impl CollectResources for MjAccordion {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_accordion::NAME, |collector| {
+            let accordion = self.renderer(collector.header.clone());
+            collector.push_font_families(accordion.attribute("font-family"));
+            for (index, child) in self.children.iter().enumerate() {
+                if let MjAccordionChild::MjAccordionElement(element) = child {
+                    collector.index = Some(index);
+                    collector.element(crate::mj_accordion_element::NAME, |collector| {
+                        let mut renderer = element.renderer(collector.header.clone());
+                        for name in ["icon-wrapped-url", "icon-unwrapped-url", "font-family"] {
+                            renderer.maybe_add_extra_attribute(name, accordion.attribute(name));
+                        }
+                        for name in ["icon-wrapped-url", "icon-unwrapped-url"] {
+                            collector.push(ResourceKind::Icon, renderer.attribute(name));
+                        }
+                        let font_family = renderer.attribute("font-family");
+                        if let Some(ref title) = element.children.title {
+                            let mut title = title.renderer(collector.header.clone());
+                            title.maybe_add_extra_attribute("font-family", font_family.clone());
+                            collector.push_font_families(title.attribute("font-family"));
+                        }
+                        if let Some(ref text) = element.children.text {
+                            collector.element(crate::mj_accordion_text::NAME, |collector| {
+                                let mut renderer = text.renderer(collector.header.clone());
+                                renderer.maybe_add_extra_attribute("font-family", font_family);
+                                collector.push_font_families(renderer.attribute("font-family"));
+                                collector.content(&text.children);
+                            });
+                        }
+                    });
+                }
+            }
+        });
+    }
+}
+
+impl CollectResources for MjNavbar {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_navbar::NAME, |collector| {
+            let base_url = self
+                .renderer(collector.header.clone())
+                .attribute("base-url");
+            for (index, child) in self.children.iter().enumerate() {
+                if let MjNavbarChild::MjNavbarLink(link) = child {
+                    collector.index = Some(index);
+                    collector.element(crate::mj_navbar_link::NAME, |collector| {
+                        let renderer = link.renderer(collector.header.clone());
+                        collector.push_font_families(renderer.attribute("font-family"));
+                        let href = renderer.attribute("href").map(|href| match base_url {
+                            Some(ref base_url) => format!("{base_url}{href}"),
+                            None => href,
+                        });
+                        collector.push(ResourceKind::Link, href);
+                    });
+                }
+            }
+        });
+    }
+}
+
+impl CollectResources for MjSocial {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_social::NAME, |collector| {
+            let font_family = self
+                .renderer(collector.header.clone())
+                .attribute("font-family");
+            collector.push_font_families(font_family);
+            for (index, child) in self.children.iter().enumerate() {
+                if let MjSocialChild::MjSocialElement(element) = child {
+                    collector.index = Some(index);
+                    collector.element(crate::mj_social_element::NAME, |collector| {
+                        let renderer = element.renderer(collector.header.clone());
+                        let network = renderer
+                            .attribute("name")
+                            .and_then(|name| SocialNetwork::find(&name));
+                        let origin = collector
+                            .options
+                            .social_icon_origin
+                            .as_deref()
+                            .unwrap_or(DEFAULT_ICON_ORIGIN);
+                        let icon = renderer
+                            .attribute("src")
+                            .or_else(|| network.as_ref().map(|network| network.icon_src(origin)));
+                        collector.push(ResourceKind::Icon, icon);
+                        collector.push_srcset(renderer.attribute("srcset"));
+                        let href = renderer.attribute("href").map(|href| {
+                            network
+                                .as_ref()
+                                .and_then(|network| network.share_url(&href))
+                                .unwrap_or(href)
+                        });
+                        collector.push(ResourceKind::Link, href);
+                    });
+                }
+            }
+        });
+    }
+}
+
+impl CollectResources for MjHead {
+    fn collect_resources(&self, collector: &mut Collector<'_, '_>) {
+        collector.element(crate::mj_head::NAME, |collector| {
+            for (index, child) in self.children.iter().enumerate() {
+                collector.index = Some(index);
+                match child {
+                    MjHeadChild::MjFont(font) => {
+                        collector.element(crate::mj_font::NAME, |collector| {
+                            collector.push(ResourceKind::Font, Some(font.href().to_string()));
+                        })
+                    }
+                    MjHeadChild::MjInclude(include) => {
+                        collector.element(crate::mj_include::NAME, |collector| {
+                            for (index, child) in include.children.iter().enumerate() {
+                                collector.index = Some(index);
+                                match child {
+                                    MjIncludeHeadChild::MjFont(font) => {
+                                        collector.element(crate::mj_font::NAME, |collector| {
+                                            let href = font.href().to_string();
+                                            collector.push(ResourceKind::Font, Some(href));
+                                        })
+                                    }
+                                    MjIncludeHeadChild::MjRaw(raw) => {
+                                        raw.collect_resources(collector)
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        })
+                    }
+                    MjHeadChild::MjRaw(raw) => raw.collect_resources(collector),
+                    _ => {}
+                }
+            }
+            collector.index = None;
+        });
+    }
+}
+
+impl Mjml {
+    /// Lists the external resources of the template, in the order of the document: the
+    /// images, backgrounds, fonts and icons to host or allow, and the links to check.
+    pub fn resources(&self, opts: &Options) -> Vec<Resource> {
+        let mut collector = Collector {
+            header: Rc::new(RefCell::new(Header::new(&self.children.head))),
+            options: opts,
+            path: Vec::new(),
+            index: None,
+            output: Vec::new(),
+        };
+        collector.element(crate::mjml::NAME, |collector| {
+            if let Some(head) = self.head() {
+                head.collect_resources(collector);
+            }
+            if let Some(MjBody { children, .. }) = self.body() {
+                collector.element(crate::mj_body::NAME, |collector| {
+                    collector.content(children);
+                });
+            }
+        });
+        collector.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Resource, ResourceKind};
+    use crate::mjml::Mjml;
+    use crate::prelude::render::Options;
+
+    fn resources(template: &str, opts: &Options) -> Vec<(ResourceKind, String, String)> {
+        Mjml::parse(template)
+            .unwrap()
+            .resources(opts)
+            .into_iter()
+            .map(|Resource { kind, url, path }| (kind, url, path))
+            .collect()
+    }
+
+    #[test]
+    fn should_list_resources_with_their_path() {
+        let template = r#"<mjml>
+  <mj-head>
+    <mj-font name="Raleway" href="https://fonts.com/raleway.css" />
+    <mj-attributes><mj-hero background-url="https://acme.com/hero.png" /></mj-attributes>
+  </mj-head>
+  <mj-body>
+    <mj-hero>
+      <mj-button href="https://acme.com/buy">Buy</mj-button>
+    </mj-hero>
+    <mj-wrapper background-url="https://acme.com/wrapper.png">
+      <mj-section>
+        <mj-column>
+          <mj-image src="https://acme.com/a.png" srcset="https://acme.com/a.png 300w, https://acme.com/a@2x.png 600w" />
+          <mj-text><p>Read <a href="https://acme.com/blog">the blog</a><img src="https://acme.com/pixel.gif" /></p></mj-text>
+        </mj-column>
+      </mj-section>
+    </mj-wrapper>
+  </mj-body>
+</mjml>"#;
+        let body = "mjml > mj-body";
+        assert_eq!(
+            resources(template, &Options::default()),
+            vec![
+                (
+                    ResourceKind::Font,
+                    "https://fonts.com/raleway.css".to_string(),
+                    "mjml > mj-head > mj-font[0]".to_string()
+                ),
+                (
+                    ResourceKind::Background,
+                    "https://acme.com/hero.png".to_string(),
+                    format!("{body} > mj-hero[0]")
+                ),
+                (
+                    ResourceKind::Font,
+                    "https://fonts.googleapis.com/css?family=Ubuntu:300,400,500,700".to_string(),
+                    format!("{body} > mj-hero[0] > mj-button[0]")
+                ),
+                (
+                    ResourceKind::Link,
+                    "https://acme.com/buy".to_string(),
+                    format!("{body} > mj-hero[0] > mj-button[0]")
+                ),
+                (
+                    ResourceKind::Background,
+                    "https://acme.com/wrapper.png".to_string(),
+                    format!("{body} > mj-wrapper[1]")
+                ),
+                (
+                    ResourceKind::Image,
+                    "https://acme.com/a.png".to_string(),
+                    format!("{body} > mj-wrapper[1] > mj-section[0] > mj-column[0] > mj-image[0]")
+                ),
+                (
+                    ResourceKind::Image,
+                    "https://acme.com/a@2x.png".to_string(),
+                    format!("{body} > mj-wrapper[1] > mj-section[0] > mj-column[0] > mj-image[0]")
+                ),
+                (
+                    ResourceKind::Link,
+                    "https://acme.com/blog".to_string(),
+                    format!("{body} > mj-wrapper[1] > mj-section[0] > mj-column[0] > mj-text[1] > p[0] > a[1]")
+                ),
+                (
+                    ResourceKind::Image,
+                    "https://acme.com/pixel.gif".to_string(),
+                    format!("{body} > mj-wrapper[1] > mj-section[0] > mj-column[0] > mj-text[1] > p[0] > img[2]")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_resolve_icons() {
+        let template = r#"<mjml><mj-body><mj-section><mj-column>
+  <mj-social><mj-social-element name="facebook" href="https://acme.com" /></mj-social>
+  <mj-navbar base-url="https://acme.com"><mj-navbar-link href="/about">About</mj-navbar-link></mj-navbar>
+  <mj-carousel><mj-carousel-image src="https://acme.com/1.png" /></mj-carousel>
+</mj-column></mj-section></mj-body></mjml>"#;
+        let opts = Options {
+            social_icon_origin: Some("https://cdn.acme.com/icons/".into()),
+            ..Default::default()
+        };
+        let result = resources(template, &opts)
+            .into_iter()
+            .map(|(kind, url, _)| (kind, url))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            vec![
+                (
+                    ResourceKind::Font,
+                    "https://fonts.googleapis.com/css?family=Ubuntu:300,400,500,700".to_string()
+                ),
+                (
+                    ResourceKind::Icon,
+                    "https://cdn.acme.com/icons/facebook.png".to_string()
+                ),
+                (
+                    ResourceKind::Link,
+                    "https://www.facebook.com/sharer/sharer.php?u=https://acme.com".to_string()
+                ),
+                (ResourceKind::Link, "https://acme.com/about".to_string()),
+                (
+                    ResourceKind::Icon,
+                    "https://i.imgur.com/xTh3hln.png".to_string()
+                ),
+                (
+                    ResourceKind::Icon,
+                    "https://i.imgur.com/os7o9kz.png".to_string()
+                ),
+                (ResourceKind::Image, "https://acme.com/1.png".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_list_used_fonts_once() {
+        let template = r#"<mjml><mj-body><mj-section><mj-column>
+  <mj-text>Hi</mj-text>
+  <mj-text font-family="Lato, 'Unknown', sans-serif">Hi</mj-text>
+  <mj-button font-family="Lato">Go</mj-button>
+</mj-column></mj-section></mj-body></mjml>"#;
+        let body = "mjml > mj-body > mj-section[0] > mj-column[0]";
+        assert_eq!(
+            resources(template, &Options::default()),
+            vec![
+                (
+                    ResourceKind::Font,
+                    "https://fonts.googleapis.com/css?family=Ubuntu:300,400,500,700".to_string(),
+                    format!("{body} > mj-text[0]")
+                ),
+                (
+                    ResourceKind::Font,
+                    "https://fonts.googleapis.com/css?family=Lato:300,400,500,700".to_string(),
+                    format!("{body} > mj-text[1]")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Rendering of a template as plain text, to build the `text/plain` alternative of an email.
+//!
+//! The content of the `mj-text` elements is converted from html and wrapped, the buttons
+//! become `Label: URL`, the images are replaced by their alternative text, the dividers by
+//! a rule and the social elements and navbar links by their links.
+//!
+//! ```rust
+//! use mrml::prelude::render::text::TextOptions;
+//!
+//! let template = r#"<mjml><mj-body>
+//!   <mj-section><mj-column>
+//!     <mj-image src="logo.png" alt="Acme" />
+//!     <mj-text><p>Hello <b>World</b>,</p><p>Your order has shipped.</p></mj-text>
+//!     <mj-button href="https://acme.com/orders/42">Track it</mj-button>
+//!   </mj-column></mj-section>
+//! </mj-body></mjml>"#;
+//! let root = mrml::parse(template).unwrap();
+//! assert_eq!(
+//!     root.render_text(&TextOptions::default()),
+//!     "Acme\n\nHello World,\n\nYour order has shipped.\n\nTrack it: https://acme.com/orders/42\n"
+//! );
+//! ```
+
+use super::{Header, Renderable};
+use crate::comment::Comment;
+use crate::helper::escape::decode_html;
+use crate::mj_accordion::{MjAccordion, MjAccordionChild};
+use crate::mj_body::{MjBody, MjBodyChild};
+use crate::mj_button::MjButton;
+use crate::mj_carousel::{MjCarousel, MjCarouselChild};
+use crate::mj_divider::MjDivider;
+use crate::mj_image::MjImage;
+use crate::mj_include::body::{MjIncludeBody, MjIncludeBodyChild};
+use crate::mj_navbar::{MjNavbar, MjNavbarChild};
+use crate::mj_raw::{MjRaw, MjRawChild};
+use crate::mj_social::{MjSocial, MjSocialChild};
+use crate::mj_social_element::network::SocialNetwork;
+use crate::mj_table::MjTable;
+use crate::mj_text::MjText;
+use crate::mjml::Mjml;
+use crate::node::Node;
+use crate::prelude::hash::Map;
+use crate::text::Text;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug)]
+pub struct TextOptions {
+    /// Maximum width of the lines, the words longer than it, like the urls, being kept whole.
+    pub width: usize,
+    /// Whether the content of the `mj-raw` elements is converted, instead of being skipped.
+    pub include_raw: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            width: 78,
+            include_raw: false,
+        }
+    }
+}
+
+/// Wraps a line at the given width, the items of a list being indented.
+fn wrap(line: &str, width: usize, output: &mut String) {
+    let indent = if line.starts_with("- ") { "  " } else { "" };
+    let mut current = 0;
+    for word in line.split(' ').filter(|word| !word.is_empty()) {
+        let len = word.chars().count();
+        if current > 0 && current + 1 + len > width {
+            output.push('\n');
+            output.push_str(indent);
+            current = indent.len();
+        } else if current > 0 {
+            output.push(' ');
+            current += 1;
+        }
+        output.push_str(word);
+        current += len;
+    }
+    output.push('\n');
+}
+
+struct TextRenderer<'h> {
+    header: Rc<RefCell<Header<'h>>>,
+    options: &'h TextOptions,
+    /// The blocks already wrapped, separated by a blank line.
+    output: String,
+    /// Content of the current block, a `\n` being a line break.
+    current: String,
+}
+
+impl<'h> TextRenderer<'h> {
+    /// Adds a text content, its references being decoded.
+    fn push_text(&mut self, value: &str) {
+        self.push_str(&decode_html(value));
+    }
+
+    /// Adds an already decoded value, like an attribute value, collapsing the whitespaces.
+    fn push_str(&mut self, value: &str) {
+        for c in value.chars() {
+            if c.is_whitespace() {
+                if !self.current.is_empty() && !self.current.ends_with([' ', '\n']) {
+                    self.current.push(' ');
+                }
+            } else {
+                self.current.push(c);
+            }
+        }
+    }
+
+    fn line_break(&mut self) {
+        let len = self.current.trim_end_matches(' ').len();
+        self.current.truncate(len);
+        if !self.current.is_empty() && !self.current.ends_with('\n') {
+            self.current.push('\n');
+        }
+    }
+
+    fn paragraph(&mut self) {
+        self.line_break();
+        if !self.current.is_empty() && !self.current.ends_with("\n\n") {
+            self.current.push('\n');
+        }
+    }
+
+    /// Wraps the current block and adds it to the output.
+    fn flush(&mut self) {
+        let current = std::mem::take(&mut self.current);
+        let mut block = String::new();
+        let mut blank = false;
+        for line in current.split('\n').map(str::trim) {
+            if line.is_empty() {
+                blank = !block.is_empty();
+                continue;
+            }
+            if blank {
+                block.push('\n');
+                blank = false;
+            }
+            wrap(line, self.options.width, &mut block);
+        }
+        if !block.is_empty() {
+            if !self.output.is_empty() {
+                self.output.push('\n');
+            }
+            self.output.push_str(&block);
+        }
+    }
+
+    /// Adds a block, flushing the current one.
+    fn block(&mut self, value: &str) {
+        self.flush();
+        self.current.push_str(value);
+        self.flush();
+    }
+
+    /// Renders the children as a single line, to be used as a label.
+    fn label<T: RenderText>(&mut self, children: &[T]) -> String {
+        let current = std::mem::take(&mut self.current);
+        children.iter().for_each(|child| child.render_text(self));
+        let label = std::mem::replace(&mut self.current, current);
+        label.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn link(&mut self, label: &str, href: Option<String>) {
+        match href {
+            Some(href) if !label.is_empty() => {
+                self.current.push_str(&format!("{label}: {href}\n"));
+            }
+            Some(href) => self.current.push_str(&format!("{href}\n")),
+            None if !label.is_empty() => self.current.push_str(&format!("{label}\n")),
+            None => {}
+        }
+    }
+
+    fn node<T: RenderText>(&mut self, tag: &str, attributes: &Map<String, String>, children: &[T]) {
+        match tag {
+            "head" | "script" | "style" | "title" => {}
+            "br" => self.line_break(),
+            "hr" => {
+                self.paragraph();
+                self.current.push_str(&"-".repeat(self.options.width));
+                self.paragraph();
+            }
+            "img" => {
+                if let Some(alt) = attributes.get("alt") {
+                    self.push_str(alt);
+                }
+            }
+            "a" => {
+                let start = self.current.len();
+                children.iter().for_each(|child| child.render_text(self));
+                // the children may have flushed the current block
+                let label = self.current.get(start..).unwrap_or_default().trim();
+                let label = label.strip_prefix("mailto:").unwrap_or(label);
+                if let Some(href) = attributes.get("href") {
+                    let target = href.strip_prefix("mailto:").unwrap_or(href);
+                    if !href.starts_with('#') && !href.is_empty() && target != label {
+                        self.push_str(" ");
+                        self.current.push_str(&format!("({href})"));
+                    }
+                }
+            }
+            "li" => {
+                self.line_break();
+                self.current.push_str("- ");
+                children.iter().for_each(|child| child.render_text(self));
+                self.line_break();
+            }
+            "td" | "th" => {
+                self.push_str(" ");
+                children.iter().for_each(|child| child.render_text(self));
+                self.push_str(" ");
+            }
+            "div" | "dd" | "dt" | "tr" => {
+                self.line_break();
+                children.iter().for_each(|child| child.render_text(self));
+                self.line_break();
+            }
+            "blockquote" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ol" | "p" | "pre"
+            | "table" | "ul" => {
+                self.paragraph();
+                children.iter().for_each(|child| child.render_text(self));
+                self.paragraph();
+            }
+            _ => children.iter().for_each(|child| child.render_text(self)),
+        }
+    }
+
+    fn content<T: RenderText>(&mut self, children: &[T]) {
+        self.flush();
+        children.iter().for_each(|child| child.render_text(self));
+        self.flush();
+    }
+}
+
+trait RenderText {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>);
+}
+
+impl RenderText for Comment {
+    fn render_text(&self, _renderer: &mut TextRenderer<'_>) {}
+}
+
+impl RenderText for Text {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.push_text(self.inner_str());
+    }
+}
+
+impl<T: RenderText> RenderText for Node<T> {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.node(&self.tag, &self.attributes, &self.children);
+    }
+}
+
+impl RenderText for MjRawChild {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        match self {
+            Self::Comment(elt) => elt.render_text(renderer),
+            Self::Node(elt) => elt.render_text(renderer),
+            Self::Text(elt) => elt.render_text(renderer),
+        }
+    }
+}
+
+impl RenderText for MjBodyChild {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        match self {
+            Self::Comment(elt) => elt.render_text(renderer),
+            Self::MjAccordion(elt) => elt.render_text(renderer),
+            Self::MjButton(elt) => elt.render_text(renderer),
+            Self::MjCarousel(elt) => elt.render_text(renderer),
+            Self::MjColumn(elt) => renderer.content(&elt.children),
+            Self::MjDivider(elt) => elt.render_text(renderer),
+            Self::MjGroup(elt) => renderer.content(&elt.children),
+            Self::MjHero(elt) => renderer.content(&elt.children),
+            Self::MjInclude(elt) => elt.render_text(renderer),
+            Self::MjImage(elt) => elt.render_text(renderer),
+            Self::MjNavbar(elt) => elt.render_text(renderer),
+            Self::MjRaw(elt) => elt.render_text(renderer),
+            Self::MjSection(elt) => renderer.content(&elt.children),
+            Self::MjSocial(elt) => elt.render_text(renderer),
+            Self::MjSpacer(_) => {}
+            Self::MjTable(elt) => elt.render_text(renderer),
+            Self::MjText(elt) => elt.render_text(renderer),
+            Self::MjWrapper(elt) => renderer.content(&elt.children),
+            Self::Node(elt) => elt.render_text(renderer),
+            Self::Text(elt) => elt.render_text(renderer),
+        }
+    }
+}
+
+impl RenderText for MjIncludeBodyChild {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        match self {
+            Self::Comment(elt) => elt.render_text(renderer),
+            Self::MjAccordion(elt) => elt.render_text(renderer),
+            Self::MjButton(elt) => elt.render_text(renderer),
+            Self::MjCarousel(elt) => elt.render_text(renderer),
+            Self::MjColumn(elt) => renderer.content(&elt.children),
+            Self::MjDivider(elt) => elt.render_text(renderer),
+            Self::MjGroup(elt) => renderer.content(&elt.children),
+            Self::MjHero(elt) => renderer.content(&elt.children),
+            Self::MjImage(elt) => elt.render_text(renderer),
+            Self::MjNavbar(elt) => elt.render_text(renderer),
+            Self::MjRaw(elt) => elt.render_text(renderer),
+            Self::MjSection(elt) => renderer.content(&elt.children),
+            Self::MjSocial(elt) => elt.render_text(renderer),
+            Self::MjSpacer(_) => {}
+            Self::MjTable(elt) => elt.render_text(renderer),
+            Self::MjText(elt) => elt.render_text(renderer),
+            Self::MjWrapper(elt) => renderer.content(&elt.children),
+            Self::Node(elt) => elt.render_text(renderer),
+            Self::Text(elt) => elt.render_text(renderer),
+        }
+    }
+}
+
+impl RenderText for MjIncludeBody {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.content(&self.children);
+    }
+}
+
+impl RenderText for MjText {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.content(&self.children);
+    }
+}
+
+impl RenderText for MjTable {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.content(&self.children);
+    }
+}
+
+impl RenderText for MjRaw {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        if renderer.options.include_raw {
+            renderer.content(&self.children);
+        }
+    }
+}
+
+impl RenderText for MjButton {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.flush();
+        let label = renderer.label(&self.children);
+        let href = self.renderer(renderer.header.clone()).attribute("href");
+        renderer.link(&label, href);
+        renderer.flush();
+    }
+}
+
+impl RenderText for MjImage {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        let alt = self.renderer(renderer.header.clone()).attribute("alt");
+        if let Some(alt) = alt {
+            renderer.block(&alt);
+        }
+    }
+}
+
+impl RenderText for MjDivider {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        let rule = "-".repeat(renderer.options.width);
+        renderer.block(&rule);
+    }
+}
+
+impl RenderText for MjCarousel {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.flush();
+        for child in self.children.iter() {
+            if let MjCarouselChild::MjCarouselImage(image) = child {
+                let image = image.renderer(renderer.header.clone());
+                if let Some(alt) = image.attribute("alt") {
+                    renderer.push_str(&alt);
+                    renderer.link("", image.attribute("href"));
+                    renderer.line_break();
+                }
+            }
+        }
+        renderer.flush();
+    }
+}
+
+impl RenderText for MjAccordion {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        for child in self.children.iter() {
+            if let MjAccordionChild::MjAccordionElement(element) = child {
+                if let Some(ref title) = element.children.title {
+                    let label = renderer.label(&title.children);
+                    renderer.block(&label);
+                }
+                if let Some(ref text) = element.children.text {
+                    renderer.content(&text.children);
+                }
+            }
+        }
+    }
+}
+
+impl RenderText for MjNavbar {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.flush();
+        let base_url = self.renderer(renderer.header.clone()).attribute("base-url");
+        for child in self.children.iter() {
+            if let MjNavbarChild::MjNavbarLink(link) = child {
+                let label = renderer.label(&link.children);
+                let href = link
+                    .renderer(renderer.header.clone())
+                    .attribute("href")
+                    .map(|href| match base_url {
+                        Some(ref base_url) => format!("{base_url}{href}"),
+                        None => href,
+                    });
+                renderer.link(&label, href);
+            }
+        }
+        renderer.flush();
+    }
+}
+
+impl RenderText for MjSocial {
+    fn render_text(&self, renderer: &mut TextRenderer<'_>) {
+        renderer.flush();
+        for child in self.children.iter() {
+            if let MjSocialChild::MjSocialElement(element) = child {
+                let attributes = element.renderer(renderer.header.clone());
+                let name = attributes.attribute("name");
+                let href = attributes.attribute("href").map(|href| {
+                    name.as_deref()
+                        .and_then(SocialNetwork::find)
+                        .and_then(|network| network.share_url(&href))
+                        .unwrap_or(href)
+                });
+                let mut label = renderer.label(&element.children);
+                if label.is_empty() {
+                    label = name
+                        .map(|name| name.trim_end_matches("-noshare").to_string())
+                        .unwrap_or_default();
+                }
+                renderer.link(&label, href);
+            }
+        }
+        renderer.flush();
+    }
+}
+
+impl Mjml {
+    /// Renders the template as plain text. The attributes defined in `mj-attributes` are
+    /// taken into account, like when rendering the html.
+    pub fn render_text(&self, opts: &TextOptions) -> String {
+        let header = Rc::new(RefCell::new(Header::new(&self.children.head)));
+        let mut renderer = TextRenderer {
+            header,
+            options: opts,
+            output: String::default(),
+            current: String::default(),
+        };
+        if let Some(MjBody { children, .. }) = self.body() {
+            renderer.content(children);
+        }
+        renderer.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextOptions;
+
+    fn render(body: &str, options: &TextOptions) -> String {
+        let template = format!("<mjml><mj-body><mj-section><mj-column>{body}</mj-column></mj-section></mj-body></mjml>");
+        crate::mjml::Mjml::parse(template)
+            .unwrap()
+            .render_text(options)
+    }
+
+    #[test]
+    fn should_wrap_text() {
+        let options = TextOptions {
+            width: 20,
+            ..Default::default()
+        };
+        let result = render(
+            r#"<mj-text>
+              <h1>Welcome&nbsp;aboard</h1>
+              <p>Lorem ipsum dolor sit amet, consectetur adipiscing&amp;elit.<br/>Next line</p>
+              <ul><li>first item of the list</li><li>second</li></ul>
+              <p>See <a href="https://acme.com/a/very/long/url">the docs</a> or <a href="https://acme.com">https://acme.com</a></p>
+            </mj-text>"#,
+            &options,
+        );
+        similar_asserts::assert_eq!(
+            result,
+            r#"Welcome aboard
+
+Lorem ipsum dolor
+sit amet,
+consectetur
+adipiscing&elit.
+Next line
+
+- first item of the
+  list
+- second
+
+See the docs
+(https://acme.com/a/very/long/url)
+or https://acme.com
+"#
+        );
+    }
+
+    #[test]
+    fn should_render_components() {
+        let result = render(
+            r#"<mj-image src="logo.png" alt="Acme &amp; Co" />
+            <mj-image src="spacer.png" />
+            <mj-button href="https://acme.com"><b>Sign</b> in</mj-button>
+            <mj-divider />
+            <mj-social>
+              <mj-social-element name="facebook" href="https://acme.com">Share</mj-social-element>
+              <mj-social-element name="github-noshare" href="https://github.com/acme" />
+            </mj-social>
+            <mj-navbar base-url="https://acme.com">
+              <mj-navbar-link href="/blog">Blog</mj-navbar-link>
+            </mj-navbar>
+            <mj-raw><p>Raw content</p></mj-raw>
+            <mj-table><tr><th>Item</th><th>Price</th></tr><tr><td>Book</td><td>10€</td></tr></mj-table>"#,
+            &TextOptions {
+                width: 40,
+                ..Default::default()
+            },
+        );
+        similar_asserts::assert_eq!(
+            result,
+            r#"Acme & Co
+
+Sign in: https://acme.com
+
+----------------------------------------
+
+Share:
+https://www.facebook.com/sharer/sharer.php?u=https://acme.com
+github: https://github.com/acme
+
+Blog: https://acme.com/blog
+
+Item Price
+Book 10€
+"#
+        );
+    }
+
+    #[test]
+    fn should_include_raw_when_enabled() {
+        let options = TextOptions {
+            include_raw: true,
+            ..Default::default()
+        };
+        let result = render("<mj-raw><p>Raw <span>content</span></p></mj-raw>", &options);
+        assert_eq!(result, "Raw content\n");
+    }
+
+    #[test]
+    fn should_use_head_attributes() {
+        let template = r#"<mjml>
+          <mj-head><mj-attributes><mj-class name="logo" alt="Acme" /></mj-attributes></mj-head>
+          <mj-body><mj-image mj-class="logo" src="logo.png" /></mj-body>
+        </mjml>"#;
+        let root = crate::mjml::Mjml::parse(template).unwrap();
+        assert_eq!(root.render_text(&TextOptions::default()), "Acme\n");
+    }
+
+    #[test]
+    fn should_not_decode_attributes_twice() {
+        let result = render(
+            r#"<mj-image src="logo.png" alt="&amp;lt;tag&amp;gt;" />
+            <mj-carousel><mj-carousel-image src="a.png" alt="&amp;amp;" /></mj-carousel>
+            <mj-text><img src="b.png" alt="&amp;nbsp;" /></mj-text>"#,
+            &TextOptions::default(),
+        );
+        assert_eq!(result, "&lt;tag&gt;\n\n&amp;\n\n&nbsp;\n");
+    }
+
+    #[test]
+    fn should_render_components_inside_links() {
+        let result = render(
+            r#"<p>Intro <a href="https://x"><mj-text>Hi</mj-text></a></p>"#,
+            &TextOptions::default(),
+        );
+        assert_eq!(result, "Intro\n\nHi\n\n(https://x)\n");
+    }
+}
@@ -0,0 +1,375 @@
+//! Module containing the schema validation of a template.
+//!
+//! Like the [mjml validator](https://github.com/mjmlio/mjml/tree/master/packages/mjml-validator),
+//! every component declares the elements it can be a child of and the attributes it accepts,
+//! with the grammar their values should follow. Validating a template doesn't stop on the first
+//! issue, it lists every [`Warning`] found in the tree.
+//!
+//! ```rust
+//! use mrml::prelude::validate::Validate;
+//!
+//! let root = mrml::parse("<mjml><mj-body><mj-column /></mj-body></mjml>").unwrap();
+//! let warnings = root.validate();
+//! assert_eq!(warnings.len(), 1);
+//! assert_eq!(
+//!     warnings[0].to_string(),
+//!     "mjml > mj-body > mj-column[0]: mj-column cannot be used inside mj-body"
+//! );
+//! ```
+
+use crate::helper::size::Size;
+use crate::helper::sort::sort_by_key;
+use crate::prelude::hash::Map;
+use std::convert::TryFrom;
+
+/// Attributes that are accepted by every component.
+const GLOBAL_ATTRIBUTES: [&str; 2] = ["css-class", "mj-class"];
+
+/// Grammar that the value of an attribute should follow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grammar {
+    /// Any value is accepted.
+    String,
+    /// `true` or `false`.
+    Boolean,
+    /// Hexadecimal, functional (like `rgb(0, 0, 0)`) or named color.
+    Color,
+    Integer,
+    /// One of the given keywords.
+    Enum(&'static [&'static str]),
+    /// Between one and the given number of space separated sizes, using one of the given units.
+    /// An empty unit stands for a number without unit.
+    Unit(&'static [&'static str], usize),
+}
+
+impl Grammar {
+    pub const ALIGN: Self = Self::Enum(&["left", "center", "right"]);
+    pub const DIRECTION: Self = Self::Enum(&["ltr", "rtl"]);
+    pub const VERTICAL_ALIGN: Self = Self::Enum(&["top", "bottom", "middle"]);
+    pub const PIXEL: Self = Self::Unit(&["px"], 1);
+    pub const SIZE: Self = Self::Unit(&["px", "%"], 1);
+    pub const SPACING: Self = Self::Unit(&["px", "%"], 4);
+    pub const LINE_HEIGHT: Self = Self::Unit(&["px", "%", ""], 1);
+
+    fn is_color(value: &str) -> bool {
+        if let Some(hex) = value.strip_prefix('#') {
+            matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        } else if let Some((name, rest)) = value.split_once('(') {
+            matches!(name, "rgb" | "rgba" | "hsl" | "hsla") && rest.ends_with(')')
+        } else {
+            !value.is_empty() && value.chars().all(|c| c.is_ascii_alphabetic())
+        }
+    }
+
+    fn is_unit(value: &str, units: &[&str]) -> bool {
+        match Size::try_from(value) {
+            Ok(Size::Pixel(_)) => units.contains(&"px"),
+            Ok(Size::Percent(_)) => units.contains(&"%"),
+            // a zero doesn't need a unit
+            Ok(Size::Raw(value)) => units.contains(&"") || value == 0.0,
+            Err(_) => false,
+        }
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::String => true,
+            Self::Boolean => matches!(value, "true" | "false"),
+            Self::Color => Self::is_color(value.trim()),
+            Self::Integer => value.parse::<i64>().is_ok(),
+            Self::Enum(items) => items.contains(&value),
+            Self::Unit(units, max) => {
+                let sections = value.split_whitespace().collect::<Vec<_>>();
+                !sections.is_empty()
+                    && sections.len() <= *max
+                    && sections.iter().all(|item| Self::is_unit(item, units))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Grammar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String => f.write_str("string"),
+            Self::Boolean => f.write_str("boolean"),
+            Self::Color => f.write_str("color"),
+            Self::Integer => f.write_str("integer"),
+            Self::Enum(items) => write!(f, "enum({})", items.join(",")),
+            Self::Unit(units, 1) => write!(f, "unit({})", units.join(",")),
+            Self::Unit(units, max) => write!(f, "unit({}){{1,{max}}}", units.join(",")),
+        }
+    }
+}
+
+/// Validation rules of a component.
+#[derive(Debug)]
+pub struct Rule {
+    pub tag: &'static str,
+    /// Tags of the elements the component can be a child of, an empty list meaning anywhere.
+    pub parents: &'static [&'static str],
+    /// Accepted attributes, on top of `css-class` and `mj-class`.
+    pub attributes: &'static [(&'static str, Grammar)],
+}
+
+/// Step of the path leading to an element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathItem {
+    pub tag: String,
+    /// Position of the element amongst its siblings, if it's part of a list.
+    pub index: Option<usize>,
+}
+
+impl std::fmt::Display for PathItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{}[{index}]", self.tag),
+            None => f.write_str(&self.tag),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    InvalidParent {
+        tag: String,
+        parent: String,
+    },
+    UnknownAttribute {
+        tag: String,
+        name: String,
+    },
+    InvalidAttributeValue {
+        name: String,
+        value: String,
+        expected: Grammar,
+    },
+}
+
+impl std::fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParent { tag, parent } => {
+                write!(f, "{tag} cannot be used inside {parent}")
+            }
+            Self::UnknownAttribute { tag, name } => {
+                write!(f, "attribute {name:?} is illegal for {tag}")
+            }
+            Self::InvalidAttributeValue {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "attribute {name:?} has invalid value {value:?}, expected {expected}"
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// Path from the root of the template to the element.
+    pub path: Vec<PathItem>,
+    pub kind: WarningKind,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, item) in self.path.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" > ")?;
+            }
+            item.fmt(f)?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+/// Keeps track of the position in the tree and collects the warnings.
+#[derive(Debug, Default)]
+pub struct Validator {
+    path: Vec<PathItem>,
+    /// Tags of the elements containing the current one, `mj-include` excluded.
+    parents: Vec<String>,
+    /// Index of the next element, when visiting a list of children.
+    index: Option<usize>,
+    warnings: Vec<Warning>,
+}
+
+impl Validator {
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    pub fn into_warnings(self) -> Vec<Warning> {
+        self.warnings
+    }
+
+    pub fn warn(&mut self, kind: WarningKind) {
+        self.warnings.push(Warning {
+            path: self.path.clone(),
+            kind,
+        });
+    }
+
+    fn enter(&mut self, tag: &str) {
+        let index = self.index.take();
+        self.path.push(PathItem {
+            tag: tag.to_string(),
+            index,
+        });
+    }
+
+    fn leave(&mut self) {
+        self.path.pop();
+    }
+
+    /// Visits an element, checking its parent and running the given function
+    /// to validate its attributes and children.
+    pub fn element<F>(&mut self, tag: &str, parents: &[&str], func: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.enter(tag);
+        if let Some(parent) = self.parents.last() {
+            if !parents.is_empty() && !parents.contains(&parent.as_str()) {
+                let parent = parent.clone();
+                self.warn(WarningKind::InvalidParent {
+                    tag: tag.to_string(),
+                    parent,
+                });
+            }
+        }
+        self.parents.push(tag.to_string());
+        func(self);
+        self.parents.pop();
+        self.leave();
+    }
+
+    /// Visits an element that doesn't exist once rendered, like `mj-include`.
+    /// Its children are validated as if they were children of its parent.
+    pub fn transparent_element<F>(&mut self, tag: &str, func: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.enter(tag);
+        func(self);
+        self.leave();
+    }
+
+    /// Visits a component, checking its parent and its attributes against the rule.
+    pub fn component<F>(&mut self, rule: &Rule, attributes: &Map<String, String>, func: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.element(rule.tag, rule.parents, |validator| {
+            validator.attributes(rule, attributes);
+            func(validator);
+        });
+    }
+
+    pub fn attributes(&mut self, rule: &Rule, attributes: &Map<String, String>) {
+        let mut entries = attributes.iter().collect::<Vec<_>>();
+        entries.sort_by(sort_by_key);
+        for (name, value) in entries {
+            if GLOBAL_ATTRIBUTES.contains(&name.as_str()) {
+                continue;
+            }
+            let grammar = rule
+                .attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .or_else(|| {
+                    // the colors accept a variant for the dark color scheme, like `dark-color`
+                    let base = name.strip_prefix("dark-")?;
+                    rule.attributes
+                        .iter()
+                        .find(|(key, grammar)| *key == base && *grammar == Grammar::Color)
+                })
+                .map(|(_, grammar)| *grammar);
+            match grammar {
+                Some(grammar) => self.attribute(name, value, grammar),
+                None => self.warn(WarningKind::UnknownAttribute {
+                    tag: rule.tag.to_string(),
+                    name: name.clone(),
+                }),
+            }
+        }
+    }
+
+    pub fn attribute(&mut self, name: &str, value: &str, grammar: Grammar) {
+        if !grammar.matches(value) {
+            self.warn(WarningKind::InvalidAttributeValue {
+                name: name.to_string(),
+                value: value.to_string(),
+                expected: grammar,
+            });
+        }
+    }
+
+    pub fn children<T: Validate>(&mut self, children: &[T]) {
+        for (index, child) in children.iter().enumerate() {
+            self.index = Some(index);
+            child.validate_with(self);
+        }
+        self.index = None;
+    }
+}
+
+pub trait Validate {
+    fn validate_with(&self, validator: &mut Validator);
+
+    fn validate(&self) -> Vec<Warning> {
+        let mut validator = Validator::default();
+        self.validate_with(&mut validator);
+        validator.into_warnings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grammar;
+
+    #[test]
+    fn should_match_colors() {
+        assert!(Grammar::Color.matches("#fff"));
+        assert!(Grammar::Color.matches("#A0b1C2"));
+        assert!(Grammar::Color.matches("rgba(0, 0, 0, 0.5)"));
+        assert!(Grammar::Color.matches("transparent"));
+        assert!(!Grammar::Color.matches("#ffff0"));
+        assert!(!Grammar::Color.matches("12px"));
+        assert!(!Grammar::Color.matches("rgb(0,0,0"));
+    }
+
+    #[test]
+    fn should_match_units() {
+        assert!(Grammar::PIXEL.matches("12px"));
+        assert!(Grammar::PIXEL.matches("0"));
+        assert!(!Grammar::PIXEL.matches("12%"));
+        assert!(!Grammar::PIXEL.matches("12"));
+        assert!(Grammar::SIZE.matches("50%"));
+        assert!(Grammar::LINE_HEIGHT.matches("1.5"));
+        assert!(Grammar::SPACING.matches("10px 5% 0 2px"));
+        assert!(!Grammar::SPACING.matches("10px 5% 0 2px 1px"));
+        assert!(!Grammar::SPACING.matches(""));
+        assert!(!Grammar::SPACING.matches("auto"));
+    }
+
+    #[test]
+    fn should_match_keywords() {
+        assert!(Grammar::ALIGN.matches("center"));
+        assert!(!Grammar::ALIGN.matches("middle"));
+        assert!(Grammar::Boolean.matches("true"));
+        assert!(!Grammar::Boolean.matches("yes"));
+        assert!(Grammar::Integer.matches("-4"));
+        assert!(!Grammar::Integer.matches("4.2"));
+    }
+
+    #[test]
+    fn should_display_grammar() {
+        assert_eq!(Grammar::ALIGN.to_string(), "enum(left,center,right)");
+        assert_eq!(Grammar::PIXEL.to_string(), "unit(px)");
+        assert_eq!(Grammar::SPACING.to_string(), "unit(px,%){1,4}");
+    }
+}
@@ -4,8 +4,10 @@ mod json;
 mod print;
 #[cfg(feature = "render")]
 mod render;
+#[cfg(feature = "validate")]
+mod validate;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Text(String);
 
 impl Text {
@@ -1,13 +1,15 @@
 use super::Text;
+use crate::helper::escape::escape_text;
 use crate::prelude::print::{self, Print};
 use crate::print_display;
 
 impl Print for Text {
     fn print(&self, pretty: bool, level: usize, indent_size: usize) -> String {
+        let content = escape_text(&self.0).into_owned();
         if pretty {
-            print::indent(level, indent_size, self.0.clone())
+            print::indent(level, indent_size, content)
         } else {
-            self.0.clone()
+            content
         }
     }
 }
@@ -23,4 +25,10 @@ mod tests {
         let item = crate::text::Text::from("Hello World");
         assert_eq!("Hello World", item.dense_print());
     }
+
+    #[test]
+    fn should_escape() {
+        let item = crate::text::Text::from("1 < 2 &amp; 3");
+        assert_eq!("1 &lt; 2 &amp; 3", item.dense_print());
+    }
 }
@@ -0,0 +1,6 @@
+use super::Text;
+use crate::prelude::validate::{Validate, Validator};
+
+impl Validate for Text {
+    fn validate_with(&self, _validator: &mut Validator) {}
+}